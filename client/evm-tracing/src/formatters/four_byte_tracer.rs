@@ -0,0 +1,12 @@
+use crate::{listeners::four_byte::Listener, types::single::TransactionTrace};
+
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = TransactionTrace;
+
+	fn format(listener: Listener) -> Option<TransactionTrace> {
+		Some(TransactionTrace::FourByte(listener.selectors))
+	}
+}
@@ -16,12 +16,18 @@
 
 pub mod blockscout;
 pub mod call_tracer;
+pub mod four_byte_tracer;
+pub mod prestate_tracer;
 pub mod raw;
+pub mod state_diff;
 pub mod trace_filter;
 
 pub use blockscout::Formatter as Blockscout;
 pub use call_tracer::Formatter as CallTracer;
+pub use four_byte_tracer::Formatter as FourByteTracer;
+pub use prestate_tracer::{DiffFormatter as PrestateDiffTracer, Formatter as PrestateTracer};
 pub use raw::Formatter as Raw;
+pub use state_diff::Formatter as StateDiff;
 pub use trace_filter::Formatter as TraceFilter;
 
 use evm_tracing_events::Listener;
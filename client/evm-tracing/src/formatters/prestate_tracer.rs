@@ -0,0 +1,203 @@
+use crate::{
+	listeners::prestate::{AccountState, Listener},
+	types::{serialization::option_bytes_0x_serialize, single::TransactionTrace},
+};
+use ethereum_types::{H160, H256, U256};
+use parity_scale_codec::{Decode, Encode};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Account entry of geth's `prestateTracer` output. Fields which are not set are omitted.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
+pub struct PrestateAccount {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "option_bytes_0x_serialize")]
+	pub code: Option<Vec<u8>>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<H256, H256>,
+}
+
+impl PrestateAccount {
+	/// Full account, zero nonce and empty code being omitted like geth does.
+	fn new(state: AccountState, storage: BTreeMap<H256, H256>) -> Self {
+		Self {
+			balance: Some(state.balance),
+			nonce: (!state.nonce.is_zero()).then(|| state.nonce.low_u64()),
+			code: (!state.code.is_empty()).then_some(state.code),
+			storage,
+		}
+	}
+}
+
+/// `prestateTracer` default mode: state of every touched account before the transaction.
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = TransactionTrace;
+
+	fn format(listener: Listener) -> Option<TransactionTrace> {
+		Some(TransactionTrace::Prestate(
+			listener
+				.pre
+				.into_iter()
+				.map(|(address, state)| {
+					let storage = state.storage.clone();
+					(address, PrestateAccount::new(state, storage))
+				})
+				.collect(),
+		))
+	}
+}
+
+/// `prestateTracer` diff mode: accounts modified by the transaction, with their state before it
+/// and the fields which changed after it.
+pub struct DiffFormatter;
+
+impl super::ResponseFormatter for DiffFormatter {
+	type Listener = Listener;
+	type Response = TransactionTrace;
+
+	fn format(listener: Listener) -> Option<TransactionTrace> {
+		let mut pre: BTreeMap<H160, PrestateAccount> = BTreeMap::new();
+		let mut post: BTreeMap<H160, PrestateAccount> = BTreeMap::new();
+
+		for (address, before) in listener.pre.into_iter() {
+			let after = listener.post.get(&address).cloned().unwrap_or_default();
+
+			let mut changed = PrestateAccount::default();
+			if before.balance != after.balance {
+				changed.balance = Some(after.balance);
+			}
+			if before.nonce != after.nonce {
+				changed.nonce = Some(after.nonce.low_u64());
+			}
+			let code_changed = before.code != after.code;
+			if code_changed && !after.code.is_empty() {
+				changed.code = Some(after.code);
+			}
+
+			// Only the modified slots are reported, cleared ones being omitted after the
+			// transaction.
+			let mut modified_storage = BTreeMap::new();
+			for (slot, value) in before.storage.iter() {
+				let new_value = after.storage.get(slot).copied().unwrap_or_default();
+				if new_value != *value {
+					modified_storage.insert(*slot, *value);
+					if !new_value.is_zero() {
+						changed.storage.insert(*slot, new_value);
+					}
+				}
+			}
+
+			let modified = changed.balance.is_some() ||
+				changed.nonce.is_some() ||
+				code_changed || !modified_storage.is_empty();
+			if !modified {
+				continue
+			}
+
+			if !(listener.created.contains(&address) && before.is_empty()) {
+				pre.insert(address, PrestateAccount::new(before, modified_storage));
+			}
+			if !listener.destroyed.contains(&address) {
+				post.insert(address, changed);
+			}
+		}
+
+		Some(TransactionTrace::PrestateDiff { pre, post })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::formatters::ResponseFormatter;
+
+	fn state(balance: u64, nonce: u64, storage: &[(u64, u64)]) -> AccountState {
+		AccountState {
+			balance: balance.into(),
+			nonce: nonce.into(),
+			code: vec![],
+			storage: storage
+				.iter()
+				.map(|(slot, value)| (H256::from_low_u64_be(*slot), H256::from_low_u64_be(*value)))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn diff_mode_reports_modified_accounts_only() {
+		let sender = H160::repeat_byte(0x01);
+		let contract = H160::repeat_byte(0x02);
+		let untouched = H160::repeat_byte(0x03);
+
+		let mut listener = Listener::default();
+		listener.pre.insert(sender, state(1_000, 4, &[]));
+		listener.post.insert(sender, state(900, 5, &[]));
+		listener.pre.insert(contract, state(0, 1, &[(1, 7), (2, 8), (3, 9)]));
+		listener.post.insert(contract, state(0, 1, &[(1, 7), (2, 0), (3, 10)]));
+		listener.pre.insert(untouched, state(5, 0, &[]));
+		listener.post.insert(untouched, state(5, 0, &[]));
+
+		let Some(TransactionTrace::PrestateDiff { pre, post }) = DiffFormatter::format(listener)
+		else {
+			panic!("diff mode output expected")
+		};
+
+		assert_eq!(pre.len(), 2);
+		assert_eq!(post.len(), 2);
+		assert_eq!(
+			pre[&sender],
+			PrestateAccount { balance: Some(1_000.into()), nonce: Some(4), ..Default::default() }
+		);
+		assert_eq!(
+			post[&sender],
+			PrestateAccount { balance: Some(900.into()), nonce: Some(5), ..Default::default() }
+		);
+		assert_eq!(
+			pre[&contract].storage,
+			[(2, 8), (3, 9)]
+				.iter()
+				.map(|(slot, value)| (H256::from_low_u64_be(*slot), H256::from_low_u64_be(*value)))
+				.collect::<BTreeMap<_, _>>()
+		);
+		assert_eq!(
+			post[&contract],
+			PrestateAccount {
+				storage: [(H256::from_low_u64_be(3), H256::from_low_u64_be(10))].into(),
+				..Default::default()
+			}
+		);
+	}
+
+	#[test]
+	fn diff_mode_omits_created_and_destroyed_sides() {
+		let created = H160::repeat_byte(0x01);
+		let destroyed = H160::repeat_byte(0x02);
+
+		let mut listener = Listener::default();
+		listener.created.insert(created);
+		listener.destroyed.insert(destroyed);
+		listener.pre.insert(created, state(0, 0, &[]));
+		listener
+			.post
+			.insert(created, AccountState { code: vec![0x60], ..state(0, 1, &[]) });
+		listener
+			.pre
+			.insert(destroyed, AccountState { code: vec![0x60], ..state(3, 1, &[]) });
+		listener.post.insert(destroyed, state(0, 0, &[]));
+
+		let Some(TransactionTrace::PrestateDiff { pre, post }) = DiffFormatter::format(listener)
+		else {
+			panic!("diff mode output expected")
+		};
+
+		assert_eq!(pre.keys().collect::<Vec<_>>(), vec![&destroyed]);
+		assert_eq!(post.keys().collect::<Vec<_>>(), vec![&created]);
+		assert_eq!(post[&created].code, Some(vec![0x60]));
+	}
+}
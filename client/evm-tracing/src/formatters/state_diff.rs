@@ -0,0 +1,178 @@
+use crate::{
+	listeners::prestate::{AccountState, Listener},
+	types::block::{AccountDiff, Bytes, Diff, StateDiff},
+};
+
+impl AccountDiff {
+	/// Account which didn't exist before the transaction. Cleared slots are omitted.
+	fn born(state: AccountState) -> Self {
+		Self {
+			balance: Diff::Born(state.balance),
+			code: Diff::Born(Bytes(state.code)),
+			nonce: Diff::Born(state.nonce),
+			storage: state
+				.storage
+				.into_iter()
+				.filter(|(_, value)| !value.is_zero())
+				.map(|(slot, value)| (slot, Diff::Born(value)))
+				.collect(),
+		}
+	}
+
+	/// Account which doesn't exist anymore after the transaction. Cleared slots are omitted.
+	fn died(state: AccountState) -> Self {
+		Self {
+			balance: Diff::Died(state.balance),
+			code: Diff::Died(Bytes(state.code)),
+			nonce: Diff::Died(state.nonce),
+			storage: state
+				.storage
+				.into_iter()
+				.filter(|(_, value)| !value.is_zero())
+				.map(|(slot, value)| (slot, Diff::Died(value)))
+				.collect(),
+		}
+	}
+
+	/// Account existing before and after the transaction. Only the modified slots are reported.
+	fn changed(before: AccountState, after: AccountState) -> Self {
+		let storage = before
+			.storage
+			.into_iter()
+			.filter_map(|(slot, value)| {
+				let new_value = after.storage.get(&slot).copied().unwrap_or_default();
+				(new_value != value).then_some((slot, Diff::Changed { from: value, to: new_value }))
+			})
+			.collect();
+
+		Self {
+			balance: Diff::new(before.balance, after.balance),
+			code: Diff::new(Bytes(before.code), Bytes(after.code)),
+			nonce: Diff::new(before.nonce, after.nonce),
+			storage,
+		}
+	}
+
+	fn is_unchanged(&self) -> bool {
+		self.balance.is_same() &&
+			self.code.is_same() &&
+			self.nonce.is_same() &&
+			self.storage.is_empty()
+	}
+}
+
+/// Parity's `stateDiff` of a transaction, from the state of the touched accounts before and after
+/// it.
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = StateDiff;
+
+	fn format(listener: Listener) -> Option<StateDiff> {
+		let mut diff = StateDiff::new();
+
+		for (address, before) in listener.pre.into_iter() {
+			let after = listener.post.get(&address).cloned().unwrap_or_default();
+
+			let existed = !before.is_empty() && !listener.created.contains(&address);
+			let exists = !after.is_empty() && !listener.destroyed.contains(&address);
+
+			let account = match (existed, exists) {
+				(false, false) => continue,
+				(false, true) => AccountDiff::born(after),
+				(true, false) => AccountDiff::died(before),
+				(true, true) => AccountDiff::changed(before, after),
+			};
+			if !account.is_unchanged() {
+				diff.insert(address, account);
+			}
+		}
+
+		Some(diff)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::formatters::ResponseFormatter;
+	use ethereum_types::{H160, H256};
+
+	fn state(balance: u64, nonce: u64, storage: &[(u64, u64)]) -> AccountState {
+		AccountState {
+			balance: balance.into(),
+			nonce: nonce.into(),
+			code: vec![],
+			storage: storage
+				.iter()
+				.map(|(slot, value)| (H256::from_low_u64_be(*slot), H256::from_low_u64_be(*value)))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn reports_modified_fields_and_slots() {
+		let sender = H160::repeat_byte(0x01);
+		let contract = H160::repeat_byte(0x02);
+		let untouched = H160::repeat_byte(0x03);
+
+		let mut listener = Listener::default();
+		listener.pre.insert(sender, state(1_000, 4, &[]));
+		listener.post.insert(sender, state(900, 5, &[]));
+		listener.pre.insert(contract, state(0, 1, &[(1, 7), (2, 8)]));
+		listener.post.insert(contract, state(0, 1, &[(1, 7), (2, 0)]));
+		listener.pre.insert(untouched, state(5, 0, &[]));
+		listener.post.insert(untouched, state(5, 0, &[]));
+
+		let diff = Formatter::format(listener).unwrap();
+
+		assert_eq!(diff.keys().collect::<Vec<_>>(), vec![&sender, &contract]);
+		assert_eq!(diff[&sender].balance, Diff::Changed { from: 1_000.into(), to: 900.into() });
+		assert_eq!(diff[&sender].nonce, Diff::Changed { from: 4.into(), to: 5.into() });
+		assert_eq!(diff[&contract].balance, Diff::Same);
+		assert_eq!(
+			diff[&contract].storage,
+			[(
+				H256::from_low_u64_be(2),
+				Diff::Changed { from: H256::from_low_u64_be(8), to: H256::zero() }
+			)]
+			.into()
+		);
+	}
+
+	#[test]
+	fn reports_born_and_died_accounts() {
+		let created = H160::repeat_byte(0x01);
+		let destroyed = H160::repeat_byte(0x02);
+
+		let mut listener = Listener::default();
+		listener.created.insert(created);
+		listener.destroyed.insert(destroyed);
+		listener.pre.insert(created, state(0, 0, &[]));
+		listener
+			.post
+			.insert(created, AccountState { code: vec![0x60], ..state(0, 1, &[(1, 2)]) });
+		listener
+			.pre
+			.insert(destroyed, AccountState { code: vec![0x60], ..state(3, 1, &[]) });
+		listener.post.insert(destroyed, state(0, 1, &[]));
+
+		let diff = Formatter::format(listener).unwrap();
+
+		assert_eq!(diff[&created].code, Diff::Born(Bytes(vec![0x60])));
+		assert_eq!(
+			diff[&created].storage,
+			[(H256::from_low_u64_be(1), Diff::Born(H256::from_low_u64_be(2)))].into()
+		);
+		assert_eq!(diff[&destroyed].balance, Diff::Died(3.into()));
+		assert_eq!(
+			serde_json::to_value(&diff[&destroyed].nonce).unwrap(),
+			serde_json::json!({ "-": "0x1" })
+		);
+		assert_eq!(
+			serde_json::to_value(&diff[&created].balance).unwrap(),
+			serde_json::json!({ "+": "0x0" })
+		);
+	}
+}
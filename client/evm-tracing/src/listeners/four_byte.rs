@@ -0,0 +1,132 @@
+//! Listener counting the function selectors called by a transaction, as geth's `4byteTracer`.
+
+use ethereum_types::H160;
+use std::collections::BTreeMap;
+
+use evm_tracing_events::{Event, EvmEvent, Listener as ListenerT, StepEventFilter};
+
+/// Highest address of the precompile range.
+const PRECOMPILE_RANGE_END: u64 = 4095;
+/// Prefix of the assets ERC20 precompiles.
+const ASSET_PRECOMPILE_PREFIX: [u8; 4] = [255u8; 4];
+
+/// Calls to precompiles are not reported, like geth does.
+fn is_precompile(address: &H160) -> bool {
+	let bytes = address.as_bytes();
+	if bytes.starts_with(&ASSET_PRECOMPILE_PREFIX) {
+		return true
+	}
+	bytes[..12].iter().all(|b| *b == 0) &&
+		(1..=PRECOMPILE_RANGE_END).contains(&address.to_low_u64_be())
+}
+
+#[derive(Debug, Default)]
+pub struct Listener {
+	/// `EvmEvent::TransactCall` is followed by the `EvmEvent::Call` of the same call, which
+	/// must not be counted twice.
+	skip_next_call: bool,
+
+	/// Number of calls per `<selector>-<call data size>` key.
+	pub selectors: BTreeMap<String, u64>,
+}
+
+impl Listener {
+	pub fn using<R, F: FnOnce() -> R>(&mut self, f: F) -> R {
+		evm_tracing_events::using(self, f)
+	}
+
+	fn record(&mut self, address: H160, input: &[u8]) {
+		if input.len() < 4 || is_precompile(&address) {
+			return
+		}
+
+		let key = format!("0x{}-{}", hex::encode(&input[..4]), input.len() - 4);
+		*self.selectors.entry(key).or_default() += 1;
+	}
+
+	pub fn evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { address, data, .. } => {
+				self.record(address, &data);
+				self.skip_next_call = true;
+			},
+			EvmEvent::Call { code_address, input, .. } =>
+				if self.skip_next_call {
+					self.skip_next_call = false;
+				} else {
+					self.record(code_address, &input);
+				},
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+}
+
+impl ListenerT for Listener {
+	fn event(&mut self, event: Event) {
+		if let Event::Evm(e) = event {
+			self.evm_event(e);
+		}
+	}
+
+	fn step_event_filter(&self) -> StepEventFilter {
+		StepEventFilter { enable_memory: false, enable_stack: false }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::U256;
+	use evm_tracing_events::Context;
+
+	fn call(code_address: H160, input: Vec<u8>) -> EvmEvent {
+		EvmEvent::Call {
+			code_address,
+			transfer: None,
+			input,
+			target_gas: None,
+			is_static: false,
+			context: Context {
+				address: code_address,
+				caller: H160::default(),
+				apparent_value: U256::zero(),
+			},
+		}
+	}
+
+	#[test]
+	fn transact_call_is_counted_once() {
+		let contract = H160::repeat_byte(0x11);
+		let mut listener = Listener::default();
+
+		listener.evm_event(EvmEvent::TransactCall {
+			caller: H160::default(),
+			address: contract,
+			value: U256::zero(),
+			data: vec![0xa9, 0x05, 0x9c, 0xbb, 0, 0],
+			gas_limit: 100_000,
+		});
+		listener.evm_event(call(contract, vec![0xa9, 0x05, 0x9c, 0xbb, 0, 0]));
+		listener.evm_event(call(contract, vec![0xa9, 0x05, 0x9c, 0xbb, 0, 0]));
+		listener.evm_event(call(contract, vec![0x70, 0xa0, 0x82, 0x31]));
+
+		assert_eq!(listener.selectors.get("0xa9059cbb-2"), Some(&2));
+		assert_eq!(listener.selectors.get("0x70a08231-0"), Some(&1));
+	}
+
+	#[test]
+	fn short_inputs_and_precompiles_are_ignored() {
+		let mut listener = Listener::default();
+		let mut asset = [0u8; 20];
+		asset[..4].copy_from_slice(&ASSET_PRECOMPILE_PREFIX);
+
+		listener.evm_event(call(H160::repeat_byte(0x11), vec![0xa9, 0x05]));
+		listener.evm_event(call(H160::from_low_u64_be(1), vec![0xa9, 0x05, 0x9c, 0xbb]));
+		listener.evm_event(call(H160::from_low_u64_be(2048), vec![0xa9, 0x05, 0x9c, 0xbb]));
+		listener.evm_event(call(H160::from(asset), vec![0xa9, 0x05, 0x9c, 0xbb]));
+
+		assert!(listener.selectors.is_empty());
+	}
+}
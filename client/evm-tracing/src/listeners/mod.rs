@@ -15,7 +15,11 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod call_list;
+pub mod four_byte;
+pub mod prestate;
 pub mod raw;
 
 pub use call_list::Listener as CallList;
+pub use four_byte::Listener as FourByte;
+pub use prestate::Listener as Prestate;
 pub use raw::Listener as Raw;
@@ -0,0 +1,167 @@
+//! Listener collecting the accounts and storage slots accessed by a transaction.
+//!
+//! Events only tell which parts of the state are touched. The actual values before and after the
+//! transaction are read from the replayed state by the caller and stored in `pre` and `post`,
+//! which are then used by the prestate formatters.
+
+use ethereum_types::{H160, H256, U256};
+use std::collections::{BTreeMap, BTreeSet};
+
+use evm_tracing_events::{Event, EvmEvent, Listener as ListenerT, RuntimeEvent, StepEventFilter};
+use fp_rpc::EthereumRuntimeRPCApi;
+use sp_runtime::traits::Block as BlockT;
+
+/// Opcodes reading an account whose address is on top of the stack.
+const ACCOUNT_ACCESS_OPCODES: [&[u8]; 4] =
+	[b"Balance", b"ExtCodeSize", b"ExtCodeCopy", b"ExtCodeHash"];
+
+/// State of an account, restricted to the storage slots accessed by the transaction.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct AccountState {
+	pub balance: U256,
+	pub nonce: U256,
+	pub code: Vec<u8>,
+	pub storage: BTreeMap<H256, H256>,
+}
+
+impl AccountState {
+	/// Read the balance, nonce, code and the given storage slots of an account from the runtime
+	/// state of `api`.
+	pub fn read<B, A>(
+		api: &A,
+		at: B::Hash,
+		address: H160,
+		slots: &BTreeSet<H256>,
+	) -> Result<Self, String>
+	where
+		B: BlockT,
+		A: EthereumRuntimeRPCApi<B>,
+	{
+		let map_err = |e: sp_api::ApiError| format!("Runtime api access error: {:?}", e);
+
+		let account = api.account_basic(at, address).map_err(map_err)?;
+		let code = api.account_code_at(at, address).map_err(map_err)?;
+		let storage = slots
+			.iter()
+			.map(|slot| {
+				api.storage_at(at, address, U256::from_big_endian(slot.as_bytes()))
+					.map(|value| (*slot, value))
+			})
+			.collect::<Result<_, _>>()
+			.map_err(map_err)?;
+
+		Ok(Self { balance: account.balance, nonce: account.nonce, code, storage })
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.balance.is_zero() && self.nonce.is_zero() && self.code.is_empty()
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct Listener {
+	/// Accounts touched by the transaction, with the storage slots accessed in each of them.
+	pub accounts: BTreeMap<H160, BTreeSet<H256>>,
+	/// Accounts created by the transaction.
+	pub created: BTreeSet<H160>,
+	/// Accounts self-destructed by the transaction.
+	pub destroyed: BTreeSet<H160>,
+
+	/// State of the touched accounts before the transaction.
+	pub pre: BTreeMap<H160, AccountState>,
+	/// State of the touched accounts after the transaction.
+	pub post: BTreeMap<H160, AccountState>,
+}
+
+impl Listener {
+	pub fn using<R, F: FnOnce() -> R>(&mut self, f: F) -> R {
+		evm_tracing_events::using(self, f)
+	}
+
+	/// Read the state of every touched account with the provided accessor.
+	pub fn read_accounts<E, F>(&self, mut read: F) -> Result<BTreeMap<H160, AccountState>, E>
+	where
+		F: FnMut(H160, &BTreeSet<H256>) -> Result<AccountState, E>,
+	{
+		self.accounts
+			.iter()
+			.map(|(address, slots)| read(*address, slots).map(|state| (*address, state)))
+			.collect()
+	}
+
+	fn touch(&mut self, address: H160) {
+		self.accounts.entry(address).or_default();
+	}
+
+	fn touch_slot(&mut self, address: H160, index: H256) {
+		self.accounts.entry(address).or_default().insert(index);
+	}
+
+	pub fn evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { caller, address, .. } => {
+				self.touch(caller);
+				self.touch(address);
+			},
+			EvmEvent::TransactCreate { caller, address, .. } |
+			EvmEvent::TransactCreate2 { caller, address, .. } |
+			EvmEvent::Create { caller, address, .. } => {
+				self.touch(caller);
+				self.touch(address);
+				self.created.insert(address);
+			},
+			EvmEvent::Call { code_address, transfer, context, .. } |
+			EvmEvent::PrecompileSubcall { code_address, transfer, context, .. } => {
+				self.touch(code_address);
+				self.touch(context.address);
+				self.touch(context.caller);
+				if let Some(transfer) = transfer {
+					self.touch(transfer.source);
+					self.touch(transfer.target);
+				}
+			},
+			EvmEvent::Suicide { address, target, .. } => {
+				self.touch(address);
+				self.touch(target);
+				self.destroyed.insert(address);
+			},
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+
+	pub fn runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::Step { context, opcode, stack, .. } => {
+				self.touch(context.address);
+
+				if ACCOUNT_ACCESS_OPCODES.contains(&&opcode[..]) {
+					if let Some(top) = stack.as_ref().and_then(|stack| stack.data.last()) {
+						self.touch(H160::from_slice(&top.as_bytes()[12..]));
+					}
+				}
+			},
+			RuntimeEvent::SLoad { address, index, .. } |
+			RuntimeEvent::SStore { address, index, .. } => self.touch_slot(address, index),
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+}
+
+impl ListenerT for Listener {
+	fn event(&mut self, event: Event) {
+		match event {
+			Event::Evm(e) => self.evm_event(e),
+			Event::Runtime(e) => self.runtime_event(e),
+			_ => {},
+		};
+	}
+
+	fn step_event_filter(&self) -> StepEventFilter {
+		// The stack is needed to know which account is read by `BALANCE` and `EXTCODE*`.
+		StepEventFilter { enable_memory: false, enable_stack: true }
+	}
+}
@@ -21,7 +21,7 @@ use serde::Serialize;
 
 use ethereum_types::{H160, H256, U256};
 use parity_scale_codec::{Decode, Encode};
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -91,3 +91,46 @@ pub enum TransactionTraceResult {
 	},
 	Suicide,
 }
+
+/// Changes made by a transaction to the accounts it touched, as Parity's `stateDiff`.
+pub type StateDiff = BTreeMap<H160, AccountDiff>;
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+	pub balance: Diff<U256>,
+	pub code: Diff<Bytes>,
+	pub nonce: Diff<U256>,
+	pub storage: BTreeMap<H256, Diff<H256>>,
+}
+
+/// Change of a value : unchanged, set by the account creation, removed by the account
+/// destruction, or modified.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub enum Diff<T> {
+	#[serde(rename = "=")]
+	Same,
+	#[serde(rename = "+")]
+	Born(T),
+	#[serde(rename = "-")]
+	Died(T),
+	#[serde(rename = "*")]
+	Changed { from: T, to: T },
+}
+
+impl<T: PartialEq> Diff<T> {
+	pub fn new(from: T, to: T) -> Self {
+		if from == to {
+			Diff::Same
+		} else {
+			Diff::Changed { from, to }
+		}
+	}
+
+	pub fn is_same(&self) -> bool {
+		matches!(self, Diff::Same)
+	}
+}
+
+#[derive(Clone, Default, Eq, PartialEq, Debug, Serialize)]
+pub struct Bytes(#[serde(serialize_with = "bytes_0x_serialize")] pub Vec<u8>);
@@ -20,9 +20,10 @@
 //! the whole block tracing output.
 
 use super::serialization::*;
+use crate::formatters::prestate_tracer::PrestateAccount;
 use serde::Serialize;
 
-use ethereum_types::{H256, U256};
+use ethereum_types::{H160, H256, U256};
 use parity_scale_codec::{Decode, Encode};
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
@@ -41,6 +42,10 @@ pub enum TraceType {
 	CallList,
 	/// A single block trace. Use in `debug_traceTransactionByNumber` / `traceTransactionByHash`.
 	Block,
+	/// Accounts touched by a transaction, in geth's prestateTracer default or diff mode.
+	Prestate { diff_mode: bool },
+	/// Function selectors called by a transaction, as geth's 4byteTracer.
+	FourByte,
 }
 
 /// Single transaction trace.
@@ -61,6 +66,12 @@ pub enum TransactionTrace {
 	CallList(Vec<Call>),
 	/// Used by Geth's callTracer.
 	CallListNested(Call),
+	/// Used by Geth's prestateTracer in default mode.
+	Prestate(BTreeMap<H160, PrestateAccount>),
+	/// Used by Geth's prestateTracer in diff mode.
+	PrestateDiff { pre: BTreeMap<H160, PrestateAccount>, post: BTreeMap<H160, PrestateAccount> },
+	/// Used by Geth's 4byteTracer.
+	FourByte(BTreeMap<String, u64>),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
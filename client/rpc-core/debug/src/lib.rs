@@ -27,9 +27,18 @@ pub struct TraceParams {
 	pub disable_stack: Option<bool>,
 	/// Javascript tracer (we just check if it's Blockscout tracer string)
 	pub tracer: Option<String>,
+	pub tracer_config: Option<TracerConfig>,
 	pub timeout: Option<String>,
 }
 
+/// Options of the native tracers.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerConfig {
+	/// Report the state changes of `prestateTracer` instead of the touched state.
+	pub diff_mode: Option<bool>,
+}
+
 #[rpc(server)]
 #[jsonrpsee::core::async_trait]
 pub trait Debug {
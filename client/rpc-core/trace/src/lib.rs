@@ -14,17 +14,44 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::H160;
+use ethereum_types::{H160, H256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use peaq_client_evm_tracing::types::block::TransactionTrace;
+use peaq_client_evm_tracing::types::{
+	block::{StateDiff, TransactionTrace},
+	serialization::bytes_0x_serialize,
+};
 use peaq_rpc_core_types::RequestBlockId;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[rpc(server)]
 #[jsonrpsee::core::async_trait]
 pub trait Trace {
 	#[method(name = "trace_filter")]
 	async fn filter(&self, filter: FilterRequest) -> RpcResult<Vec<TransactionTrace>>;
+
+	#[method(name = "trace_filterPage")]
+	async fn filter_page(&self, filter: FilterRequest) -> RpcResult<FilterPage>;
+
+	#[method(name = "trace_block")]
+	async fn block(&self, block: RequestBlockId) -> RpcResult<Vec<TransactionTrace>>;
+
+	#[method(name = "trace_transaction")]
+	async fn transaction(&self, transaction_hash: H256)
+		-> RpcResult<Option<Vec<TransactionTrace>>>;
+
+	#[method(name = "trace_get")]
+	async fn get(
+		&self,
+		transaction_hash: H256,
+		indices: Vec<U64>,
+	) -> RpcResult<Option<TransactionTrace>>;
+
+	#[method(name = "trace_replayBlockTransactions")]
+	async fn replay_block_transactions(
+		&self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> RpcResult<Vec<TraceResults>>;
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
@@ -47,4 +74,87 @@ pub struct FilterRequest {
 
 	/// (optional) Integer number of traces to display in a batch.
 	pub count: Option<u32>,
+
+	/// (optional) How `fromAddress` and `toAddress` are combined, `intersection` by default.
+	pub mode: Option<FilterMode>,
+
+	/// (optional) Only traces of these types.
+	pub trace_type: Option<Vec<FilterTraceType>>,
+
+	/// (optional) Only successful or failed traces.
+	pub status: Option<FilterStatus>,
+
+	/// (optional) Cursor returned by `trace_filterPage`, to resume a previous request from.
+	pub cursor: Option<String>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterMode {
+	/// Traces matching both `fromAddress` and `toAddress`.
+	#[default]
+	Intersection,
+	/// Traces matching either `fromAddress` or `toAddress`.
+	Union,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterTraceType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Suicide,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterStatus {
+	Success,
+	Error,
+}
+
+/// Page of traces, as returned by `trace_filterPage`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterPage {
+	pub traces: Vec<TransactionTrace>,
+
+	/// Cursor to provide in the same request to get the next page, if there are more traces.
+	pub next_cursor: Option<String>,
+}
+
+/// Outputs requested to `trace_replayBlockTransactions`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+	/// Call traces, as returned by `trace_block`.
+	Trace,
+	/// Full VM execution trace (not supported).
+	VmTrace,
+	/// Changes made to the state.
+	StateDiff,
+}
+
+/// Replay of a transaction, as returned by `trace_replayBlockTransactions`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+	/// Output of the transaction call, or code of the created contract.
+	#[serde(serialize_with = "bytes_0x_serialize")]
+	pub output: Vec<u8>,
+
+	/// Changes made to the state, if requested.
+	pub state_diff: Option<StateDiff>,
+
+	/// Call traces, if requested.
+	pub trace: Vec<TransactionTrace>,
+
+	/// Always null as VM traces are not supported.
+	pub vm_trace: Option<()>,
+
+	/// Hash of the replayed transaction.
+	pub transaction_hash: H256,
 }
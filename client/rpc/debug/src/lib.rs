@@ -24,15 +24,17 @@ use tokio::{
 	sync::{oneshot, Semaphore},
 };
 
-use ethereum_types::H256;
+use ethereum_types::{H160, H256, U256};
 use fc_rpc::{frontier_backend_client, internal_err, OverrideHandle};
 use fp_rpc::EthereumRuntimeRPCApi;
-use peaq_client_evm_tracing::{formatters::ResponseFormatter, types::single};
+use peaq_client_evm_tracing::{
+	formatters::ResponseFormatter, listeners::prestate::AccountState, types::single,
+};
 use peaq_rpc_core_types::{RequestBlockId, RequestBlockTag};
 use peaq_rpc_primitives_debug::{DebugRuntimeApi, TracerInput};
 use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
 use sc_utils::mpsc::TracingUnboundedSender;
-use sp_api::{ApiExt, BlockId, Core, HeaderT, ProvideRuntimeApi};
+use sp_api::{ApiExt, ApiRef, BlockId, Core, HeaderT, ProvideRuntimeApi, TransactionOutcome};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{
 	Backend as BlockchainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
@@ -231,22 +233,29 @@ where
 	fn handle_params(params: Option<TraceParams>) -> RpcResult<(TracerInput, single::TraceType)> {
 		// Set trace input and type
 		match params {
-			Some(TraceParams { tracer: Some(tracer), .. }) => {
+			Some(TraceParams { tracer: Some(tracer), tracer_config, .. }) => {
 				const BLOCKSCOUT_JS_CODE_HASH: [u8; 16] =
 					hex_literal::hex!("94d9f08796f91eb13a2e82a6066882f7");
 				const BLOCKSCOUT_JS_CODE_HASH_V2: [u8; 16] =
 					hex_literal::hex!("89db13694675692951673a1e6e18ff02");
 				let hash = sp_io::hashing::twox_128(tracer.as_bytes());
-				let tracer =
-					if hash == BLOCKSCOUT_JS_CODE_HASH || hash == BLOCKSCOUT_JS_CODE_HASH_V2 {
-						Some(TracerInput::Blockscout)
-					} else if tracer == "callTracer" {
-						Some(TracerInput::CallTracer)
-					} else {
-						None
-					};
+				let tracer = if hash == BLOCKSCOUT_JS_CODE_HASH ||
+					hash == BLOCKSCOUT_JS_CODE_HASH_V2
+				{
+					Some((TracerInput::Blockscout, single::TraceType::CallList))
+				} else if tracer == "callTracer" {
+					Some((TracerInput::CallTracer, single::TraceType::CallList))
+				} else if tracer == "prestateTracer" {
+					let diff_mode =
+						tracer_config.and_then(|config| config.diff_mode).unwrap_or(false);
+					Some((TracerInput::PrestateTracer, single::TraceType::Prestate { diff_mode }))
+				} else if tracer == "4byteTracer" {
+					Some((TracerInput::FourByteTracer, single::TraceType::FourByte))
+				} else {
+					None
+				};
 				if let Some(tracer) = tracer {
-					Ok((tracer, single::TraceType::CallList))
+					Ok(tracer)
 				} else {
 					Err(internal_err(format!(
 						"javascript based tracing is not available (hash :{:?})",
@@ -467,6 +476,11 @@ where
 		if let Some(block) = reference_block {
 			let transactions = block.transactions;
 			if let Some(transaction) = transactions.get(index) {
+				// The prestate tracer replays the block a second time to read the state the
+				// transaction was executed on.
+				let prestate_exts =
+					matches!(trace_type, single::TraceType::Prestate { .. }).then(|| exts.clone());
+
 				let f = || -> RpcResult<_> {
 					api.initialize_block(parent_block_id, &header)
 						.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
@@ -546,6 +560,52 @@ where
 						}?;
 						Ok(Response::Single(response))
 					},
+					single::TraceType::Prestate { diff_mode } => {
+						let mut proxy = peaq_client_evm_tracing::listeners::Prestate::default();
+						proxy.using(f)?;
+
+						// `api` now holds the state right after the traced transaction.
+						proxy.post = proxy.read_accounts(|address, slots| {
+							AccountState::read::<B, _>(&*api, parent_block_id, address, slots)
+								.map_err(internal_err)
+						})?;
+
+						let from = overrides
+							.schemas
+							.get(&schema)
+							.and_then(|schema| schema.current_transaction_statuses(reference_id))
+							.and_then(|statuses| statuses.get(index).map(|status| status.from))
+							.ok_or_else(|| internal_err("Transaction status not found"))?;
+						let pre_api = Self::replay_until_transaction(
+							client.as_ref(),
+							parent_block_id,
+							&header,
+							prestate_exts.unwrap_or_default(),
+							from,
+							transaction_nonce(transaction),
+						)?;
+						proxy.pre = proxy.read_accounts(|address, slots| {
+							AccountState::read::<B, _>(&*pre_api, parent_block_id, address, slots)
+								.map_err(internal_err)
+						})?;
+
+						let response = if diff_mode {
+							peaq_client_evm_tracing::formatters::PrestateDiffTracer::format(proxy)
+						} else {
+							peaq_client_evm_tracing::formatters::PrestateTracer::format(proxy)
+						};
+						Ok(Response::Single(
+							response.ok_or_else(|| internal_err("Trace result is empty."))?,
+						))
+					},
+					single::TraceType::FourByte => {
+						let mut proxy = peaq_client_evm_tracing::listeners::FourByte::default();
+						proxy.using(f)?;
+						Ok(Response::Single(
+							peaq_client_evm_tracing::formatters::FourByteTracer::format(proxy)
+								.ok_or_else(|| internal_err("Trace result is empty."))?,
+						))
+					},
 					not_supported => Err(internal_err(format!(
 						"Bug: `handle_transaction_request` does not support {:?}.",
 						not_supported
@@ -555,4 +615,46 @@ where
 		}
 		Err(internal_err("Runtime block call failed".to_string()))
 	}
+
+	/// Returns an ApiRef holding the state the transaction of `from` with `nonce` was executed
+	/// on, by applying the block extrinsics preceding it on top of the parent block.
+	///
+	/// Extrinsics are applied one by one in a storage transaction. The one including the
+	/// transaction is recognised by the sender nonce going past the transaction nonce, its
+	/// changes are then reverted.
+	fn replay_until_transaction<'a>(
+		client: &'a C,
+		parent_block_id: B::Hash,
+		header: &B::Header,
+		exts: Vec<B::Extrinsic>,
+		from: H160,
+		nonce: U256,
+	) -> RpcResult<ApiRef<'a, C::Api>> {
+		let api = client.runtime_api();
+		api.initialize_block(parent_block_id, header)
+			.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+
+		for ext in exts {
+			let applied = api.execute_in_transaction(|api| {
+				let _ = api.apply_extrinsic(parent_block_id, ext);
+				match api.account_basic(parent_block_id, from) {
+					Ok(account) if account.nonce > nonce => TransactionOutcome::Rollback(false),
+					_ => TransactionOutcome::Commit(true),
+				}
+			});
+			if !applied {
+				return Ok(api)
+			}
+		}
+
+		Err(internal_err("Transaction not found among the block extrinsics".to_string()))
+	}
+}
+
+fn transaction_nonce(transaction: &ethereum::TransactionV2) -> U256 {
+	match transaction {
+		ethereum::TransactionV2::Legacy(tx) => tx.nonce,
+		ethereum::TransactionV2::EIP2930(tx) => tx.nonce,
+		ethereum::TransactionV2::EIP1559(tx) => tx.nonce,
+	}
 }
@@ -0,0 +1,215 @@
+//! Matching of traces against a `trace_filter` request, and cursors to resume such a request.
+
+use ethereum_types::H160;
+use peaq_client_evm_tracing::types::{
+	block::{TransactionTrace, TransactionTraceAction, TransactionTraceOutput},
+	CallType,
+};
+use peaq_rpc_core_trace::{FilterMode, FilterRequest, FilterStatus, FilterTraceType};
+
+/// Criteria of a `trace_filter` request a trace must match.
+pub struct Filter {
+	from_address: Vec<H160>,
+	to_address: Vec<H160>,
+	mode: FilterMode,
+	trace_type: Vec<FilterTraceType>,
+	status: Option<FilterStatus>,
+}
+
+impl Filter {
+	pub fn new(req: &FilterRequest) -> Self {
+		Self {
+			from_address: req.from_address.clone().unwrap_or_default(),
+			to_address: req.to_address.clone().unwrap_or_default(),
+			mode: req.mode.unwrap_or_default(),
+			trace_type: req.trace_type.clone().unwrap_or_default(),
+			status: req.status,
+		}
+	}
+
+	pub fn matches(&self, trace: &TransactionTrace) -> bool {
+		self.matches_addresses(trace) && self.matches_type(trace) && self.matches_status(trace)
+	}
+
+	/// Create and suicide traces have no destination, thus only match an empty `to_address`
+	/// in intersection mode.
+	fn matches_addresses(&self, trace: &TransactionTrace) -> bool {
+		let (from, to) = match trace.action {
+			TransactionTraceAction::Call { from, to, .. } => (from, Some(to)),
+			TransactionTraceAction::Create { from, .. } => (from, None),
+			TransactionTraceAction::Suicide { address, .. } => (address, None),
+		};
+		let from_matches = self.from_address.contains(&from);
+		let to_matches = to.map_or(false, |to| self.to_address.contains(&to));
+
+		match self.mode {
+			FilterMode::Intersection =>
+				(self.from_address.is_empty() || from_matches) &&
+					(self.to_address.is_empty() || to_matches),
+			FilterMode::Union =>
+				(self.from_address.is_empty() && self.to_address.is_empty()) ||
+					from_matches || to_matches,
+		}
+	}
+
+	fn matches_type(&self, trace: &TransactionTrace) -> bool {
+		if self.trace_type.is_empty() {
+			return true
+		}
+
+		let trace_type = match trace.action {
+			TransactionTraceAction::Call { call_type, .. } => match call_type {
+				CallType::Call => FilterTraceType::Call,
+				CallType::CallCode => FilterTraceType::CallCode,
+				CallType::DelegateCall => FilterTraceType::DelegateCall,
+				CallType::StaticCall => FilterTraceType::StaticCall,
+			},
+			TransactionTraceAction::Create { .. } => FilterTraceType::Create,
+			TransactionTraceAction::Suicide { .. } => FilterTraceType::Suicide,
+		};
+		self.trace_type.contains(&trace_type)
+	}
+
+	fn matches_status(&self, trace: &TransactionTrace) -> bool {
+		match (self.status, &trace.output) {
+			(None, _) => true,
+			(Some(FilterStatus::Success), TransactionTraceOutput::Result(_)) => true,
+			(Some(FilterStatus::Error), TransactionTraceOutput::Error(_)) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Position to resume a `trace_filter` request from : the block height, and the number of
+/// matching traces of this block already returned.
+///
+/// It is given to the clients as an opaque hex string, and is only meaningful for the request
+/// it was returned for.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct FilterCursor {
+	pub block: u32,
+	pub skip: u32,
+}
+
+impl FilterCursor {
+	pub fn encode(&self) -> String {
+		format!("0x{:08x}{:08x}", self.block, self.skip)
+	}
+
+	pub fn decode(cursor: &str) -> Result<Self, String> {
+		let invalid = || format!("Invalid cursor '{}'", cursor);
+
+		let cursor = cursor.strip_prefix("0x").filter(|c| c.len() == 16).ok_or_else(invalid)?;
+		let block = u32::from_str_radix(&cursor[..8], 16).map_err(|_| invalid())?;
+		let skip = u32::from_str_radix(&cursor[8..], 16).map_err(|_| invalid())?;
+
+		Ok(Self { block, skip })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::H256;
+	use peaq_client_evm_tracing::types::{block::TransactionTraceResult, CreateType};
+
+	fn trace(action: TransactionTraceAction, success: bool) -> TransactionTrace {
+		TransactionTrace {
+			action,
+			block_hash: H256::default(),
+			block_number: 1,
+			output: if success {
+				TransactionTraceOutput::Result(TransactionTraceResult::Suicide)
+			} else {
+				TransactionTraceOutput::Error(b"Reverted".to_vec())
+			},
+			subtraces: 0,
+			trace_address: vec![],
+			transaction_hash: H256::default(),
+			transaction_position: 0,
+		}
+	}
+
+	fn call(call_type: CallType, from: u64, to: u64) -> TransactionTraceAction {
+		TransactionTraceAction::Call {
+			call_type,
+			from: H160::from_low_u64_be(from),
+			gas: Default::default(),
+			input: vec![],
+			to: H160::from_low_u64_be(to),
+			value: Default::default(),
+		}
+	}
+
+	fn request(json: serde_json::Value) -> FilterRequest {
+		serde_json::from_value(json).unwrap()
+	}
+
+	#[test]
+	fn matches_addresses_by_mode() {
+		let a = format!("{:?}", H160::from_low_u64_be(1));
+		let b = format!("{:?}", H160::from_low_u64_be(2));
+		let intersection = Filter::new(&request(serde_json::json!({
+			"fromAddress": [a],
+			"toAddress": [b],
+		})));
+		let union = Filter::new(&request(serde_json::json!({
+			"fromAddress": [a],
+			"toAddress": [b],
+			"mode": "union",
+		})));
+
+		let both = trace(call(CallType::Call, 1, 2), true);
+		let from_only = trace(call(CallType::Call, 1, 3), true);
+		let to_only = trace(call(CallType::Call, 3, 2), true);
+		let create = trace(
+			TransactionTraceAction::Create {
+				creation_method: CreateType::Create,
+				from: H160::from_low_u64_be(1),
+				gas: Default::default(),
+				init: vec![],
+				value: Default::default(),
+			},
+			true,
+		);
+
+		assert!(intersection.matches(&both));
+		assert!(!intersection.matches(&from_only));
+		assert!(!intersection.matches(&to_only));
+		assert!(!intersection.matches(&create));
+
+		assert!(union.matches(&both));
+		assert!(union.matches(&from_only));
+		assert!(union.matches(&to_only));
+		assert!(union.matches(&create));
+		assert!(!union.matches(&trace(call(CallType::Call, 3, 4), true)));
+	}
+
+	#[test]
+	fn matches_type_and_status() {
+		let filter = Filter::new(&request(serde_json::json!({
+			"traceType": ["delegatecall", "suicide"],
+			"status": "error",
+		})));
+
+		let suicide = TransactionTraceAction::Suicide {
+			address: H160::from_low_u64_be(1),
+			balance: Default::default(),
+			refund_address: H160::from_low_u64_be(2),
+		};
+
+		assert!(filter.matches(&trace(call(CallType::DelegateCall, 1, 2), false)));
+		assert!(filter.matches(&trace(suicide, false)));
+		assert!(!filter.matches(&trace(call(CallType::DelegateCall, 1, 2), true)));
+		assert!(!filter.matches(&trace(call(CallType::Call, 1, 2), false)));
+	}
+
+	#[test]
+	fn cursor_round_trip() {
+		let cursor = FilterCursor { block: 1_234_567, skip: 42 };
+
+		assert_eq!(FilterCursor::decode(&cursor.encode()), Ok(cursor));
+		assert!(FilterCursor::decode("0x1234").is_err());
+		assert!(FilterCursor::decode("00000001000000020").is_err());
+	}
+}
@@ -0,0 +1,244 @@
+//! Background task tracing finalized blocks ahead of `trace_filter` requests.
+//!
+//! The indexer backfills blocks from a start height up to the last finalized one, then traces
+//! each newly finalized block. Traces are written to the `TraceStore`, from which the `CacheTask`
+//! serves them. Tracing is done using the blocking permits shared with the RPC handlers, of which
+//! the indexer leaves at least one to them. Blocks failing to be traced are attempted again along
+//! the next finalized ones.
+
+use futures::{future, stream, StreamExt};
+use std::{collections::BTreeMap, future::Future, marker::PhantomData, sync::Arc};
+use tokio::sync::Semaphore;
+use tracing::Instrument;
+
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	client::BlockchainEvents,
+};
+use sp_api::{ApiExt, HeaderT, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+
+use ethereum_types::H256;
+use fc_rpc::OverrideHandle;
+use fp_rpc::EthereumRuntimeRPCApi;
+use peaq_rpc_primitives_debug::DebugRuntimeApi;
+
+use crate::{CacheTask, TraceStore};
+
+/// Number of times the indexer attempts to trace a block before giving up on it.
+const MAX_INDEX_ATTEMPTS: u32 = 3;
+
+/// Prometheus metrics of the indexer progress.
+struct Metrics {
+	finalized_height: Gauge<U64>,
+	indexed_height: Gauge<U64>,
+	traced_blocks: Counter<U64>,
+	failed_blocks: Counter<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			finalized_height: register(
+				Gauge::new(
+					"peaq_trace_indexer_finalized_height",
+					"Height of the last finalized block known by the trace indexer.",
+				)?,
+				registry,
+			)?,
+			indexed_height: register(
+				Gauge::new(
+					"peaq_trace_indexer_indexed_height",
+					"Height of the last block processed by the trace indexer.",
+				)?,
+				registry,
+			)?,
+			traced_blocks: register(
+				Counter::new(
+					"peaq_trace_indexer_traced_blocks_total",
+					"Number of blocks traced and stored by the trace indexer.",
+				)?,
+				registry,
+			)?,
+			failed_blocks: register(
+				Counter::new(
+					"peaq_trace_indexer_failed_blocks_total",
+					"Number of blocks the trace indexer failed to trace.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Type wrapper for the indexer task, generic over the Client, Block and Backend types.
+pub struct IndexerTask<B, C, BE>(PhantomData<(B, C, BE)>);
+
+impl<B, C, BE> IndexerTask<B, C, BE>
+where
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	C: ProvideRuntimeApi<B>,
+	C: StorageProvider<B, BE>,
+	C: HeaderMetadata<B, Error = BlockChainError> + HeaderBackend<B>,
+	C: BlockchainEvents<B>,
+	C: Send + Sync + 'static,
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	B::Header: HeaderT<Number = u32>,
+	C::Api: BlockBuilder<B>,
+	C::Api: DebugRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C::Api: ApiExt<B>,
+{
+	/// Create the indexer task, backfilling blocks from `start` if provided. Otherwise only the
+	/// blocks finalized from now on are indexed. At most `max_permits` blocks are traced at once,
+	/// always leaving one of the `blocking_permits` to the RPC handlers if there are several.
+	///
+	/// Returns a Future that needs to be added to a tokio executor.
+	pub fn create(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		store: Arc<TraceStore>,
+		blocking_permits: Arc<Semaphore>,
+		max_permits: usize,
+		overrides: Arc<OverrideHandle<B>>,
+		start: Option<u32>,
+		registry: Option<&Registry>,
+	) -> impl Future<Output = ()> {
+		let metrics = registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(|e| tracing::warn!("Failed to register trace indexer metrics : {:?}", e))
+				.ok()
+		});
+		let concurrency =
+			max_permits.min(blocking_permits.available_permits().saturating_sub(1)).max(1);
+
+		async move {
+			let mut finality_notifications = client.finality_notification_stream();
+			// No traces for genesis block.
+			let mut next = start.unwrap_or_else(|| client.info().finalized_number + 1).max(1);
+			// Blocks which failed to be traced, with the number of attempts made.
+			let mut failed = BTreeMap::<u32, u32>::new();
+
+			loop {
+				let finalized = client.info().finalized_number;
+				if let Some(metrics) = &metrics {
+					metrics.finalized_height.set(finalized.into());
+				}
+
+				if next > finalized {
+					// Wait for the next finalized block.
+					if finality_notifications.next().await.is_none() {
+						break
+					}
+					continue
+				}
+
+				tracing::debug!(
+					"Indexing traces of blocks {} to {}, retrying {} failed ones",
+					next,
+					finalized,
+					failed.len()
+				);
+				let retries: Vec<u32> = failed.keys().copied().collect();
+				stream::iter(retries.into_iter().chain(next..=finalized))
+					.map(|number| {
+						Self::index_block(
+							Arc::clone(&client),
+							Arc::clone(&backend),
+							Arc::clone(&store),
+							Arc::clone(&blocking_permits),
+							Arc::clone(&overrides),
+							number,
+						)
+					})
+					.buffered(concurrency)
+					.for_each(|(number, result)| {
+						match result {
+							Ok(traced) => {
+								failed.remove(&number);
+								if let Some(metrics) = &metrics {
+									if traced {
+										metrics.traced_blocks.inc();
+									}
+									if u64::from(number) > metrics.indexed_height.get() {
+										metrics.indexed_height.set(number.into());
+									}
+								}
+							},
+							Err(e) => {
+								let attempts = failed.entry(number).or_insert(0);
+								*attempts += 1;
+								if *attempts < MAX_INDEX_ATTEMPTS {
+									tracing::warn!(
+										"Failed to index traces of block {}, will retry : {}",
+										number,
+										e
+									);
+								} else {
+									tracing::warn!(
+										"Failed to index traces of block {} {} times, giving \
+										 up : {}",
+										number,
+										attempts,
+										e
+									);
+									failed.remove(&number);
+								}
+								if let Some(metrics) = &metrics {
+									metrics.failed_blocks.inc();
+								}
+							},
+						}
+						future::ready(())
+					})
+					.await;
+
+				next = finalized + 1;
+			}
+		}
+		.instrument(tracing::debug_span!("trace_indexer"))
+	}
+
+	/// Trace and store a block once a blocking permit is obtained. Returns whether the block
+	/// has been traced, blocks already stored or out of the retention depth being skipped.
+	async fn index_block(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		store: Arc<TraceStore>,
+		blocking_permits: Arc<Semaphore>,
+		overrides: Arc<OverrideHandle<B>>,
+		number: u32,
+	) -> (u32, Result<bool, String>) {
+		let result = async {
+			let hash = client
+				.hash(number)
+				.map_err(|e| format!("Error when fetching block {} header : {:?}", number, e))?
+				.ok_or_else(|| format!("Block with height {} don't exist", number))?;
+
+			if !store.is_retained(number, client.info().finalized_number) || store.contains(hash) {
+				return Ok(false)
+			}
+
+			let _permit = blocking_permits.acquire().await;
+			tokio::task::spawn_blocking(move || -> Result<bool, String> {
+				let traces = CacheTask::<B, C, BE>::cache_block(
+					Arc::clone(&client),
+					backend,
+					hash,
+					overrides,
+				)?;
+				CacheTask::<B, C, BE>::store_block(&client, &store, hash, &traces);
+				Ok(true)
+			})
+			.await
+			.map_err(|e| format!("Tracing Substrate block {} panicked : {:?}", hash, e))?
+		}
+		.await;
+
+		(number, result)
+	}
+}
@@ -14,15 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-//! `trace_filter` RPC handler and its associated service task.
-//! The RPC handler rely on `CacheTask` which provides a future that must be run inside a tokio
+//! `trace` RPC handlers and their associated service task.
+//! The RPC handlers rely on `CacheTask` which provides a future that must be run inside a tokio
 //! executor.
 //!
 //! The implementation is composed of multiple tasks :
-//! - Many calls the the RPC handler `Trace::filter`, communicating with the main task.
+//! - Many calls the the RPC handlers (`Trace::filter`, `Trace::block`, ...), communicating with the
+//!   main task.
 //! - A main `CacheTask` managing the cache and the communication between tasks.
 //! - For each traced block an async task responsible to wait for a permit, spawn a blocking task
 //!   and waiting for the result, then send it to the main `CacheTask`.
+//!
+//! State diffs requested to `trace_replayBlockTransactions` are not cached, the block being
+//! replayed in a blocking task for each request.
+//!
+//! When a `TraceStore` is provided, traces of finalized blocks are persisted in it and blocks
+//! found in the store are served without being replayed. An `IndexerTask` can fill it ahead of
+//! the requests.
 
 use futures::{select, stream::FuturesUnordered, FutureExt, StreamExt};
 use std::{collections::BTreeMap, future::Future, marker::PhantomData, sync::Arc, time::Duration};
@@ -32,42 +40,56 @@ use tokio::{
 };
 use tracing::{instrument, Instrument};
 
-use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	client::BlockchainEvents,
+};
 use sc_utils::mpsc::TracingUnboundedSender;
-use sp_api::{ApiExt, Core, HeaderT, ProvideRuntimeApi};
+use sp_api::{ApiExt, Core, HeaderT, ProvideRuntimeApi, TransactionOutcome};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{
 	Backend as BlockchainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
 };
 use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
 
-use ethereum_types::H256;
-use fc_rpc::OverrideHandle;
+use ethereum_types::{H256, U64};
+use fc_rpc::{frontier_backend_client, OverrideHandle};
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use peaq_client_evm_tracing::{
 	formatters::ResponseFormatter,
-	types::block::{self, TransactionTrace},
+	listeners::prestate::AccountState,
+	types::block::{self, StateDiff, TransactionTrace},
 };
-pub use peaq_rpc_core_trace::{FilterRequest, TraceServer};
+pub use peaq_rpc_core_trace::{FilterPage, FilterRequest, TraceResults, TraceServer, TraceType};
 use peaq_rpc_core_types::{RequestBlockId, RequestBlockTag};
 use peaq_rpc_primitives_debug::DebugRuntimeApi;
 
+mod filter;
+mod indexer;
+mod store;
+use filter::{Filter, FilterCursor};
+pub use indexer::IndexerTask;
+pub use store::TraceStore;
+
 type TxsTraceRes = Result<Vec<TransactionTrace>, String>;
+type StateDiffsRes = Result<Vec<StateDiff>, String>;
 
 /// RPC handler. Will communicate with a `CacheTask` through a `CacheRequester`.
-pub struct Trace<B, C> {
+pub struct Trace<B: BlockT, C> {
 	_phantom: PhantomData<B>,
 	client: Arc<C>,
+	frontier_backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
 	requester: CacheRequester,
 	max_count: u32,
 }
 
-impl<B, C> Clone for Trace<B, C> {
+impl<B: BlockT, C> Clone for Trace<B, C> {
 	fn clone(&self) -> Self {
 		Self {
 			_phantom: PhantomData,
 			client: Arc::clone(&self.client),
+			frontier_backend: Arc::clone(&self.frontier_backend),
 			requester: self.requester.clone(),
 			max_count: self.max_count,
 		}
@@ -82,27 +104,236 @@ where
 	C: Send + Sync + 'static,
 {
 	/// Create a new RPC handler.
-	pub fn new(client: Arc<C>, requester: CacheRequester, max_count: u32) -> Self {
-		Self { client, requester, max_count, _phantom: PhantomData }
+	pub fn new(
+		client: Arc<C>,
+		frontier_backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+		requester: CacheRequester,
+		max_count: u32,
+	) -> Self {
+		Self { client, frontier_backend, requester, max_count, _phantom: PhantomData }
 	}
 
-	/// Convert an optional block ID (number or tag) to a block height.
-	fn block_id(&self, id: Option<RequestBlockId>) -> Result<u32, &'static str> {
+	/// Convert an optional block ID (number, hash or tag) to a block height.
+	async fn block_id(&self, id: Option<RequestBlockId>) -> Result<u32, String> {
 		match id {
 			Some(RequestBlockId::Number(n)) => Ok(n),
 			None | Some(RequestBlockId::Tag(RequestBlockTag::Latest)) =>
 				Ok(self.client.info().best_number),
 			Some(RequestBlockId::Tag(RequestBlockTag::Earliest)) => Ok(0),
 			Some(RequestBlockId::Tag(RequestBlockTag::Pending)) =>
-				Err("'pending' is not supported"),
-			Some(RequestBlockId::Hash(_)) => Err("Block hash not supported"),
+				Err("'pending' is not supported".to_string()),
+			Some(RequestBlockId::Hash(eth_hash)) => {
+				let hash = frontier_backend_client::load_hash::<B, C>(
+					self.client.as_ref(),
+					self.frontier_backend.as_ref(),
+					eth_hash,
+				)
+				.await
+				.map_err(|e| format!("Error when fetching block {} : {:?}", eth_hash, e))?
+				.ok_or_else(|| format!("Block with hash {} don't exist", eth_hash))?;
+
+				self.client
+					.number(hash)
+					.map_err(|e| format!("Error when fetching block {} header : {:?}", hash, e))?
+					.ok_or_else(|| format!("Block with hash {} don't exist", eth_hash))
+			},
+		}
+	}
+
+	/// Substrate hash of the block at the given height.
+	fn block_hash(&self, block_height: u32) -> Result<H256, String> {
+		self.client
+			.hash(block_height)
+			.map_err(|e| format!("Error when fetching block {} header : {:?}", block_height, e))?
+			.ok_or_else(|| format!("Block with height {} don't exist", block_height))
+	}
+
+	/// Substrate hash of the canonical block including the given Ethereum transaction, if any.
+	async fn transaction_block(&self, transaction_hash: H256) -> Result<Option<H256>, String> {
+		let eth_block_hash = match frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			transaction_hash,
+			true,
+		)
+		.await
+		{
+			Ok(Some((eth_block_hash, _))) => eth_block_hash,
+			Ok(None) => return Ok(None),
+			Err(e) =>
+				return Err(format!(
+					"Error when fetching transaction {} : {:?}",
+					transaction_hash, e
+				)),
+		};
+
+		frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			eth_block_hash,
+		)
+		.await
+		.map_err(|e| format!("Error when fetching block {} : {:?}", eth_block_hash, e))
+	}
+
+	/// Traces of a single block, requested to the cache task in their own batch.
+	async fn block_traces(&self, block_hash: H256) -> TxsTraceRes {
+		let batch_id = self.requester.start_batch(vec![block_hash]).await?;
+		let res = self.requester.get_traces(block_hash).await;
+		self.requester.stop_batch(batch_id).await;
+
+		res
+	}
+
+	/// `trace_block` endpoint.
+	async fn block(self, block: RequestBlockId) -> TxsTraceRes {
+		let block_height = self.block_id(Some(block)).await?;
+		if block_height == 0 {
+			return Ok(vec![]) // no traces for genesis block.
+		}
+
+		let block_hash = self.block_hash(block_height)?;
+		self.block_traces(block_hash).await
+	}
+
+	/// `trace_transaction` endpoint.
+	async fn transaction(
+		self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<TransactionTrace>>, String> {
+		let Some(block_hash) = self.transaction_block(transaction_hash).await? else {
+			return Ok(None)
+		};
+
+		let traces = self.block_traces(block_hash).await?;
+		Ok(Some(
+			traces
+				.into_iter()
+				.filter(|trace| trace.transaction_hash == transaction_hash)
+				.collect(),
+		))
+	}
+
+	/// `trace_get` endpoint. Indices are the trace address of the requested call.
+	async fn get(
+		self,
+		transaction_hash: H256,
+		indices: Vec<U64>,
+	) -> Result<Option<TransactionTrace>, String> {
+		let trace_address = indices
+			.into_iter()
+			.map(|index| u32::try_from(index).map_err(|_| format!("Invalid trace index {}", index)))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(self.transaction(transaction_hash).await?.and_then(|traces| {
+			traces.into_iter().find(|trace| trace.trace_address == trace_address)
+		}))
+	}
+
+	/// `trace_replayBlockTransactions` endpoint. Call traces are taken from the cache, while state
+	/// diffs are computed by replaying the block again.
+	async fn replay_block_transactions(
+		self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> Result<Vec<TraceResults>, String> {
+		if trace_types.contains(&TraceType::VmTrace) {
+			return Err("'vmTrace' is not supported".to_string())
+		}
+
+		let block_height = self.block_id(Some(block)).await?;
+		if block_height == 0 {
+			return Ok(vec![]) // no traces for genesis block.
+		}
+		let block_hash = self.block_hash(block_height)?;
+
+		let traces = self.block_traces(block_hash).await?;
+		let state_diffs = if trace_types.contains(&TraceType::StateDiff) {
+			Some(self.requester.get_state_diffs(block_hash).await?)
+		} else {
+			None
+		};
+
+		// Traces are ordered by transaction, starting with the top level call of each of them.
+		let mut results: Vec<TraceResults> = vec![];
+		for trace in traces {
+			if trace.trace_address.is_empty() {
+				let state_diff = state_diffs.as_ref().map(|state_diffs| {
+					state_diffs
+						.get(trace.transaction_position as usize)
+						.cloned()
+						.unwrap_or_default()
+				});
+				let output = match &trace.output {
+					block::TransactionTraceOutput::Result(
+						block::TransactionTraceResult::Call { output, .. },
+					) => output.clone(),
+					block::TransactionTraceOutput::Result(
+						block::TransactionTraceResult::Create { code, .. },
+					) => code.clone(),
+					_ => vec![],
+				};
+				results.push(TraceResults {
+					output,
+					state_diff,
+					trace: vec![],
+					vm_trace: None,
+					transaction_hash: trace.transaction_hash,
+				});
+			}
+
+			if let Some(result) = results.last_mut() {
+				if trace_types.contains(&TraceType::Trace) {
+					result.trace.push(trace);
+				}
+			}
 		}
+
+		Ok(results)
 	}
 
 	/// `trace_filter` endpoint (wrapped in the trait implementation with futures compatibilty)
 	async fn filter(self, req: FilterRequest) -> TxsTraceRes {
-		let from_block = self.block_id(req.from_block)?;
-		let to_block = self.block_id(req.to_block)?;
+		let count_provided = req.count.is_some();
+		let (traces, _) = self.filter_traces(req).await?;
+
+		// If we reached "count" (the limit) while using the default maximum, we return an error.
+		if !count_provided && traces.len() >= self.max_count as usize {
+			return Err(format!(
+				"the amount of traces goes over the maximum ({}), please use 'after' \
+				and 'count' in your request",
+				self.max_count
+			))
+		}
+
+		Ok(traces)
+	}
+
+	/// `trace_filterPage` endpoint. Instead of an error when reaching "count", the cursor to
+	/// request the next page is returned.
+	async fn filter_page(self, req: FilterRequest) -> Result<FilterPage, String> {
+		let (traces, next_cursor) = self.filter_traces(req).await?;
+
+		Ok(FilterPage { traces, next_cursor: next_cursor.map(|cursor| cursor.encode()) })
+	}
+
+	/// Traces matching the request, and the cursor of the next matching trace if "count" has
+	/// been reached.
+	async fn filter_traces(
+		&self,
+		req: FilterRequest,
+	) -> Result<(Vec<TransactionTrace>, Option<FilterCursor>), String> {
+		let cursor = req.cursor.as_deref().map(FilterCursor::decode).transpose()?;
+		if cursor.is_some() && req.after.is_some() {
+			return Err("'after' can't be used along with 'cursor'".to_string())
+		}
+
+		// A cursor replaces the start of the range and the offset.
+		let (from_block, after) = match cursor {
+			Some(cursor) => (cursor.block, cursor.skip),
+			None => (self.block_id(req.from_block).await?, req.after.unwrap_or(0)),
+		};
+		let to_block = self.block_id(req.to_block).await?;
 		let block_heights = from_block..=to_block;
 
 		let count = req.count.unwrap_or(self.max_count);
@@ -114,29 +345,26 @@ where
 		}
 
 		// Build a list of all the Substrate block hashes that need to be traced.
-		let mut block_hashes = vec![];
+		let mut blocks = vec![];
 		for block_height in block_heights {
 			if block_height == 0 {
 				continue // no traces for genesis block.
 			}
 
-			let block_hash = self
-				.client
-				.hash(block_height)
-				.map_err(|e| {
-					format!("Error when fetching block {} header : {:?}", block_height, e)
-				})?
-				.ok_or_else(|| format!("Block with height {} don't exist", block_height))?;
-
-			block_hashes.push(block_hash);
+			blocks.push((block_height, self.block_hash(block_height)?));
 		}
 
 		// Start a batch with these blocks.
-		let batch_id = self.requester.start_batch(block_hashes.clone()).await?;
+		let batch_id = self
+			.requester
+			.start_batch(blocks.iter().map(|(_, block_hash)| *block_hash).collect())
+			.await?;
 		// Fetch all the traces. It is done in another function to simplify error handling and allow
 		// to call the following `stop_batch` regardless of the result. This is important for the
 		// cache cleanup to work properly.
-		let res = self.fetch_traces(req, &block_hashes, count as usize).await;
+		let res = self
+			.fetch_traces(&Filter::new(&req), &blocks, after as usize, count as usize)
+			.await;
 		// Stop the batch, allowing the cache task to remove useless non-started block traces and
 		// start the expiration delay.
 		self.requester.stop_batch(batch_id).await;
@@ -146,70 +374,42 @@ where
 
 	async fn fetch_traces(
 		&self,
-		req: FilterRequest,
-		block_hashes: &[H256],
+		filter: &Filter,
+		blocks: &[(u32, H256)],
+		mut after: usize,
 		count: usize,
-	) -> TxsTraceRes {
-		let from_address = req.from_address.unwrap_or_default();
-		let to_address = req.to_address.unwrap_or_default();
-
-		let mut traces_amount: i64 = -(req.after.unwrap_or(0) as i64);
+	) -> Result<(Vec<TransactionTrace>, Option<FilterCursor>), String> {
 		let mut traces = vec![];
 
-		for &block_hash in block_hashes {
+		for &(block_height, block_hash) in blocks {
 			// Request the traces of this block to the cache service.
 			// This will resolve quickly if the block is already cached, or wait until the block
 			// has finished tracing.
 			let block_traces = self.requester.get_traces(block_hash).await?;
 
-			// Filter addresses.
-			let mut block_traces: Vec<_> = block_traces
-				.iter()
-				.filter(|trace| match trace.action {
-					block::TransactionTraceAction::Call { from, to, .. } =>
-						(from_address.is_empty() || from_address.contains(&from)) &&
-							(to_address.is_empty() || to_address.contains(&to)),
-					block::TransactionTraceAction::Create { from, .. } =>
-						(from_address.is_empty() || from_address.contains(&from)) &&
-							to_address.is_empty(),
-					block::TransactionTraceAction::Suicide { address, .. } =>
-						(from_address.is_empty() || from_address.contains(&address)) &&
-							to_address.is_empty(),
-				})
-				.cloned()
-				.collect();
+			// Filter traces.
+			let block_traces: Vec<_> =
+				block_traces.into_iter().filter(|trace| filter.matches(trace)).collect();
 
 			// Don't insert anything if we're still before "after"
-			traces_amount += block_traces.len() as i64;
-			if traces_amount > 0 {
-				let traces_amount = traces_amount as usize;
-				// If the current Vec of traces is across the "after" marker,
-				// we skip some elements of it.
-				if traces_amount < block_traces.len() {
-					let skip = block_traces.len() - traces_amount;
-					block_traces = block_traces.into_iter().skip(skip).collect();
+			let skip = after.min(block_traces.len());
+			after -= skip;
+
+			for (position, trace) in block_traces.into_iter().enumerate().skip(skip) {
+				// If we go over "count" (the limit), we stop at this trace which will start the
+				// next page.
+				if traces.len() == count {
+					return Ok((
+						traces,
+						Some(FilterCursor { block: block_height, skip: position as u32 }),
+					))
 				}
 
-				traces.append(&mut block_traces);
-
-				// If we go over "count" (the limit), we trim and exit the loop,
-				// unless we used the default maximum, in which case we return an error.
-				if traces_amount >= count {
-					if req.count.is_none() {
-						return Err(format!(
-							"the amount of traces goes over the maximum ({}), please use 'after' \
-							and 'count' in your request",
-							self.max_count
-						))
-					}
-
-					traces = traces.into_iter().take(count).collect();
-					break
-				}
+				traces.push(trace);
 			}
 		}
 
-		Ok(traces)
+		Ok((traces, None))
 	}
 }
 
@@ -227,6 +427,43 @@ where
 	) -> jsonrpsee::core::RpcResult<Vec<TransactionTrace>> {
 		self.clone().filter(filter).await.map_err(fc_rpc::internal_err)
 	}
+
+	async fn filter_page(&self, filter: FilterRequest) -> jsonrpsee::core::RpcResult<FilterPage> {
+		self.clone().filter_page(filter).await.map_err(fc_rpc::internal_err)
+	}
+
+	async fn block(
+		&self,
+		block: RequestBlockId,
+	) -> jsonrpsee::core::RpcResult<Vec<TransactionTrace>> {
+		self.clone().block(block).await.map_err(fc_rpc::internal_err)
+	}
+
+	async fn transaction(
+		&self,
+		transaction_hash: H256,
+	) -> jsonrpsee::core::RpcResult<Option<Vec<TransactionTrace>>> {
+		self.clone().transaction(transaction_hash).await.map_err(fc_rpc::internal_err)
+	}
+
+	async fn get(
+		&self,
+		transaction_hash: H256,
+		indices: Vec<U64>,
+	) -> jsonrpsee::core::RpcResult<Option<TransactionTrace>> {
+		self.clone().get(transaction_hash, indices).await.map_err(fc_rpc::internal_err)
+	}
+
+	async fn replay_block_transactions(
+		&self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> jsonrpsee::core::RpcResult<Vec<TraceResults>> {
+		self.clone()
+			.replay_block_transactions(block, trace_types)
+			.await
+			.map_err(fc_rpc::internal_err)
+	}
 }
 
 /// An opaque batch ID.
@@ -254,6 +491,14 @@ enum CacheRequest {
 	/// Notify the cache that it can stop the batch with that ID. Any block contained only in
 	/// this batch and still not started will be discarded.
 	StopBatch { batch_id: CacheBatchId },
+	/// Compute the state diffs of the transactions of given block hash.
+	/// The block is replayed for this request only, the result not being cached.
+	GetStateDiffs {
+		/// Returns the array of state diffs or an error.
+		sender: oneshot::Sender<StateDiffsRes>,
+		/// Hash of the block.
+		block: H256,
+	},
 }
 
 /// Allows to interact with the cache task.
@@ -300,6 +545,25 @@ impl CacheRequester {
 			.map_err(|e| format!("Failed to replay block. Error : {:?}", e))
 	}
 
+	/// Compute the state diffs of the Ethereum transactions of given block hash, in the order of
+	/// the transactions.
+	#[instrument(skip(self))]
+	pub async fn get_state_diffs(&self, block: H256) -> StateDiffsRes {
+		let (response_tx, response_rx) = oneshot::channel();
+		let sender = self.0.clone();
+
+		sender
+			.unbounded_send(CacheRequest::GetStateDiffs { sender: response_tx, block })
+			.map_err(|e| {
+				format!("Failed to send request to the trace cache task. Error : {:?}", e)
+			})?;
+
+		response_rx
+			.await
+			.map_err(|e| format!("Trace cache task closed the response channel. Error : {:?}", e))?
+			.map_err(|e| format!("Failed to replay block. Error : {:?}", e))
+	}
+
 	/// Notify the cache that it can stop the batch with that ID. Any block contained only in
 	/// this batch and still in the waiting pool will be discarded.
 	#[instrument(skip(self))]
@@ -364,6 +628,7 @@ pub struct CacheTask<B, C, BE> {
 	client: Arc<C>,
 	backend: Arc<BE>,
 	blocking_permits: Arc<Semaphore>,
+	store: Option<Arc<TraceStore>>,
 	cached_blocks: BTreeMap<H256, CacheBlock>,
 	batches: BTreeMap<u64, Vec<H256>>,
 	next_batch_id: u64,
@@ -377,6 +642,7 @@ where
 	C: ProvideRuntimeApi<B>,
 	C: StorageProvider<B, BE>,
 	C: HeaderMetadata<B, Error = BlockChainError> + HeaderBackend<B>,
+	C: BlockchainEvents<B>,
 	C: Send + Sync + 'static,
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
 	B::Header: HeaderT<Number = u32>,
@@ -395,6 +661,7 @@ where
 		cache_duration: Duration,
 		blocking_permits: Arc<Semaphore>,
 		overrides: Arc<OverrideHandle<B>>,
+		store: Option<Arc<TraceStore>>,
 	) -> (impl Future<Output = ()>, CacheRequester) {
 		// Communication with the outside world :
 		let (requester_tx, mut requester_rx) =
@@ -407,6 +674,7 @@ where
 			let mut batch_expirations = FuturesUnordered::new();
 			let (blocking_tx, mut blocking_rx) =
 				mpsc::channel(blocking_permits.available_permits() * 2);
+			let mut finality_notifications = client.finality_notification_stream().fuse();
 
 			// Contains the inner state of the cache task, excluding the pooled futures/channels.
 			// Having this object allow to refactor each event into its own function, simplifying
@@ -415,6 +683,7 @@ where
 				client,
 				backend,
 				blocking_permits,
+				store,
 				cached_blocks: BTreeMap::new(),
 				batches: BTreeMap::new(),
 				next_batch_id: 0,
@@ -442,6 +711,8 @@ where
 
 								inner.request_stop_batch(batch_id);
 							},
+							Some(CacheRequest::GetStateDiffs {sender, block})
+								=> inner.request_get_state_diffs(sender, block, overrides.clone()),
 						}
 					},
 					message = blocking_rx.recv().fuse() => {
@@ -458,6 +729,15 @@ where
 							None => (),
 							Some(batch_id) => inner.expired_batch(batch_id),
 						}
+					},
+					notification = finality_notifications.next() => {
+						match notification {
+							None => (),
+							Some(notification) => inner.finalized(
+								*notification.header.number(),
+								notification.hash,
+							),
+						}
 					}
 				}
 			}
@@ -490,6 +770,17 @@ where
 					block_cache.active_batch_count
 				);
 			}
+			// The block has been persisted, no need to trace it again.
+			else if let Some(traces) = self.store.as_ref().and_then(|store| store.get(block)) {
+				tracing::trace!("Store hit for block {}, adding it to the cache.", block);
+				self.cached_blocks.insert(
+					block,
+					CacheBlock {
+						active_batch_count: 1,
+						state: CacheBlockState::Cached { traces: Ok(traces) },
+					},
+				);
+			}
 			// Otherwise we need to queue this block for tracing.
 			else {
 				tracing::trace!("Cache miss for block {}, pooling it for tracing.", block);
@@ -500,6 +791,7 @@ where
 				let backend = Arc::clone(&self.backend);
 				let blocking_tx = blocking_tx.clone();
 				let overrides = overrides.clone();
+				let store = self.store.clone();
 
 				// Spawn all block caching asynchronously.
 				// It will wait to obtain a permit, then spawn a blocking task.
@@ -527,7 +819,12 @@ where
 						// Perform block tracing in a tokio blocking task.
 						let result = async {
 							tokio::task::spawn_blocking(move || {
-								Self::cache_block(client, backend, block, overrides)
+								let result =
+									Self::cache_block(client.clone(), backend, block, overrides);
+								if let (Some(store), Ok(traces)) = (store, &result) {
+									Self::store_block(&client, &store, block, traces);
+								}
+								result
 							})
 							.await
 							.map_err(|e| {
@@ -641,6 +938,41 @@ where
 		}
 	}
 
+	/// Handle a request to get the state diffs of the provided block.
+	/// The block is replayed in a blocking task once a permit is obtained, and the result is sent
+	/// directly to the requester.
+	#[instrument(skip(self, sender, overrides))]
+	fn request_get_state_diffs(
+		&self,
+		sender: oneshot::Sender<StateDiffsRes>,
+		block: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) {
+		let blocking_permits = Arc::clone(&self.blocking_permits);
+		let client = Arc::clone(&self.client);
+		let backend = Arc::clone(&self.backend);
+
+		tokio::spawn(
+			async move {
+				let _permit = blocking_permits.acquire().await;
+
+				let result = async {
+					tokio::task::spawn_blocking(move || {
+						Self::state_diff_block(client, backend, block, overrides)
+					})
+					.await
+					.map_err(|e| {
+						format!("Replaying Substrate block {} panicked : {:?}", block, e)
+					})?
+				}
+				.await;
+
+				let _ = sender.send(result);
+			}
+			.instrument(tracing::trace_span!("Block state diffs", block = %block)),
+		);
+	}
+
 	/// A tracing blocking task notifies it got a permit and is starting the tracing.
 	/// This started status is stored to avoid removing this block entry.
 	#[instrument(skip(self))]
@@ -704,6 +1036,36 @@ where
 		}
 	}
 
+	/// A new block has been finalized. The store is updated in a blocking task as it may prune
+	/// many blocks.
+	#[instrument(skip(self))]
+	fn finalized(&mut self, number: u32, hash: H256) {
+		if let Some(store) = self.store.clone() {
+			tokio::task::spawn_blocking(move || {
+				if let Err(e) = store.finalize(number, hash) {
+					tracing::warn!("Failed to update the trace store : {}", e);
+				}
+			});
+		}
+	}
+
+	/// (In blocking task) Persist the traces of a block if it is finalized and canonical.
+	#[instrument(skip(client, store, traces))]
+	fn store_block(client: &C, store: &TraceStore, hash: H256, traces: &[TransactionTrace]) {
+		let finalized = client.info().finalized_number;
+		let number = match client.number(hash) {
+			Ok(Some(number)) if number <= finalized => number,
+			_ => return,
+		};
+		if client.hash(number).ok().flatten() != Some(hash) {
+			return
+		}
+
+		if let Err(e) = store.insert(number, hash, traces, finalized) {
+			tracing::warn!("Failed to store the traces of block {} : {}", hash, e);
+		}
+	}
+
 	/// (In blocking task) Use the Runtime API to trace the block.
 	#[instrument(skip(client, backend, overrides))]
 	fn cache_block(
@@ -743,6 +1105,11 @@ where
 			_ => return Err(format!("No storage override at {:?}", substrate_hash)),
 		};
 
+		// Blocks without Ethereum transactions don't need to be replayed.
+		if eth_transactions.is_empty() {
+			return Ok(vec![])
+		}
+
 		let eth_block_hash = eth_block.header.hash();
 		let eth_tx_hashes = eth_transactions.iter().map(|t| t.transaction_hash).collect();
 
@@ -805,4 +1172,119 @@ where
 		}
 		Ok(traces)
 	}
+	/// (In blocking task) Replay the block to compute the state diffs of its Ethereum
+	/// transactions.
+	///
+	/// Extrinsics are applied one by one. The one including the next Ethereum transaction is
+	/// first traced in a storage transaction to collect the touched accounts and read their state
+	/// after it. Its changes are then reverted to read the state before it, and it is applied
+	/// again.
+	#[instrument(skip(client, backend, overrides))]
+	fn state_diff_block(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		substrate_hash: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> StateDiffsRes {
+		// Get Subtrate block data.
+		let api = client.runtime_api();
+		let block_header = client
+			.header(substrate_hash)
+			.map_err(|e| {
+				format!("Error when fetching substrate block {} header : {:?}", substrate_hash, e)
+			})?
+			.ok_or_else(|| format!("Subtrate block {} don't exist", substrate_hash))?;
+
+		let height = *block_header.number();
+		let substrate_parent_id = *block_header.parent_hash();
+
+		let schema =
+			fc_storage::onchain_storage_schema::<B, C, BE>(client.as_ref(), substrate_hash);
+
+		// Get Ethereum block data.
+		let eth_block = match overrides.schemas.get(&schema) {
+			Some(schema) => schema.current_block(substrate_hash).ok_or_else(|| {
+				format!("Failed to get Ethereum block data for Substrate block {}", substrate_hash)
+			})?,
+			_ => return Err(format!("No storage override at {:?}", substrate_hash)),
+		};
+
+		// Blocks without Ethereum transactions don't need to be replayed.
+		if eth_block.transactions.is_empty() {
+			return Ok(vec![])
+		}
+
+		// Single transactions can only be traced since version 4 of the runtime API.
+		match api.api_version::<dyn DebugRuntimeApi<B>>(substrate_parent_id) {
+			Ok(Some(version)) if version >= 4 => (),
+			_ =>
+				return Err(format!(
+					"State diffs are not supported by the runtime of block {}",
+					height
+				)),
+		}
+
+		// Get extrinsics (containing Ethereum ones)
+		let extrinsics = backend
+			.blockchain()
+			.body(substrate_hash)
+			.map_err(|e| {
+				format!("Blockchain error when fetching extrinsics of block {} : {:?}", height, e)
+			})?
+			.ok_or_else(|| format!("Could not find block {} when fetching extrinsics.", height))?;
+
+		api.initialize_block(substrate_parent_id, &block_header)
+			.map_err(|e| format!("Runtime api access error: {:?}", e))?;
+
+		let mut extrinsics = extrinsics.into_iter();
+		let mut state_diffs = Vec::with_capacity(eth_block.transactions.len());
+		for transaction in eth_block.transactions.iter() {
+			let mut proxy = peaq_client_evm_tracing::listeners::Prestate::default();
+
+			loop {
+				let extrinsic = extrinsics.next().ok_or_else(|| {
+					format!(
+						"Bug: An Ethereum transaction has not been found while replaying block {}.",
+						height
+					)
+				})?;
+
+				// The extrinsic is traced if it includes the transaction, its changes being
+				// reverted once the state after it is read.
+				let post = api.execute_in_transaction(|api| {
+					match proxy.using(|| {
+						api.trace_transaction(
+							substrate_parent_id,
+							vec![extrinsic.clone()],
+							transaction,
+						)
+					}) {
+						Ok(Ok(())) => TransactionOutcome::Rollback(Some(proxy.read_accounts(
+							|address, slots| {
+								AccountState::read::<B, _>(api, substrate_parent_id, address, slots)
+							},
+						))),
+						_ => TransactionOutcome::Commit(None),
+					}
+				});
+
+				if let Some(post) = post {
+					proxy.post = post?;
+					proxy.pre = proxy.read_accounts(|address, slots| {
+						AccountState::read::<B, _>(&*api, substrate_parent_id, address, slots)
+					})?;
+					let _ = api
+						.apply_extrinsic(substrate_parent_id, extrinsic)
+						.map_err(|e| format!("Runtime api access error: {:?}", e))?;
+					break
+				}
+			}
+
+			state_diffs.push(
+				peaq_client_evm_tracing::formatters::StateDiff::format(proxy)
+					.ok_or("Fail to format proxy")?,
+			);
+		}
+		Ok(state_diffs)
+	}
 }
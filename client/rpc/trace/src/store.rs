@@ -0,0 +1,228 @@
+//! Persistent store of the `trace_filter` traces of finalized blocks.
+//!
+//! Traces are kept in a ParityDB database under the node base path, so that they survive restarts
+//! and blocks leaving the `CacheTask` cache don't have to be replayed again.
+//!
+//! Only blocks which are finalized and part of the canonical chain are written, thus a reorg can't
+//! make an entry stale. If the chain is nonetheless reverted, the entry of a height is replaced as
+//! soon as another block is finalized at this height.
+
+use ethereum_types::H256;
+use parity_scale_codec::{Decode, Encode};
+use peaq_client_evm_tracing::types::block::TransactionTrace;
+use std::path::Path;
+
+/// Traces of a block, by block hash.
+const COLUMN_TRACES: u8 = 0;
+/// Hash of the stored block, by big endian block number. Ordered to prune the oldest blocks.
+const COLUMN_NUMBERS: u8 = 1;
+const NUM_COLUMNS: u8 = 2;
+
+type Transaction = Vec<(u8, Vec<u8>, Option<Vec<u8>>)>;
+
+pub struct TraceStore {
+	db: parity_db::Db,
+	/// Number of blocks kept below the last finalized one. All blocks are kept if `None`.
+	retention: Option<u32>,
+}
+
+impl TraceStore {
+	/// Open the store at `path`, creating it if needed.
+	pub fn open(path: &Path, retention: Option<u32>) -> Result<Self, String> {
+		let mut options = parity_db::Options::with_columns(path, NUM_COLUMNS);
+		options.columns[COLUMN_NUMBERS as usize].btree_index = true;
+
+		let db = parity_db::Db::open_or_create(&options)
+			.map_err(|e| format!("Failed to open the trace store at {:?} : {:?}", path, e))?;
+
+		Ok(Self { db, retention })
+	}
+
+	/// Traces of the given block, if stored.
+	pub fn get(&self, hash: H256) -> Option<Vec<TransactionTrace>> {
+		match self.db.get(COLUMN_TRACES, hash.as_bytes()) {
+			Ok(Some(value)) => Vec::<TransactionTrace>::decode(&mut &value[..])
+				.map_err(|e| {
+					tracing::warn!("Failed to decode stored traces of block {} : {:?}", hash, e)
+				})
+				.ok(),
+			Ok(None) => None,
+			Err(e) => {
+				tracing::warn!("Failed to read stored traces of block {} : {:?}", hash, e);
+				None
+			},
+		}
+	}
+
+	/// Whether the traces of the given block are stored.
+	pub fn contains(&self, hash: H256) -> bool {
+		matches!(self.db.get(COLUMN_TRACES, hash.as_bytes()), Ok(Some(_)))
+	}
+
+	/// Whether a block at this height is still within the retention depth.
+	pub fn is_retained(&self, number: u32, finalized: u32) -> bool {
+		self.retention
+			.map_or(true, |retention| number >= finalized.saturating_sub(retention))
+	}
+
+	/// Store the traces of the finalized block `number`, replacing the block previously stored at
+	/// this height if any.
+	pub fn insert(
+		&self,
+		number: u32,
+		hash: H256,
+		traces: &[TransactionTrace],
+		finalized: u32,
+	) -> Result<(), String> {
+		if !self.is_retained(number, finalized) {
+			return Ok(())
+		}
+
+		let mut transaction = self.replace_at(number, hash)?;
+		transaction.push((COLUMN_TRACES, hash.as_bytes().to_vec(), Some(traces.encode())));
+		transaction.push((
+			COLUMN_NUMBERS,
+			number.to_be_bytes().to_vec(),
+			Some(hash.as_bytes().to_vec()),
+		));
+
+		self.commit(transaction)
+	}
+
+	/// Update the store for a newly finalized block: an entry stored at its height for another
+	/// block is removed, as well as the blocks falling out of the retention depth.
+	pub fn finalize(&self, number: u32, hash: H256) -> Result<(), String> {
+		let mut transaction = self.replace_at(number, hash)?;
+		if !transaction.is_empty() {
+			transaction.push((COLUMN_NUMBERS, number.to_be_bytes().to_vec(), None));
+		}
+
+		if let Some(retention) = self.retention {
+			let lowest = number.saturating_sub(retention);
+			let mut iter = self
+				.db
+				.iter(COLUMN_NUMBERS)
+				.map_err(|e| format!("Failed to iterate the trace store : {:?}", e))?;
+			iter.seek_to_first()
+				.map_err(|e| format!("Failed to iterate the trace store : {:?}", e))?;
+
+			while let Some((key, value)) = iter
+				.next()
+				.map_err(|e| format!("Failed to iterate the trace store : {:?}", e))?
+			{
+				match <[u8; 4]>::try_from(&key[..]) {
+					Ok(stored) if u32::from_be_bytes(stored) >= lowest => break,
+					_ => {
+						transaction.push((COLUMN_TRACES, value, None));
+						transaction.push((COLUMN_NUMBERS, key, None));
+					},
+				}
+			}
+		}
+
+		if transaction.is_empty() {
+			return Ok(())
+		}
+		self.commit(transaction)
+	}
+
+	/// Changes removing the traces stored at `number` if they belong to another block than
+	/// `hash`.
+	fn replace_at(&self, number: u32, hash: H256) -> Result<Transaction, String> {
+		let stored = self
+			.db
+			.get(COLUMN_NUMBERS, &number.to_be_bytes())
+			.map_err(|e| format!("Failed to read the trace store : {:?}", e))?;
+
+		Ok(match stored {
+			Some(stored) if stored != hash.as_bytes() => {
+				tracing::debug!(
+					"Removing stored traces of non canonical block at height {}",
+					number
+				);
+				vec![(COLUMN_TRACES, stored, None)]
+			},
+			_ => vec![],
+		})
+	}
+
+	fn commit(&self, transaction: Transaction) -> Result<(), String> {
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("Failed to write the trace store : {:?}", e))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use peaq_client_evm_tracing::types::{
+		block::{TransactionTraceAction, TransactionTraceOutput},
+		CallType,
+	};
+
+	fn trace(block_number: u32) -> TransactionTrace {
+		TransactionTrace {
+			action: TransactionTraceAction::Call {
+				call_type: CallType::Call,
+				from: Default::default(),
+				gas: Default::default(),
+				input: vec![],
+				to: Default::default(),
+				value: Default::default(),
+			},
+			block_hash: H256::repeat_byte(block_number as u8),
+			block_number,
+			output: TransactionTraceOutput::Error(b"Reverted".to_vec()),
+			subtraces: 0,
+			trace_address: vec![],
+			transaction_hash: Default::default(),
+			transaction_position: 0,
+		}
+	}
+
+	fn temp_dir(name: &str) -> std::path::PathBuf {
+		let dir =
+			std::env::temp_dir().join(format!("peaq-trace-store-{}-{}", name, std::process::id()));
+		let _ = std::fs::remove_dir_all(&dir);
+		dir
+	}
+
+	#[test]
+	fn traces_survive_reopening() {
+		let dir = temp_dir("reopen");
+		let hash = H256::repeat_byte(1);
+		{
+			let store = TraceStore::open(&dir, None).unwrap();
+			store.insert(1, hash, &[trace(1)], 1).unwrap();
+		}
+
+		let store = TraceStore::open(&dir, None).unwrap();
+		assert_eq!(store.get(hash), Some(vec![trace(1)]));
+		assert_eq!(store.get(H256::repeat_byte(2)), None);
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn finalize_prunes_and_replaces_blocks() {
+		let dir = temp_dir("finalize");
+		let store = TraceStore::open(&dir, Some(2)).unwrap();
+		for number in 1..=4u32 {
+			store
+				.insert(number, H256::repeat_byte(number as u8), &[trace(number)], 4)
+				.unwrap();
+		}
+		// Out of the retention depth.
+		assert_eq!(store.get(H256::repeat_byte(1)), None);
+
+		// Another block is finalized at height 5 than the one stored.
+		store.insert(5, H256::repeat_byte(0xaa), &[trace(5)], 5).unwrap();
+		store.finalize(5, H256::repeat_byte(5)).unwrap();
+
+		assert_eq!(store.get(H256::repeat_byte(0xaa)), None);
+		assert_eq!(store.get(H256::repeat_byte(2)), None);
+		assert_eq!(store.get(H256::repeat_byte(3)), Some(vec![trace(3)]));
+		assert_eq!(store.get(H256::repeat_byte(4)), Some(vec![trace(4)]));
+		let _ = std::fs::remove_dir_all(&dir);
+	}
+}
@@ -36,6 +36,31 @@ pub struct RunCmd {
 	#[clap(long, default_value = "300")]
 	pub ethapi_trace_cache_duration: u64,
 
+	/// Persist the `trace_filter` traces of finalized blocks in the chain data directory, so they
+	/// are not replayed again after they leave the cache or after a restart.
+	#[clap(long)]
+	pub ethapi_trace_store: bool,
+
+	/// Number of blocks below the last finalized one the trace store keeps traces for. All of
+	/// them are kept if not provided.
+	#[clap(long, requires = "ethapi_trace_store")]
+	pub ethapi_trace_store_retention: Option<u32>,
+
+	/// Trace finalized blocks in the background and persist them in the trace store, so that
+	/// `trace_filter` requests don't have to replay them.
+	#[clap(long, requires = "ethapi_trace_store")]
+	pub ethapi_trace_indexer: bool,
+
+	/// Height from which the trace indexer backfills the blocks finalized before it started.
+	/// Only blocks finalized while the node runs are indexed if not provided.
+	#[clap(long, requires = "ethapi_trace_indexer")]
+	pub ethapi_trace_indexer_start: Option<u32>,
+
+	/// Number of blocks the trace indexer traces at once, using the permits of
+	/// `--ethapi-max-permits`. At least one of those is always left to the RPC requests.
+	#[clap(long, default_value = "1", requires = "ethapi_trace_indexer")]
+	pub ethapi_trace_indexer_permits: u32,
+
 	/// Size of the LRU cache for block data and their transaction statuses.
 	#[clap(long, default_value = "3000")]
 	pub eth_log_block_cache: usize,
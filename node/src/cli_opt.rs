@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum EthApi {
@@ -25,6 +25,11 @@ pub struct RpcConfig {
 	pub ethapi_max_permits: u32,
 	pub ethapi_trace_max_count: u32,
 	pub ethapi_trace_cache_duration: u64,
+	pub ethapi_trace_store: Option<PathBuf>,
+	pub ethapi_trace_store_retention: Option<u32>,
+	pub ethapi_trace_indexer: bool,
+	pub ethapi_trace_indexer_start: Option<u32>,
+	pub ethapi_trace_indexer_permits: u32,
 	pub eth_log_block_cache: usize,
 	pub eth_statuses_cache: usize,
 	pub fee_history_limit: u64,
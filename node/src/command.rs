@@ -427,6 +427,14 @@ pub fn run() -> sc_cli::Result<()> {
 					ethapi_max_permits: cli.run.ethapi_max_permits,
 					ethapi_trace_max_count: cli.run.ethapi_trace_max_count,
 					ethapi_trace_cache_duration: cli.run.ethapi_trace_cache_duration,
+					ethapi_trace_store: cli
+						.run
+						.ethapi_trace_store
+						.then(|| service::trace_store_dir(&config)),
+					ethapi_trace_store_retention: cli.run.ethapi_trace_store_retention,
+					ethapi_trace_indexer: cli.run.ethapi_trace_indexer,
+					ethapi_trace_indexer_start: cli.run.ethapi_trace_indexer_start,
+					ethapi_trace_indexer_permits: cli.run.ethapi_trace_indexer_permits,
 					eth_log_block_cache: cli.run.eth_log_block_cache,
 					eth_statuses_cache: cli.run.eth_statuses_cache,
 					fee_history_limit: cli.run.fee_history_limit,
@@ -139,14 +139,15 @@ fn configure_genesis(
 		inflation_manager: Default::default(),
 		block_reward: BlockRewardConfig {
 			// Make sure sum is 100
-			reward_config: pallet_block_reward::RewardDistributionConfig {
+			beneficiaries: pallet_block_reward::RewardDistributionConfig {
 				treasury_percent: Perbill::from_percent(25),
 				collators_delegators_percent: Perbill::from_percent(40),
 				coretime_percent: Perbill::from_percent(10),
 				subsidization_pool_percent: Perbill::from_percent(5),
 				depin_staking_percent: Perbill::from_percent(5),
 				depin_incentivization_percent: Perbill::from_percent(15),
-			},
+			}
+			.into_beneficiaries(),
 			_phantom: Default::default(),
 		},
 		vesting: peaq_dev_runtime::VestingConfig { vesting: vec![] },
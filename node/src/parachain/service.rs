@@ -78,6 +78,10 @@ pub fn frontier_database_dir(config: &Configuration, path: &str) -> std::path::P
 	config.base_path.config_dir(config.chain_spec.id()).join("frontier").join(path)
 }
 
+pub fn trace_store_dir(config: &Configuration) -> std::path::PathBuf {
+	config.base_path.config_dir(config.chain_spec.id()).join("trace")
+}
+
 pub fn open_frontier_backend<C: sp_blockchain::HeaderBackend<Block>>(
 	client: Arc<C>,
 	config: &Configuration,
@@ -310,7 +314,9 @@ where
 		+ peaq_rpc_primitives_txpool::TxPoolRuntimeApi<Block>
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>
-		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>,
+		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>
+		+ parachain_staking_rpc::ParachainStakingRuntimeApi<Block, AccountId, Balance>
+		+ address_unification_rpc::AddressUnificationRuntimeApi<Block, AccountId>,
 	sc_client_api::StateBackendFor<FullBackend, Block>: sp_api::StateBackend<BlakeTwo256>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 	BIQ: FnOnce(
@@ -473,6 +479,7 @@ where
 					overrides: overrides.clone(),
 					fee_history_limit,
 					fee_history_cache: fee_history_cache.clone(),
+					prometheus_registry: prometheus_registry.clone(),
 				},
 			)
 		} else {
@@ -732,7 +739,9 @@ where
 		+ sp_consensus_aura::AuraApi<Block, AuraId>
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>
-		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>,
+		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>
+		+ parachain_staking_rpc::ParachainStakingRuntimeApi<Block, AccountId, Balance>
+		+ address_unification_rpc::AddressUnificationRuntimeApi<Block, AccountId>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 {
 	start_contracts_node_impl::<RuntimeApi, Executor, _, _>(
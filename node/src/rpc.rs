@@ -38,6 +38,7 @@ pub struct SpawnTasksParams<'a, B: BlockT, C, BE> {
 	pub overrides: Arc<OverrideHandle<B>>,
 	pub fee_history_limit: u64,
 	pub fee_history_cache: FeeHistoryCache,
+	pub prometheus_registry: Option<substrate_prometheus_endpoint::Registry>,
 }
 
 pub type XcmSenders = Option<(flume::Sender<Vec<u8>>, flume::Sender<(ParaId, Vec<u8>)>)>;
@@ -115,16 +116,20 @@ where
 	C::Api: peaq_rpc_primitives_txpool::TxPoolRuntimeApi<Block>,
 	C::Api: peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>,
 	C::Api: zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>,
+	C::Api: parachain_staking_rpc::ParachainStakingRuntimeApi<Block, AccountId, Balance>,
+	C::Api: address_unification_rpc::AddressUnificationRuntimeApi<Block, AccountId>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 
 	BE::Blockchain: BlockchainBackend<Block>,
 {
+	use address_unification_rpc::{AddressUnification, AddressUnificationApiServer};
 	use fc_rpc::{
 		Eth, EthApiServer, EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, Net,
 		NetApiServer, Web3, Web3ApiServer,
 	};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use parachain_staking_rpc::{ParachainStaking, ParachainStakingApiServer};
 	use peaq_pallet_did_rpc::{PeaqDID, PeaqDIDApiServer};
 	use peaq_pallet_rbac_rpc::{PeaqRBAC, PeaqRBACApiServer};
 	use peaq_pallet_storage_rpc::{PeaqStorage, PeaqStorageApiServer};
@@ -195,7 +200,7 @@ where
 		io.merge(
 			EthFilter::new(
 				client.clone(),
-				frontier_backend,
+				Arc::clone(&frontier_backend),
 				fc_rpc::TxPool::new(client.clone(), graph.clone()),
 				filter_pool,
 				500_usize, // max stored filters
@@ -225,6 +230,8 @@ where
 	io.merge(PeaqDID::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(PeaqRBAC::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(ZenlinkProtocol::new(Arc::clone(&client)).into_rpc())?;
+	io.merge(ParachainStaking::new(Arc::clone(&client)).into_rpc())?;
+	io.merge(AddressUnification::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(Web3::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(
 		EthPubSub::new(
@@ -244,8 +251,13 @@ where
 	if let Some(tracing_config) = maybe_tracing_config {
 		if let Some(trace_filter_requester) = tracing_config.tracing_requesters.trace {
 			io.merge(
-				Trace::new(client, trace_filter_requester, tracing_config.trace_filter_max_count)
-					.into_rpc(),
+				Trace::new(
+					client,
+					frontier_backend,
+					trace_filter_requester,
+					tracing_config.trace_filter_max_count,
+				)
+				.into_rpc(),
 			)?;
 		}
 
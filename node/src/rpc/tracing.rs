@@ -17,7 +17,9 @@
 use super::*;
 
 use peaq_rpc_debug::{DebugHandler, DebugRequester};
-use peaq_rpc_trace::{CacheRequester as TraceFilterCacheRequester, CacheTask};
+use peaq_rpc_trace::{
+	CacheRequester as TraceFilterCacheRequester, CacheTask, IndexerTask, TraceStore,
+};
 use tokio::sync::Semaphore;
 
 use crate::cli_opt::EthApi as EthApiCmd;
@@ -54,6 +56,17 @@ where
 {
 	let permit_pool = Arc::new(Semaphore::new(rpc_config.ethapi_max_permits as usize));
 
+	let trace_store = if rpc_config.ethapi.contains(&EthApiCmd::Trace) {
+		rpc_config.ethapi_trace_store.as_ref().and_then(|path| {
+			TraceStore::open(path, rpc_config.ethapi_trace_store_retention)
+				.map_err(|e| log::error!("{}, traces will not be persisted", e))
+				.ok()
+				.map(Arc::new)
+		})
+	} else {
+		None
+	};
+
 	let (trace_filter_task, trace_filter_requester) =
 		if rpc_config.ethapi.contains(&EthApiCmd::Trace) {
 			let (trace_filter_task, trace_filter_requester) = CacheTask::create(
@@ -62,12 +75,27 @@ where
 				Duration::from_secs(rpc_config.ethapi_trace_cache_duration),
 				Arc::clone(&permit_pool),
 				Arc::clone(&params.overrides),
+				trace_store.clone(),
 			);
 			(Some(trace_filter_task), Some(trace_filter_requester))
 		} else {
 			(None, None)
 		};
 
+	let trace_indexer_task = match trace_store {
+		Some(trace_store) if rpc_config.ethapi_trace_indexer => Some(IndexerTask::create(
+			Arc::clone(&params.client),
+			Arc::clone(&params.substrate_backend),
+			trace_store,
+			Arc::clone(&permit_pool),
+			rpc_config.ethapi_trace_indexer_permits as usize,
+			Arc::clone(&params.overrides),
+			rpc_config.ethapi_trace_indexer_start,
+			params.prometheus_registry.as_ref(),
+		)),
+		_ => None,
+	};
+
 	let (debug_task, debug_requester) = if rpc_config.ethapi.contains(&EthApiCmd::Debug) {
 		let (debug_task, debug_requester) = DebugHandler::task(
 			Arc::clone(&params.client),
@@ -94,6 +122,15 @@ where
 		);
 	}
 
+	// Trace indexer task if enabled. Fills the `trace_filter` store ahead of the requests.
+	if let Some(trace_indexer_task) = trace_indexer_task {
+		params.task_manager.spawn_handle().spawn(
+			"trace-indexer",
+			Some("eth-tracing"),
+			trace_indexer_task,
+		);
+	}
+
 	// `debug` task if enabled. Essential.
 	// Proxies rpc requests to it's handler.
 	if let Some(debug_task) = debug_task {
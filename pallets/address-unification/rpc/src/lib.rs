@@ -0,0 +1,145 @@
+//! RPC interface for the address-unification pallet.
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::Codec;
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{bytes::to_hex, H160};
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+pub use address_unification_runtime_api::{
+	AccountMapping, AddressUnificationApi as AddressUnificationRuntimeApi, Eip712ClaimData,
+};
+
+#[rpc(client, server)]
+pub trait AddressUnificationApi<BlockHash, AccountId> {
+	/// The account mapped to an EVM address, the default account if the
+	/// address is not linked.
+	#[method(name = "addressUnification_getAccountId")]
+	fn get_account_id(
+		&self,
+		evm_address: H160,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountMapping<AccountId>>;
+
+	/// The EVM address mapped to an account, the default EVM address if the
+	/// account is not linked.
+	#[method(name = "addressUnification_getEvmAddress")]
+	fn get_evm_address(
+		&self,
+		account_id: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountMapping<AccountId>>;
+
+	/// The EIP-712 typed data an EVM address has to sign to be claimed by an
+	/// account, as expected by `eth_signTypedData_v4`.
+	#[method(name = "addressUnification_getClaimTypedData")]
+	fn get_claim_typed_data(
+		&self,
+		account_id: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Value>;
+}
+
+/// A struct that implements the [`AddressUnificationApiServer`].
+pub struct AddressUnification<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> AddressUnification<C, B> {
+	/// Create new `AddressUnification` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for failed runtime calls.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Builds the typed data of the `Transaction(bytes substrateAddress)` claim message.
+pub fn claim_typed_data(claim_data: Eip712ClaimData) -> Value {
+	json!({
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "version", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "salt", "type": "bytes32" },
+			],
+			"Transaction": [
+				{ "name": "substrateAddress", "type": "bytes" },
+			],
+		},
+		"primaryType": "Transaction",
+		"domain": {
+			"name": String::from_utf8_lossy(&claim_data.name),
+			"version": String::from_utf8_lossy(&claim_data.version),
+			"chainId": claim_data.chain_id,
+			"salt": to_hex(claim_data.salt.as_bytes(), false),
+		},
+		"message": {
+			"substrateAddress": to_hex(&claim_data.substrate_address, false),
+		},
+	})
+}
+
+impl<C, Block, AccountId> AddressUnificationApiServer<<Block as BlockT>::Hash, AccountId>
+	for AddressUnification<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AddressUnificationRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn get_account_id(
+		&self,
+		evm_address: H160,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccountMapping<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_account_id(at, evm_address).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_evm_address(
+		&self,
+		account_id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccountMapping<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_evm_address(at, account_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_claim_typed_data(
+		&self,
+		account_id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Value> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_claim_data(at, account_id)
+			.map(claim_typed_data)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,25 @@
+//! Runtime API definition for the address-unification pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_core::H160;
+
+pub use address_unification::{AccountMapping, Eip712ClaimData};
+
+sp_api::decl_runtime_apis! {
+	pub trait AddressUnificationApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// The account mapped to an EVM address, the default account if the
+		/// address is not linked.
+		fn get_account_id(evm_address: H160) -> AccountMapping<AccountId>;
+		/// The EVM address mapped to an account, the default EVM address if
+		/// the account is not linked.
+		fn get_evm_address(account_id: AccountId) -> AccountMapping<AccountId>;
+		/// The EIP-712 domain and message an EVM address has to sign to be
+		/// claimed by an account.
+		fn get_claim_data(account_id: AccountId) -> Eip712ClaimData;
+	}
+}
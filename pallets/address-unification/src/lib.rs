@@ -29,7 +29,11 @@
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{fungible, Currency, IsType, OnKilledAccount},
+	traits::{
+		fungible, fungibles,
+		tokens::{Fortitude, Preservation},
+		Currency, IsType, OnKilledAccount,
+	},
 	transactional,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
@@ -41,7 +45,10 @@ use peaq_primitives_xcm::{evm::EvmAddress, to_bytes, AccountIndex};
 use sp_core::{H160, H256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 use sp_runtime::{
-	traits::{LookupError, StaticLookup, Zero},
+	traits::{
+		CheckedSub, IdentifyAccount, LookupError, One, Saturating, StaticLookup,
+		UniqueSaturatedInto, Verify, Zero,
+	},
 	MultiAddress,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
@@ -50,17 +57,20 @@ mod convert_impl;
 mod mock;
 mod tests;
 mod traits;
+pub mod types;
 pub mod weights;
 
 use convert_impl::*;
 pub use module::*;
 pub use traits::EVMAddressMapping;
+pub use types::{AccountMapping, Eip712ClaimData};
 pub use weights::WeightInfo;
 
 /// A signature (a 512-bit value, plus 8 bits for recovery ID).
 pub type Eip712Signature = [u8; 65];
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::AssetId;
 
 #[frame_support::pallet]
 pub mod module {
@@ -72,9 +82,24 @@ pub mod module {
 
 		/// The Currency for managing Evm account assets.
 		type Currency: Currency<Self::AccountId>
-			+ fungible::Inspect<Self::AccountId, Balance = BalanceOf<Self>>;
+			+ fungible::Inspect<Self::AccountId, Balance = BalanceOf<Self>>
+			+ fungible::Mutate<Self::AccountId>;
+
+		/// The assets which can be merged from the EVM padded account when claiming.
+		type Assets: fungibles::Mutate<Self::AccountId>;
+
+		/// Maximum number of assets which can be merged in one claim.
+		#[pallet::constant]
+		type MaxMergedAssets: Get<u32>;
 
 		type OriginAddressMapping: PalletEVMAddressMapping<Self::AccountId>;
+
+		/// Signature of a Substrate account, proving its ownership when an EVM address claims
+		/// it.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key behind `OffchainSignature`.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
 		/*
 		 *         /// Mapping from address to account id.
 		 *         type AddressMapping: EVMAddressMapping<Self::AccountId>;
@@ -84,6 +109,14 @@ pub mod module {
 		#[pallet::constant]
 		type ChainId: Get<u64>;
 
+		/// Maximum number of secondary EVM addresses of an account.
+		#[pallet::constant]
+		type MaxSecondaryEvmAddresses: Get<u32>;
+
+		/// Number of blocks an unlinked EvmAddress can't be linked again.
+		#[pallet::constant]
+		type UnlinkCooldown: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -94,6 +127,26 @@ pub mod module {
 		/// Mapping between Substrate accounts and EVM accounts
 		/// claim account.
 		ClaimAccount { account_id: T::AccountId, evm_address: EvmAddress },
+		/// Mapping between Substrate accounts and EVM accounts
+		/// removed.
+		UnlinkAccount { account_id: T::AccountId, evm_address: EvmAddress },
+		/// Mapping of a Substrate account moved to a new EVM account.
+		RelinkAccount {
+			account_id: T::AccountId,
+			old_evm_address: EvmAddress,
+			new_evm_address: EvmAddress,
+		},
+		/// The balances of the EVM padded account were merged into the
+		/// claiming account.
+		MergeAccount {
+			account_id: T::AccountId,
+			merged_account_id: T::AccountId,
+			balance: BalanceOf<T>,
+		},
+		/// A secondary EVM address was added to a Substrate account.
+		SecondaryEvmAddressAdded { account_id: T::AccountId, evm_address: EvmAddress },
+		/// A secondary EVM address was removed from a Substrate account.
+		SecondaryEvmAddressRemoved { account_id: T::AccountId, evm_address: EvmAddress },
 	}
 
 	/// Error for evm accounts module.
@@ -111,6 +164,18 @@ pub mod module {
 		NonZeroRefCount,
 		/// Non Empty Accounts
 		NonEmptyAccounts,
+		/// AccountId has not been mapped
+		AccountIdNotMapped,
+		/// Eth address has not been mapped
+		EthAddressNotMapped,
+		/// Eth address has been unlinked recently
+		EthAddressInCooldown,
+		/// Too many secondary Eth addresses
+		TooManyEvmAddresses,
+		/// Eth address is not a secondary address of the account
+		NotSecondaryEvmAddress,
+		/// The EVM padded account holds locked or reserved balance
+		NonTransferableBalance,
 	}
 
 	/// The Substrate Account for EvmAddresses
@@ -129,16 +194,71 @@ pub mod module {
 	pub type EvmAddresses<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, EvmAddress, OptionQuery>;
 
+	/// The secondary EvmAddresses for Substrate Accounts, resolved by `Accounts` as well
+	///
+	/// SecondaryEvmAddresses: map AccountId => Vec<EvmAddress>
+	#[pallet::storage]
+	#[pallet::getter(fn secondary_evm_addresses)]
+	pub type SecondaryEvmAddresses<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<EvmAddress, T::MaxSecondaryEvmAddresses>,
+		ValueQuery,
+	>;
+
+	/// The block at which an EvmAddress was unlinked, until its cooldown passed
+	///
+	/// UnlinkedAt: map EvmAddress => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn unlinked_at)]
+	pub type UnlinkedAt<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The EvmAddresses unlinked at a block, to clear their `UnlinkedAt` once the cooldown
+	/// passed
+	///
+	/// UnlinkedInBlock: double_map BlockNumber, EvmAddress => Option<()>
+	#[pallet::storage]
+	pub type UnlinkedInBlock<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		EvmAddress,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// the cooldown of the addresses unlinked at this block passed in the previous one
+			let Some(unlinked_at) =
+				now.checked_sub(&T::UnlinkCooldown::get().saturating_add(One::one()))
+			else {
+				return Weight::zero()
+			};
+
+			let mut cleared = 0u64;
+			for (evm_address, ()) in UnlinkedInBlock::<T>::drain_prefix(unlinked_at) {
+				if UnlinkedAt::<T>::get(evm_address) == Some(unlinked_at) {
+					UnlinkedAt::<T>::remove(evm_address);
+				}
+				cleared += 1;
+			}
+			T::DbWeight::get().reads_writes(cleared + 1, cleared * 2)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Claim account mapping between Substrate accounts and EVM accounts.
-		/// Ensure evm_address has not been mapped.
+		/// Ensure evm_address has not been mapped and its padded account is empty, use
+		/// `claim_account_and_merge` otherwise.
 		///
 		/// - `evm_address`: The address to bind to the caller's account
 		/// - `eth_signature`: A signature generated by the address to prove ownership
@@ -153,40 +273,14 @@ pub mod module {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// ensure account_id and evm_address has not been mapped
-			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
-			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
-
-			// recover evm address from signature
-			let address = Self::verify_eip712_signature(&who, &eth_signature)
-				.ok_or(Error::<T>::BadSignature)?;
-			ensure!(evm_address == address, Error::<T>::InvalidSignature);
-
 			// check if the evm padded address already exists
-			let ori_account_id = T::OriginAddressMapping::into_account_id(evm_address);
+			let ori_account_id = Self::ensure_claimable(&who, evm_address, &eth_signature)?;
 			ensure!(
 				!frame_system::Pallet::<T>::account_exists(&ori_account_id),
 				Error::<T>::NonEmptyAccounts
 			);
-			/*
-			 * TODO, we don't allow users to use the non-empty account to do the linking.
-			 * if frame_system::Pallet::<T>::account_exists(&ori_account_id) {
-			 *     // merge balance from `evm padded address` to `origin`
-			 *     // Sould transfer all...
-			 *     let amount = T::Currency::reducible_balance(
-			 *         &ori_account_id,
-			 *         Preservation::Expendable,
-			 *         Fortitude::Polite,
-			 *     );
-			 *     T::Currency::transfer(&ori_account_id, &who, amount,
-			 * ExistenceRequirement::AllowDeath)?; }
-			 */
-
-			Accounts::<T>::insert(evm_address, &who);
-			EvmAddresses::<T>::insert(&who, evm_address);
-
-			Self::deposit_event(Event::ClaimAccount { account_id: who, evm_address });
 
+			Self::do_claim(who, evm_address);
 			Ok(())
 		}
 
@@ -205,6 +299,7 @@ pub mod module {
 			// make sure default address is not already mapped, this should not
 			// happen but for sanity check.
 			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
+			Self::ensure_not_in_cooldown(&evm_address)?;
 
 			// TODO, we don't allow users to use the non-empty account to do the linking.
 			let ori_account_id = T::OriginAddressMapping::into_account_id(evm_address);
@@ -219,6 +314,234 @@ pub mod module {
 			Self::deposit_event(Event::ClaimAccount { account_id: who, evm_address });
 			Ok(())
 		}
+
+		/// Remove the account mapping of the caller's account.
+		/// The EVM address can't be linked again until `UnlinkCooldown` passed.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::unlink_account(T::MaxSecondaryEvmAddresses::get()))]
+		pub fn unlink_account(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let evm_address = EvmAddresses::<T>::get(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+
+			Self::do_unlink(who, evm_address);
+			Ok(())
+		}
+
+		/// Remove the account mapping of an EVM address with a signature of the EVM key, e.g.
+		/// when the key of the Substrate account was lost.
+		/// The EVM address can't be linked again until `UnlinkCooldown` passed.
+		///
+		/// - `evm_address`: The address to unlink
+		/// - `eth_signature`: A signature of the `Unlink` message generated by the address,
+		///   containing the current nonce of the linked Substrate account
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::unlink_account_with_signature(
+			T::MaxSecondaryEvmAddresses::get()
+		))]
+		pub fn unlink_account_with_signature(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = Accounts::<T>::get(evm_address).ok_or(Error::<T>::EthAddressNotMapped)?;
+
+			// recover evm address from signature
+			let address = Self::verify_eip712_unlink_signature(&who, &eth_signature)
+				.ok_or(Error::<T>::BadSignature)?;
+			ensure!(evm_address == address, Error::<T>::InvalidSignature);
+
+			Self::do_unlink(who, evm_address);
+			Ok(())
+		}
+
+		/// Move the account mapping of the caller's account to a new EVM address.
+		/// The old EVM address can't be linked again until `UnlinkCooldown` passed.
+		///
+		/// - `evm_address`: The new address to bind to the caller's account
+		/// - `eth_signature`: A signature generated by the new address to prove ownership
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::relink_account())]
+		#[transactional]
+		pub fn relink_account(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let old_evm_address =
+				EvmAddresses::<T>::get(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
+			Self::ensure_not_in_cooldown(&evm_address)?;
+
+			// recover evm address from signature
+			let address = Self::verify_eip712_signature(&who, &eth_signature)
+				.ok_or(Error::<T>::BadSignature)?;
+			ensure!(evm_address == address, Error::<T>::InvalidSignature);
+
+			// check if the evm padded address already exists
+			let ori_account_id = T::OriginAddressMapping::into_account_id(evm_address);
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&ori_account_id),
+				Error::<T>::NonEmptyAccounts
+			);
+
+			Accounts::<T>::remove(old_evm_address);
+			Self::start_cooldown(old_evm_address, frame_system::Pallet::<T>::block_number());
+			Accounts::<T>::insert(evm_address, &who);
+			EvmAddresses::<T>::insert(&who, evm_address);
+
+			Self::deposit_event(Event::RelinkAccount {
+				account_id: who,
+				old_evm_address,
+				new_evm_address: evm_address,
+			});
+			Ok(())
+		}
+
+		/// Claim account mapping between Substrate accounts and EVM accounts, and
+		/// merge the balances held by the EVM padded account into the caller's account.
+		/// The native balance and the full balance of each of the given assets are
+		/// merged. Fails if the padded account holds locked or reserved balance, which is
+		/// owned by other pallets and cannot be moved. The nonce of the caller's account
+		/// is raised to the one of the padded account, such that transactions signed
+		/// by the EVM address before cannot be replayed.
+		///
+		/// - `evm_address`: The address to bind to the caller's account
+		/// - `eth_signature`: A signature generated by the address to prove ownership
+		/// - `assets`: The assets to merge from the padded account
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::claim_account_and_merge(assets.len() as u32))]
+		#[transactional]
+		pub fn claim_account_and_merge(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+			eth_signature: Eip712Signature,
+			assets: BoundedVec<AssetIdOf<T>, T::MaxMergedAssets>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let ori_account_id = Self::ensure_claimable(&who, evm_address, &eth_signature)?;
+			Self::do_merge(&ori_account_id, &who, &assets)?;
+
+			Self::do_claim(who, evm_address);
+			Ok(())
+		}
+
+		/// Add a secondary EVM address to the caller's account, which resolves to the
+		/// account as well. The primary address stays the reverse mapping of the account.
+		///
+		/// - `evm_address`: The address to add to the caller's account
+		/// - `eth_signature`: A signature generated by the address to prove ownership
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::add_secondary_evm_address())]
+		#[transactional]
+		pub fn add_secondary_evm_address(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdNotMapped);
+			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
+			Self::ensure_not_in_cooldown(&evm_address)?;
+
+			// recover evm address from signature
+			let address = Self::verify_eip712_signature(&who, &eth_signature)
+				.ok_or(Error::<T>::BadSignature)?;
+			ensure!(evm_address == address, Error::<T>::InvalidSignature);
+
+			// check if the evm padded address already exists
+			let ori_account_id = T::OriginAddressMapping::into_account_id(evm_address);
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&ori_account_id),
+				Error::<T>::NonEmptyAccounts
+			);
+
+			SecondaryEvmAddresses::<T>::try_mutate(&who, |evm_addresses| {
+				evm_addresses.try_push(evm_address).map_err(|_| Error::<T>::TooManyEvmAddresses)
+			})?;
+			Accounts::<T>::insert(evm_address, &who);
+
+			Self::deposit_event(Event::SecondaryEvmAddressAdded { account_id: who, evm_address });
+			Ok(())
+		}
+
+		/// Remove a secondary EVM address from the caller's account.
+		/// The EVM address can't be linked again until `UnlinkCooldown` passed.
+		///
+		/// - `evm_address`: The secondary address to remove
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::remove_secondary_evm_address())]
+		pub fn remove_secondary_evm_address(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			SecondaryEvmAddresses::<T>::try_mutate_exists(&who, |maybe_evm_addresses| {
+				let evm_addresses =
+					maybe_evm_addresses.as_mut().ok_or(Error::<T>::NotSecondaryEvmAddress)?;
+				let index = evm_addresses
+					.iter()
+					.position(|address| *address == evm_address)
+					.ok_or(Error::<T>::NotSecondaryEvmAddress)?;
+				evm_addresses.remove(index);
+				if evm_addresses.is_empty() {
+					*maybe_evm_addresses = None;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Accounts::<T>::remove(evm_address);
+			Self::start_cooldown(evm_address, frame_system::Pallet::<T>::block_number());
+
+			Self::deposit_event(Event::SecondaryEvmAddressRemoved { account_id: who, evm_address });
+			Ok(())
+		}
+
+		/// Claim account mapping between Substrate accounts and EVM accounts from the EVM
+		/// side, with the EVM padded account as origin, e.g. through the precompile.
+		/// The balances held by the padded account are merged into `account_id` as with
+		/// `claim_account_and_merge`, which reaps the padded account.
+		///
+		/// - `evm_address`: The address of the caller, whose padded account is the origin
+		/// - `account_id`: The Substrate account to bind to the address
+		/// - `signature`: A signature of `substrate_signable_message` generated by `account_id` to
+		///   prove ownership
+		/// - `assets`: The assets to merge from the padded account
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::claim_account_from_evm(assets.len() as u32))]
+		#[transactional]
+		pub fn claim_account_from_evm(
+			origin: OriginFor<T>,
+			evm_address: EvmAddress,
+			account_id: T::AccountId,
+			signature: T::OffchainSignature,
+			assets: BoundedVec<AssetIdOf<T>, T::MaxMergedAssets>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				who == T::OriginAddressMapping::into_account_id(evm_address),
+				DispatchError::BadOrigin
+			);
+
+			// ensure account_id and evm_address has not been mapped
+			ensure!(!EvmAddresses::<T>::contains_key(&account_id), Error::<T>::AccountIdHasMapped);
+			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
+			Self::ensure_not_in_cooldown(&evm_address)?;
+
+			ensure!(
+				Self::verify_substrate_signature(&account_id, evm_address, &signature),
+				Error::<T>::InvalidSignature
+			);
+
+			Self::do_merge(&who, &account_id, &assets)?;
+
+			Self::do_claim(account_id, evm_address);
+			Ok(())
+		}
 	}
 }
 
@@ -247,6 +570,172 @@ impl<T: Config> Pallet<T> {
 		r
 	}
 
+	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
+	// Constructs an unlink message and signs it.
+	pub fn eth_sign_unlink(
+		secret: &libsecp256k1::SecretKey,
+		who: &T::AccountId,
+	) -> Eip712Signature {
+		let msg = keccak_256(&Self::eip712_unlink_signable_message(who));
+		let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+		let mut r = [0u8; 65];
+		r[0..64].copy_from_slice(&sig.serialize()[..]);
+		r[64] = recovery_id.serialize();
+		r
+	}
+
+	// Checks the claim of `evm_address` by `who` and returns the EVM padded account
+	fn ensure_claimable(
+		who: &T::AccountId,
+		evm_address: EvmAddress,
+		eth_signature: &Eip712Signature,
+	) -> Result<T::AccountId, DispatchError> {
+		// ensure account_id and evm_address has not been mapped
+		ensure!(!EvmAddresses::<T>::contains_key(who), Error::<T>::AccountIdHasMapped);
+		ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EthAddressHasMapped);
+
+		Self::ensure_not_in_cooldown(&evm_address)?;
+
+		// recover evm address from signature
+		let address =
+			Self::verify_eip712_signature(who, eth_signature).ok_or(Error::<T>::BadSignature)?;
+		ensure!(evm_address == address, Error::<T>::InvalidSignature);
+
+		Ok(T::OriginAddressMapping::into_account_id(evm_address))
+	}
+
+	fn do_claim(who: T::AccountId, evm_address: EvmAddress) {
+		Accounts::<T>::insert(evm_address, &who);
+		EvmAddresses::<T>::insert(&who, evm_address);
+
+		Self::deposit_event(Event::ClaimAccount { account_id: who, evm_address });
+	}
+
+	// Moves the balances of the padded account `ori_account_id` to `who`
+	fn do_merge(
+		ori_account_id: &T::AccountId,
+		who: &T::AccountId,
+		assets: &[AssetIdOf<T>],
+	) -> DispatchResult {
+		// assets first, so the consumer references they hold on the padded account are
+		// released before the native balance is moved
+		for asset in assets {
+			let amount = <T::Assets as fungibles::Inspect<_>>::reducible_balance(
+				asset.clone(),
+				ori_account_id,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			);
+			if !amount.is_zero() {
+				<T::Assets as fungibles::Mutate<_>>::transfer(
+					asset.clone(),
+					ori_account_id,
+					who,
+					amount,
+					Preservation::Expendable,
+				)?;
+			}
+		}
+
+		let balance = <T::Currency as fungible::Inspect<_>>::reducible_balance(
+			ori_account_id,
+			Preservation::Expendable,
+			Fortitude::Polite,
+		);
+		// locked and reserved balances would be stranded once the EVM address resolves
+		// to `who`
+		ensure!(
+			<T::Currency as fungible::Inspect<_>>::total_balance(ori_account_id) == balance,
+			Error::<T>::NonTransferableBalance
+		);
+
+		// the EVM address resolves to `who` from now on, its transactions must not become
+		// valid again with the lower nonce of `who`
+		let nonce = frame_system::Pallet::<T>::account_nonce(ori_account_id);
+		if frame_system::Pallet::<T>::account_nonce(who) < nonce {
+			frame_system::Account::<T>::mutate(who, |account| account.nonce = nonce);
+		}
+
+		if !balance.is_zero() {
+			<T::Currency as fungible::Mutate<_>>::transfer(
+				ori_account_id,
+				who,
+				balance,
+				Preservation::Expendable,
+			)?;
+		}
+
+		Self::deposit_event(Event::MergeAccount {
+			account_id: who.clone(),
+			merged_account_id: ori_account_id.clone(),
+			balance,
+		});
+		Ok(())
+	}
+
+	fn ensure_not_in_cooldown(evm_address: &EvmAddress) -> DispatchResult {
+		if let Some(unlinked_at) = UnlinkedAt::<T>::get(evm_address) {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= unlinked_at.saturating_add(T::UnlinkCooldown::get()),
+				Error::<T>::EthAddressInCooldown
+			);
+			UnlinkedAt::<T>::remove(evm_address);
+			UnlinkedInBlock::<T>::remove(unlinked_at, evm_address);
+		}
+		Ok(())
+	}
+
+	// Prevents `evm_address` from being linked again until `UnlinkCooldown` passed
+	fn start_cooldown(evm_address: EvmAddress, now: T::BlockNumber) {
+		UnlinkedAt::<T>::insert(evm_address, now);
+		UnlinkedInBlock::<T>::insert(now, evm_address, ());
+	}
+
+	// Unlinks the primary EVM address of `who`, together with its secondary addresses
+	fn do_unlink(who: T::AccountId, evm_address: EvmAddress) {
+		let now = frame_system::Pallet::<T>::block_number();
+		for secondary_evm_address in SecondaryEvmAddresses::<T>::take(&who) {
+			Accounts::<T>::remove(secondary_evm_address);
+			Self::start_cooldown(secondary_evm_address, now);
+			Self::deposit_event(Event::SecondaryEvmAddressRemoved {
+				account_id: who.clone(),
+				evm_address: secondary_evm_address,
+			});
+		}
+
+		Accounts::<T>::remove(evm_address);
+		EvmAddresses::<T>::remove(&who);
+		Self::start_cooldown(evm_address, now);
+
+		Self::deposit_event(Event::UnlinkAccount { account_id: who, evm_address });
+	}
+
+	// Signers may wrap the message in `<Bytes>` tags, as the polkadot.js extension does
+	fn verify_substrate_signature(
+		who: &T::AccountId,
+		evm_address: EvmAddress,
+		signature: &T::OffchainSignature,
+	) -> bool {
+		let msg = Self::substrate_signable_message(who, evm_address);
+		let mut wrapped_msg = b"<Bytes>".to_vec();
+		wrapped_msg.extend_from_slice(&msg);
+		wrapped_msg.extend_from_slice(b"</Bytes>");
+
+		signature.verify(msg.as_slice(), who) || signature.verify(wrapped_msg.as_slice(), who)
+	}
+
+	/// The message `who` has to sign to be claimed by `evm_address` from the EVM side.
+	/// The nonce of `who` prevents replaying the signature after unlinking.
+	pub fn substrate_signable_message(who: &T::AccountId, evm_address: EvmAddress) -> Vec<u8> {
+		let nonce: u64 = frame_system::Pallet::<T>::account_nonce(who).unique_saturated_into();
+		let mut msg = types::SUBSTRATE_CLAIM_PREFIX.to_vec();
+		msg.extend_from_slice(evm_address.as_bytes());
+		msg.extend_from_slice(&to_bytes(nonce));
+		msg.extend_from_slice(Self::genesis_hash().as_bytes());
+		msg
+	}
+
 	fn verify_eip712_signature(who: &T::AccountId, sig: &[u8; 65]) -> Option<H160> {
 		let msg = Self::eip712_signable_message(who);
 		let msg_hash = keccak_256(msg.as_slice());
@@ -265,6 +754,34 @@ impl<T: Config> Pallet<T> {
 		msg
 	}
 
+	fn verify_eip712_unlink_signature(who: &T::AccountId, sig: &[u8; 65]) -> Option<H160> {
+		let msg = Self::eip712_unlink_signable_message(who);
+		let msg_hash = keccak_256(msg.as_slice());
+
+		recover_signer(sig, &msg_hash)
+	}
+
+	// Eip-712 unlink message to be signed
+	fn eip712_unlink_signable_message(who: &T::AccountId) -> Vec<u8> {
+		let domain_separator = Self::evm_account_domain_separator();
+		let payload_hash = Self::evm_account_unlink_payload_hash(who);
+
+		let mut msg = b"\x19\x01".to_vec();
+		msg.extend_from_slice(&domain_separator);
+		msg.extend_from_slice(&payload_hash);
+		msg
+	}
+
+	// The nonce of the Substrate account prevents replaying the signature after relinking
+	fn evm_account_unlink_payload_hash(who: &T::AccountId) -> [u8; 32] {
+		let tx_type_hash = keccak256!("Unlink(bytes substrateAddress,uint256 nonce)");
+		let nonce: u64 = frame_system::Pallet::<T>::account_nonce(who).unique_saturated_into();
+		let mut tx_msg = tx_type_hash.to_vec();
+		tx_msg.extend_from_slice(&keccak_256(&who.encode()));
+		tx_msg.extend_from_slice(&to_bytes(nonce));
+		keccak_256(tx_msg.as_slice())
+	}
+
 	fn evm_account_payload_hash(who: &T::AccountId) -> [u8; 32] {
 		let tx_type_hash = keccak256!("Transaction(bytes substrateAddress)");
 		let mut tx_msg = tx_type_hash.to_vec();
@@ -276,15 +793,46 @@ impl<T: Config> Pallet<T> {
 		let domain_hash =
 			keccak256!("EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)");
 		let mut domain_seperator_msg = domain_hash.to_vec();
-		domain_seperator_msg.extend_from_slice(&keccak256!("Peaq EVM claim")); // name
-		domain_seperator_msg.extend_from_slice(&keccak256!("1")); // version
+		domain_seperator_msg.extend_from_slice(&keccak_256(types::EIP712_DOMAIN_NAME)); // name
+		domain_seperator_msg.extend_from_slice(&keccak_256(types::EIP712_DOMAIN_VERSION)); // version
 		domain_seperator_msg.extend_from_slice(&to_bytes(T::ChainId::get())); // chain id
-		domain_seperator_msg.extend_from_slice(
-			frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero()).as_ref(),
-		); // genesis block hash
+		domain_seperator_msg.extend_from_slice(Self::genesis_hash().as_ref()); // genesis block hash
 		keccak_256(domain_seperator_msg.as_slice())
 	}
 
+	fn genesis_hash() -> H256 {
+		H256::from_slice(frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero()).as_ref())
+	}
+
+	/// The mapping of an EVM address, to the default account if it's not linked.
+	pub fn account_mapping_of_evm_address(evm_address: EvmAddress) -> AccountMapping<T::AccountId> {
+		AccountMapping {
+			account_id: Self::get_account_id_or_default(&evm_address),
+			evm_address,
+			is_linked: Accounts::<T>::contains_key(evm_address),
+		}
+	}
+
+	/// The mapping of a Substrate account, to the default EVM address if it's not linked.
+	pub fn account_mapping_of_account_id(account_id: T::AccountId) -> AccountMapping<T::AccountId> {
+		AccountMapping {
+			evm_address: Self::get_evm_address_or_default(&account_id),
+			is_linked: EvmAddresses::<T>::contains_key(&account_id),
+			account_id,
+		}
+	}
+
+	/// The EIP-712 domain and message an EVM address has to sign to be claimed by `who`.
+	pub fn eip712_claim_data(who: &T::AccountId) -> Eip712ClaimData {
+		Eip712ClaimData {
+			name: types::EIP712_DOMAIN_NAME.to_vec(),
+			version: types::EIP712_DOMAIN_VERSION.to_vec(),
+			chain_id: T::ChainId::get(),
+			salt: Self::genesis_hash(),
+			substrate_address: who.encode(),
+		}
+	}
+
 	/*
 	 *     fn do_claim_default_evm_address(who: T::AccountId) -> Result<EvmAddress,
 	 * DispatchError> {         // ensure account_id has not been mapped
@@ -344,11 +892,11 @@ where
 		UnifyAddressMapper::<T>::to_default_evm_address(account_id)
 	}
 
-	// Returns true if a given AccountId is associated with a given EvmAddress
-	// and false if is not.
+	// Returns true if a given AccountId is associated with a given EvmAddress,
+	// either as primary or as secondary address, and false if is not.
 	// Note: we don't check whether the default EvmAddress of the AccountId is linked or not
 	fn is_linked(account_id: &T::AccountId, evm: &EvmAddress) -> bool {
-		UnifyAddressMapper::<T>::to_set_evm_address(account_id).as_ref() == Some(evm)
+		UnifyAddressMapper::<T>::to_set_account_id(evm).as_ref() == Some(account_id)
 	}
 }
 
@@ -360,6 +908,9 @@ impl<T: Config> OnKilledAccount<T::AccountId> for CallKillEVMLinkAccount<T> {
 			Accounts::<T>::remove(evm_addr);
 			EvmAddresses::<T>::remove(who);
 		}
+		for evm_addr in SecondaryEvmAddresses::<T>::take(who) {
+			Accounts::<T>::remove(evm_addr);
+		}
 	}
 }
 
@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{
 	construct_runtime,
-	traits::{ConstU128, ConstU64, Everything},
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU64, Everything},
 };
+use frame_system::{EnsureRoot, EnsureSigned};
 
 use pallet_evm::HashedAddressMapping;
 use peaq_primitives_xcm::Balance;
@@ -33,6 +34,7 @@ use sp_io::hashing::keccak_256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	MultiSignature, MultiSigner,
 };
 
 pub type AccountId = AccountId32;
@@ -40,6 +42,7 @@ pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
+pub const ASSET_ID: u32 = 1;
 
 mod address_unification {
 	pub use super::super::*;
@@ -88,11 +91,38 @@ impl pallet_balances::Config for Runtime {
 	type MaxFreezes = ();
 }
 
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<0>;
+	type AssetIdParameter = u32;
+	type CallbackHandle = ();
+}
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type Assets = Assets;
+	type MaxMergedAssets = ConstU32<2>;
 	type ChainId = ();
+	type MaxSecondaryEvmAddresses = ConstU32<2>;
+	type UnlinkCooldown = ConstU64<10>;
 	type OriginAddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = MultiSigner;
 	type WeightInfo = ();
 }
 
@@ -108,6 +138,7 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		AddressUnificationModule: address_unification::{Pallet, Call, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -122,6 +153,14 @@ impl ExtBuilder {
 			.assimilate_storage(&mut t)
 			.unwrap();
 
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: vec![(ASSET_ID, ALICE, true, 1)],
+			metadata: vec![],
+			accounts: vec![],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
@@ -136,6 +175,14 @@ pub fn bob() -> libsecp256k1::SecretKey {
 	libsecp256k1::SecretKey::parse(&keccak_256(b"Bob")).unwrap()
 }
 
+pub fn charlie() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Charlie")).unwrap()
+}
+
+pub fn dave() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Dave")).unwrap()
+}
+
 pub fn bob_account_id() -> AccountId {
 	let address = AddressUnificationModule::evm_address(&bob());
 	let mut data = [0u8; 32];
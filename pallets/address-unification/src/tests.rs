@@ -21,13 +21,17 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, ReservableCurrency},
+};
 use mock::{
-	alice, bob, AddressUnificationModule, ExtBuilder, Runtime, RuntimeEvent, RuntimeOrigin, System,
-	ALICE, BOB,
+	alice, bob, charlie, dave, AccountId, AddressUnificationModule, Assets, Balances, ExtBuilder,
+	Runtime, RuntimeEvent, RuntimeOrigin, System, ALICE, ASSET_ID, BOB,
 };
 use pallet_evm::HashedAddressMapping;
-use sp_runtime::traits::BlakeTwo256;
+use sp_core::{sr25519, Pair};
+use sp_runtime::{traits::BlakeTwo256, MultiSignature, MultiSigner};
 
 #[test]
 fn claim_account_work() {
@@ -117,3 +121,517 @@ fn evm_get_account_id() {
 		assert!(AddressUnificationModule::is_linked(&ALICE, &evm_account));
 	});
 }
+
+#[test]
+fn unlink_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		assert_noop!(
+			AddressUnificationModule::unlink_account(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_ok!(AddressUnificationModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::UnlinkAccount { account_id: ALICE, evm_address },
+		));
+		assert!(
+			!Accounts::<Runtime>::contains_key(evm_address) &&
+				!EvmAddresses::<Runtime>::contains_key(ALICE)
+		);
+		assert_eq!(AddressUnificationModule::unlinked_at(evm_address), Some(1));
+
+		// the address can't be linked again during the cooldown
+		assert_noop!(
+			AddressUnificationModule::claim_account(
+				RuntimeOrigin::signed(ALICE),
+				evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressInCooldown
+		);
+
+		System::set_block_number(11);
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_eq!(AddressUnificationModule::unlinked_at(evm_address), None);
+	});
+}
+
+#[test]
+fn unlink_cooldown_is_cleared_once_passed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_ok!(AddressUnificationModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		assert!(UnlinkedInBlock::<Runtime>::contains_key(1, evm_address));
+
+		// the cooldown passes at block 11, and is cleared at the next one
+		AddressUnificationModule::on_initialize(11);
+		assert_eq!(AddressUnificationModule::unlinked_at(evm_address), Some(1));
+
+		AddressUnificationModule::on_initialize(12);
+		assert_eq!(AddressUnificationModule::unlinked_at(evm_address), None);
+		assert!(!UnlinkedInBlock::<Runtime>::contains_key(1, evm_address));
+	});
+}
+
+#[test]
+fn unlink_account_with_signature_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		assert_noop!(
+			AddressUnificationModule::unlink_account_with_signature(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				AddressUnificationModule::eth_sign_unlink(&alice(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressNotMapped
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+
+		// the claim signature can't be used to unlink
+		assert_noop!(
+			AddressUnificationModule::unlink_account_with_signature(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &ALICE)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			AddressUnificationModule::unlink_account_with_signature(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				AddressUnificationModule::eth_sign_unlink(&bob(), &ALICE)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		let eth_signature = AddressUnificationModule::eth_sign_unlink(&alice(), &ALICE);
+		assert_ok!(AddressUnificationModule::unlink_account_with_signature(
+			RuntimeOrigin::signed(BOB),
+			evm_address,
+			eth_signature
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::UnlinkAccount { account_id: ALICE, evm_address },
+		));
+		assert!(
+			!Accounts::<Runtime>::contains_key(evm_address) &&
+				!EvmAddresses::<Runtime>::contains_key(ALICE)
+		);
+
+		// the signature can't be replayed once the account was used again
+		System::set_block_number(11);
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		System::inc_account_nonce(ALICE);
+		assert_noop!(
+			AddressUnificationModule::unlink_account_with_signature(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				eth_signature
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn relink_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let old_evm_address = AddressUnificationModule::evm_address(&alice());
+		let new_evm_address = AddressUnificationModule::evm_address(&bob());
+		assert_noop!(
+			AddressUnificationModule::relink_account(
+				RuntimeOrigin::signed(ALICE),
+				new_evm_address,
+				AddressUnificationModule::eth_sign(&bob(), &ALICE)
+			),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			old_evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_noop!(
+			AddressUnificationModule::relink_account(
+				RuntimeOrigin::signed(ALICE),
+				new_evm_address,
+				AddressUnificationModule::eth_sign(&bob(), &BOB)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(AddressUnificationModule::relink_account(
+			RuntimeOrigin::signed(ALICE),
+			new_evm_address,
+			AddressUnificationModule::eth_sign(&bob(), &ALICE)
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::RelinkAccount { account_id: ALICE, old_evm_address, new_evm_address },
+		));
+		assert_eq!(AddressUnificationModule::accounts(new_evm_address), Some(ALICE));
+		assert_eq!(AddressUnificationModule::evm_addresses(ALICE), Some(new_evm_address));
+		assert_eq!(AddressUnificationModule::accounts(old_evm_address), None);
+
+		// the old address can't be moved back during the cooldown
+		assert_noop!(
+			AddressUnificationModule::relink_account(
+				RuntimeOrigin::signed(ALICE),
+				old_evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressInCooldown
+		);
+		assert_noop!(
+			AddressUnificationModule::claim_account(
+				RuntimeOrigin::signed(BOB),
+				old_evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &BOB)
+			),
+			Error::<Runtime>::EthAddressInCooldown
+		);
+	});
+}
+
+#[test]
+fn claim_account_and_merge_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		let padded_account_id = HashedAddressMapping::<BlakeTwo256>::into_account_id(evm_address);
+		let _ = Balances::deposit_creating(&padded_account_id, 1000);
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(ALICE),
+			ASSET_ID,
+			padded_account_id.clone(),
+			500
+		));
+		// the EVM address has sent transactions before
+		for _ in 0..5 {
+			System::inc_account_nonce(&padded_account_id);
+		}
+
+		assert_noop!(
+			AddressUnificationModule::claim_account(
+				RuntimeOrigin::signed(ALICE),
+				evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &ALICE)
+			),
+			Error::<Runtime>::NonEmptyAccounts
+		);
+		assert_noop!(
+			AddressUnificationModule::claim_account_and_merge(
+				RuntimeOrigin::signed(ALICE),
+				evm_address,
+				AddressUnificationModule::eth_sign(&alice(), &BOB),
+				vec![ASSET_ID].try_into().unwrap()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account_and_merge(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE),
+			vec![ASSET_ID].try_into().unwrap()
+		));
+		System::assert_has_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::MergeAccount {
+				account_id: ALICE,
+				merged_account_id: padded_account_id.clone(),
+				balance: 1000,
+			},
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::ClaimAccount { account_id: ALICE, evm_address },
+		));
+		assert_eq!(Balances::free_balance(&ALICE), 1000);
+		assert_eq!(Assets::balance(ASSET_ID, &ALICE), 500);
+		assert_eq!(Assets::balance(ASSET_ID, &padded_account_id), 0);
+		assert!(!System::account_exists(&padded_account_id));
+		assert_eq!(AddressUnificationModule::accounts(evm_address), Some(ALICE));
+		assert_eq!(System::account_nonce(&ALICE), 5);
+	});
+}
+
+#[test]
+fn claim_account_and_merge_rejects_reserves() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&bob());
+		let padded_account_id = HashedAddressMapping::<BlakeTwo256>::into_account_id(evm_address);
+		let _ = Balances::deposit_creating(&padded_account_id, 1000);
+		assert_ok!(Balances::reserve(&padded_account_id, 100));
+
+		// the reserved balance would be stranded on the padded account
+		assert_noop!(
+			AddressUnificationModule::claim_account_and_merge(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				AddressUnificationModule::eth_sign(&bob(), &BOB),
+				Default::default()
+			),
+			Error::<Runtime>::NonTransferableBalance
+		);
+
+		Balances::unreserve(&padded_account_id, 100);
+		assert_ok!(AddressUnificationModule::claim_account_and_merge(
+			RuntimeOrigin::signed(BOB),
+			evm_address,
+			AddressUnificationModule::eth_sign(&bob(), &BOB),
+			Default::default()
+		));
+		assert!(!System::account_exists(&padded_account_id));
+		assert_eq!(AddressUnificationModule::accounts(evm_address), Some(BOB));
+	});
+}
+
+#[test]
+fn claim_account_from_evm_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pair = sr25519::Pair::from_seed(&[1u8; 32]);
+		let account_id = MultiSigner::from(pair.public()).into_account();
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		// the caller's own padded account, which paid for the transaction
+		let padded_account_id = HashedAddressMapping::<BlakeTwo256>::into_account_id(evm_address);
+		let _ = Balances::deposit_creating(&padded_account_id, 1000);
+		System::inc_account_nonce(&padded_account_id);
+
+		let signature =
+			MultiSignature::from(pair.sign(&AddressUnificationModule::substrate_signable_message(
+				&account_id,
+				evm_address,
+			)));
+
+		assert_noop!(
+			AddressUnificationModule::claim_account_from_evm(
+				RuntimeOrigin::signed(BOB),
+				evm_address,
+				account_id.clone(),
+				signature.clone(),
+				Default::default()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AddressUnificationModule::claim_account_from_evm(
+				RuntimeOrigin::signed(padded_account_id.clone()),
+				evm_address,
+				ALICE,
+				signature.clone(),
+				Default::default()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account_from_evm(
+			RuntimeOrigin::signed(padded_account_id.clone()),
+			evm_address,
+			account_id.clone(),
+			signature.clone(),
+			Default::default()
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::ClaimAccount { account_id: account_id.clone(), evm_address },
+		));
+		assert_eq!(Balances::free_balance(&account_id), 1000);
+		assert!(!System::account_exists(&padded_account_id));
+		assert_eq!(System::account_nonce(&account_id), 1);
+		assert_eq!(
+			<AddressUnificationModule as PalletEVMAddressMapping<AccountId>>::into_account_id(
+				evm_address
+			),
+			account_id
+		);
+
+		// the signature can't be replayed once unlinked
+		assert_ok!(AddressUnificationModule::unlink_account(RuntimeOrigin::signed(
+			account_id.clone()
+		)));
+		System::set_block_number(20);
+		let _ = Balances::deposit_creating(&padded_account_id, 1000);
+		assert_noop!(
+			AddressUnificationModule::claim_account_from_evm(
+				RuntimeOrigin::signed(padded_account_id),
+				evm_address,
+				account_id,
+				signature,
+				Default::default()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn account_mapping_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnificationModule::evm_address(&alice());
+		let default_account_id = HashedAddressMapping::<BlakeTwo256>::into_account_id(evm_address);
+		let default_evm_address = AddressUnificationModule::get_detault_evm_address(&ALICE);
+
+		assert_eq!(
+			AddressUnificationModule::account_mapping_of_evm_address(evm_address),
+			AccountMapping { account_id: default_account_id, evm_address, is_linked: false }
+		);
+		assert_eq!(
+			AddressUnificationModule::account_mapping_of_account_id(ALICE),
+			AccountMapping {
+				account_id: ALICE,
+				evm_address: default_evm_address,
+				is_linked: false
+			}
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			evm_address,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_eq!(
+			AddressUnificationModule::account_mapping_of_evm_address(evm_address),
+			AccountMapping { account_id: ALICE, evm_address, is_linked: true }
+		);
+		assert_eq!(
+			AddressUnificationModule::account_mapping_of_account_id(ALICE),
+			AccountMapping { account_id: ALICE, evm_address, is_linked: true }
+		);
+	});
+}
+
+#[test]
+fn eip712_claim_data_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let claim_data = AddressUnificationModule::eip712_claim_data(&ALICE);
+		assert_eq!(claim_data.name, b"Peaq EVM claim".to_vec());
+		assert_eq!(claim_data.version, b"1".to_vec());
+		assert_eq!(claim_data.chain_id, 0);
+		assert_eq!(claim_data.salt, H256::from_slice(System::block_hash(0).as_ref()));
+		assert_eq!(claim_data.substrate_address, ALICE.encode());
+	});
+}
+
+#[test]
+fn secondary_evm_addresses_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let primary = AddressUnificationModule::evm_address(&alice());
+		let secondary = AddressUnificationModule::evm_address(&bob());
+		let other_secondary = AddressUnificationModule::evm_address(&charlie());
+		assert_noop!(
+			AddressUnificationModule::add_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				secondary,
+				AddressUnificationModule::eth_sign(&bob(), &ALICE)
+			),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(AddressUnificationModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			primary,
+			AddressUnificationModule::eth_sign(&alice(), &ALICE)
+		));
+		assert_noop!(
+			AddressUnificationModule::add_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				secondary,
+				AddressUnificationModule::eth_sign(&bob(), &BOB)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			AddressUnificationModule::add_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				primary,
+				AddressUnificationModule::eth_sign(&alice(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+
+		assert_ok!(AddressUnificationModule::add_secondary_evm_address(
+			RuntimeOrigin::signed(ALICE),
+			secondary,
+			AddressUnificationModule::eth_sign(&bob(), &ALICE)
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::SecondaryEvmAddressAdded { account_id: ALICE, evm_address: secondary },
+		));
+		assert_ok!(AddressUnificationModule::add_secondary_evm_address(
+			RuntimeOrigin::signed(ALICE),
+			other_secondary,
+			AddressUnificationModule::eth_sign(&charlie(), &ALICE)
+		));
+		assert_noop!(
+			AddressUnificationModule::add_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				AddressUnificationModule::evm_address(&dave()),
+				AddressUnificationModule::eth_sign(&dave(), &ALICE)
+			),
+			Error::<Runtime>::TooManyEvmAddresses
+		);
+
+		// every address resolves to the account, the primary one stays the reverse mapping
+		assert_eq!(AddressUnificationModule::get_account_id_or_default(&primary), ALICE);
+		assert_eq!(AddressUnificationModule::get_account_id_or_default(&secondary), ALICE);
+		assert_eq!(AddressUnificationModule::get_account_id_or_default(&other_secondary), ALICE);
+		assert_eq!(AddressUnificationModule::get_evm_address_or_default(&ALICE), primary);
+		assert!(AddressUnificationModule::is_linked(&ALICE, &secondary));
+
+		assert_noop!(
+			AddressUnificationModule::remove_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				primary
+			),
+			Error::<Runtime>::NotSecondaryEvmAddress
+		);
+		assert_ok!(AddressUnificationModule::remove_secondary_evm_address(
+			RuntimeOrigin::signed(ALICE),
+			secondary
+		));
+		System::assert_last_event(RuntimeEvent::AddressUnificationModule(
+			crate::Event::SecondaryEvmAddressRemoved { account_id: ALICE, evm_address: secondary },
+		));
+		assert_eq!(AddressUnificationModule::accounts(secondary), None);
+		assert_eq!(
+			AddressUnificationModule::secondary_evm_addresses(ALICE).to_vec(),
+			vec![other_secondary]
+		);
+		assert_noop!(
+			AddressUnificationModule::add_secondary_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				secondary,
+				AddressUnificationModule::eth_sign(&bob(), &ALICE)
+			),
+			Error::<Runtime>::EthAddressInCooldown
+		);
+
+		// unlinking the primary address unlinks the secondary ones as well
+		assert_ok!(AddressUnificationModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(AddressUnificationModule::accounts(other_secondary), None);
+		assert!(AddressUnificationModule::secondary_evm_addresses(ALICE).is_empty());
+		assert_eq!(AddressUnificationModule::unlinked_at(other_secondary), Some(1));
+	});
+}
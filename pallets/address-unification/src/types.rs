@@ -0,0 +1,37 @@
+use frame_support::pallet_prelude::*;
+use peaq_primitives_xcm::evm::EvmAddress;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+/// The name of the EIP-712 domain of the claim and unlink signatures.
+pub const EIP712_DOMAIN_NAME: &[u8] = b"Peaq EVM claim";
+/// The version of the EIP-712 domain of the claim and unlink signatures.
+pub const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+/// The prefix of the message a Substrate account signs to be claimed from the EVM side.
+pub const SUBSTRATE_CLAIM_PREFIX: &[u8] = b"Peaq EVM claim:";
+
+/// Mapping between a Substrate account and an EVM address, either set by a
+/// claim or derived by default.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AccountMapping<AccountId> {
+	pub account_id: AccountId,
+	pub evm_address: EvmAddress,
+	/// Whether the pair was linked by a claim, otherwise one side is the default of the
+	/// other.
+	pub is_linked: bool,
+}
+
+/// Domain and message of the EIP-712 claim signature of a Substrate account.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Eip712ClaimData {
+	pub name: Vec<u8>,
+	pub version: Vec<u8>,
+	pub chain_id: u64,
+	/// The genesis block hash.
+	pub salt: H256,
+	/// The SCALE encoded Substrate account.
+	pub substrate_address: Vec<u8>,
+}
@@ -50,6 +50,13 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn claim_account() -> Weight;
 	fn claim_default_account() -> Weight;
+	fn unlink_account(s: u32, ) -> Weight;
+	fn unlink_account_with_signature(s: u32, ) -> Weight;
+	fn relink_account() -> Weight;
+	fn claim_account_and_merge(n: u32, ) -> Weight;
+	fn add_secondary_evm_address() -> Weight;
+	fn remove_secondary_evm_address() -> Weight;
+	fn claim_account_from_evm(n: u32, ) -> Weight;
 }
 
 /// Weights for module_address_unification using the Acala node and recommended hardware.
@@ -65,6 +72,49 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as u64))
 			.saturating_add(T::DbWeight::get().writes(2 as u64))
 	}
+	fn unlink_account(s: u32, ) -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(s as u64)))
+	}
+	fn unlink_account_with_signature(s: u32, ) -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(s as u64)))
+	}
+	fn relink_account() -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	fn claim_account_and_merge(n: u32, ) -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
+	fn add_secondary_evm_address() -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn remove_secondary_evm_address() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn claim_account_from_evm(n: u32, ) -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -79,4 +129,47 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 	}
+	fn unlink_account(s: u32, ) -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(s as u64)))
+	}
+	fn unlink_account_with_signature(s: u32, ) -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(s as u64)))
+	}
+	fn relink_account() -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn claim_account_and_merge(n: u32, ) -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
+	fn add_secondary_evm_address() -> Weight {
+		Weight::from_parts(340_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn remove_secondary_evm_address() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn claim_account_from_evm(n: u32, ) -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(n as u64)))
+	}
 }
@@ -4,6 +4,7 @@ use super::*;
 
 use frame_benchmarking::v1::{benchmarks, impl_benchmark_test_suite};
 use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::Perbill;
 
 /// Assert that the last event equals the provided one.
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
@@ -13,11 +14,26 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 benchmarks! {
 
 	set_configuration {
-		let reward_config = RewardDistributionConfig::default();
-		assert!(reward_config.is_consistent());
-	}: _(RawOrigin::Root, reward_config.clone())
+		let n = T::MaxBeneficiaries::get().max(1);
+		let share = Perbill::one().deconstruct() / n;
+		let beneficiaries: BeneficiariesOf<T> = (0..n)
+			.map(|i| BeneficiaryConfig {
+				id: [i as u8; 8],
+				// the first beneficiary takes what is left over by the others
+				percent: if i == 0 {
+					Perbill::from_parts(Perbill::one().deconstruct() - share * (n - 1))
+				} else {
+					Perbill::from_parts(share)
+				},
+				destination: BeneficiaryDestination::Pot([i as u8; 8]),
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		assert!(beneficiaries_are_consistent(&beneficiaries));
+	}: _(RawOrigin::Root, beneficiaries.clone())
 	verify {
-		assert_last_event::<T>(Event::<T>::DistributionConfigurationChanged(reward_config).into());
+		assert_last_event::<T>(Event::<T>::DistributionConfigurationChanged(beneficiaries).into());
 	}
 
 }
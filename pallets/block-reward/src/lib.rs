@@ -12,11 +12,15 @@
 //! Major on-chain factors which can influence reward distribution are total issuance and total
 //! value locked by dapps staking.
 //!
+//! The beneficiaries are kept in a bounded registry. Each beneficiary receives its percentage of
+//! the reward either into the account of a pallet or through the `BeneficiaryPayout` handler of
+//! the runtime.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Function
 //!
-//! - `set_configuration` - used to change reward distribution configuration parameters
+//! - `set_configuration` - used to replace the list of beneficiaries and their percentages
 //! - `set_block_issue_reward` - used to change block issue reward configuration parameter
 //! - `set_max_currency_supply` - used to change the maximum currency supply parameter
 //!
@@ -32,7 +36,8 @@
 //! ## Usage
 //!
 //! 1. Pallet should be set as a handler of `OnTimestampSet`.
-//! 2. `BeneficiaryPayout` handler should be defined as an impl of `BeneficiaryPayout` trait.
+//! 2. `BeneficiaryPayout` handler should be defined as an impl of `BeneficiaryPayout` trait, it
+//!    pays out all beneficiaries with the `Handler` destination.
 //! 3. Set `RewardAmount` to desired block reward value in the genesis configuration.
 //! 4. Set `MaxCurrencySupply` to limit maximum currency supply in the genesis configuration.
 
@@ -43,10 +48,13 @@ pub use pallet::*;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Currency, Imbalance, OnTimestampSet, OnUnbalanced},
+	PalletId,
 };
 use frame_system::{ensure_root, pallet_prelude::*};
 use inflation_manager::{Config as InflationManagerConfig, Pallet as InflationManagerPallet};
 use peaq_primitives_xcm::Balance;
+use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_std::vec::Vec;
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
@@ -79,7 +87,7 @@ pub mod pallet {
 
 	use super::*;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -93,6 +101,10 @@ pub mod pallet {
 		/// Used to payout rewards
 		type BeneficiaryPayout: BeneficiaryPayout<NegativeImbalanceOf<Self>>;
 
+		/// Maximum number of beneficiaries of the reward distribution
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -100,16 +112,17 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The beneficiaries of the reward distribution, the first one receives the rounding
+	/// remainder
 	#[pallet::storage]
-	#[pallet::getter(fn reward_config)]
-	pub(super) type RewardDistributionConfigStorage<T: Config> =
-		StorageValue<_, RewardDistributionConfig, ValueQuery>;
+	#[pallet::getter(fn beneficiaries)]
+	pub(super) type Beneficiaries<T: Config> = StorageValue<_, BeneficiariesOf<T>, ValueQuery>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Distribution configuration has been updated.
-		DistributionConfigurationChanged(RewardDistributionConfig),
+		DistributionConfigurationChanged(BeneficiariesOf<T>),
 
 		/// Rewards have been distributed
 		BlockRewardsDistributed(BalanceOf<T>),
@@ -120,28 +133,36 @@ pub mod pallet {
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Sum of all rations must be one whole (100%)
+		/// Sum of all rations must be one whole (100%) and beneficiaries must be unique
 		InvalidDistributionConfiguration,
 	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
-		pub reward_config: RewardDistributionConfig,
+		pub beneficiaries: Vec<BeneficiaryConfig>,
 		pub _phantom: PhantomData<T>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { reward_config: Default::default(), _phantom: PhantomData }
+			Self {
+				beneficiaries: RewardDistributionConfig::default().into_beneficiaries(),
+				_phantom: PhantomData,
+			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			assert!(self.reward_config.is_consistent());
-			RewardDistributionConfigStorage::<T>::put(self.reward_config.clone());
+			assert!(beneficiaries_are_consistent(&self.beneficiaries));
+			let beneficiaries: BeneficiariesOf<T> = self
+				.beneficiaries
+				.clone()
+				.try_into()
+				.expect("Number of beneficiaries exceeds MaxBeneficiaries");
+			Beneficiaries::<T>::put(beneficiaries);
 		}
 	}
 
@@ -150,34 +171,44 @@ pub mod pallet {
 		fn on_runtime_upgrade() -> frame_support::weights::Weight {
 			migrations::on_runtime_upgrade::<T>()
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				beneficiaries_are_consistent(&Beneficiaries::<T>::get()),
+				"Beneficiaries are empty or inconsistent"
+			);
+			Ok(())
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Sets the reward distribution configuration parameters which will be used from next block
-		/// reward distribution.
+		/// Sets the beneficiaries which will be used from next block reward distribution.
 		///
-		/// It is mandatory that all components of configuration sum up to one whole (**100%**),
-		/// otherwise an error `InvalidDistributionConfiguration` will be raised.
+		/// It is mandatory that the percentages of all beneficiaries sum up to one whole
+		/// (**100%**) and that no beneficiary is listed twice, otherwise an error
+		/// `InvalidDistributionConfiguration` will be raised.
 		///
-		/// - `reward_distro_params` - reward distribution params
+		/// - `beneficiaries` - beneficiaries with their percentage and destination, the first one
+		///   receives the rounding remainder
 		///
 		/// Emits `DistributionConfigurationChanged` with config embeded into event itself.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::set_configuration())]
 		pub fn set_configuration(
 			origin: OriginFor<T>,
-			reward_distro_params: RewardDistributionConfig,
+			beneficiaries: BeneficiariesOf<T>,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
 			ensure!(
-				reward_distro_params.is_consistent(),
+				beneficiaries_are_consistent(&beneficiaries),
 				Error::<T>::InvalidDistributionConfiguration
 			);
-			RewardDistributionConfigStorage::<T>::put(reward_distro_params.clone());
+			Beneficiaries::<T>::put(beneficiaries.clone());
 
-			Self::deposit_event(Event::<T>::DistributionConfigurationChanged(reward_distro_params));
+			Self::deposit_event(Event::<T>::DistributionConfigurationChanged(beneficiaries));
 
 			Ok(().into())
 		}
@@ -213,37 +244,42 @@ pub mod pallet {
 		/// # Arguments
 		/// * `imbalance` - imbalance that will be split and distributed
 		fn distribute_imbalances(imbalance: NegativeImbalanceOf<T>, dpt_event: Event<T>) {
-			let distro_params = Self::reward_config();
-
-			// Pre-calculate balance which will be deposited for each beneficiary
-			let collator_delegator_balance =
-				distro_params.collators_delegators_percent * imbalance.peek();
-			let coretime_balance = distro_params.coretime_percent * imbalance.peek();
-			let subsidization_pool_balance =
-				distro_params.subsidization_pool_percent * imbalance.peek();
-			let depin_staking_balance = distro_params.depin_staking_percent * imbalance.peek();
-			let depin_incentivization_balance =
-				distro_params.depin_incentivization_percent * imbalance.peek();
-
-			// Prepare imbalances
-			let (collator_delegator_imbalance, remainder) =
-				imbalance.split(collator_delegator_balance);
-			let (coretime_imbalance, remainder) = remainder.split(coretime_balance);
-			let (subsidization_pool_imbalance, remainder) =
-				remainder.split(subsidization_pool_balance);
-			let (depin_staking_imbalance, remainder) = remainder.split(depin_staking_balance);
-			let (depin_incentivization_imbalance, treasury_imbalance) =
-				remainder.split(depin_incentivization_balance);
-
-			// Payout beneficiaries
-			T::BeneficiaryPayout::treasury(treasury_imbalance);
-			T::BeneficiaryPayout::collators_delegators(collator_delegator_imbalance);
-			T::BeneficiaryPayout::coretime(coretime_imbalance);
-			T::BeneficiaryPayout::subsidization_pool(subsidization_pool_imbalance);
-			T::BeneficiaryPayout::depin_staking(depin_staking_imbalance);
-			T::BeneficiaryPayout::depin_incentivization(depin_incentivization_imbalance);
+			let beneficiaries = Self::beneficiaries();
+			let total = imbalance.peek();
+
+			// Split off the shares of all but the first beneficiary, who gets the remainder
+			let mut remainder = imbalance;
+			if let Some((first, others)) = beneficiaries.split_first() {
+				for beneficiary in others {
+					let (share, rest) = remainder.split(beneficiary.percent * total);
+					remainder = rest;
+					Self::payout(beneficiary, share);
+				}
+				Self::payout(first, remainder);
+			}
 
 			Self::deposit_event(dpt_event);
 		}
+
+		/// Pay the reward to the destination of the beneficiary.
+		///
+		/// A reward below the existential deposit cannot create the account
+		/// of a pot, it is paid to the treasury instead of being burned.
+		fn payout(beneficiary: &BeneficiaryConfig, reward: NegativeImbalanceOf<T>) {
+			match beneficiary.destination {
+				BeneficiaryDestination::Pot(pallet_id) => {
+					let account = PalletId(pallet_id).into_account_truncating();
+					if reward.peek() < T::Currency::minimum_balance() &&
+						T::Currency::total_balance(&account).is_zero()
+					{
+						T::BeneficiaryPayout::payout(&TREASURY, reward);
+					} else {
+						T::Currency::resolve_creating(&account, reward);
+					}
+				},
+				BeneficiaryDestination::Handler =>
+					T::BeneficiaryPayout::payout(&beneficiary.id, reward),
+			}
+		}
 	}
 }
@@ -18,8 +18,13 @@ pub enum ObsoleteStorageReleases {
 	V2_3_0, // change the reward distribution configuration
 }
 
+#[storage_alias]
+type RewardDistributionConfigStorage<T: Config> =
+	StorageValue<Pallet<T>, RewardDistributionConfig, ValueQuery>;
+
 pub(crate) fn on_runtime_upgrade<T: Config>() -> Weight {
 	v2::MigrateToV2x::<T>::on_runtime_upgrade()
+		.saturating_add(v5::MigrateToV5::<T>::on_runtime_upgrade())
 }
 
 mod v2 {
@@ -46,7 +51,8 @@ mod v2 {
 			let mut weight_writes = 0;
 			let mut weight_reads = 2;
 
-			let current = Pallet::<T>::current_storage_version();
+			// the last version before the beneficiary registry
+			let current = StorageVersion::new(4);
 			let onchain_version = Pallet::<T>::on_chain_storage_version();
 
 			if onchain_version < current {
@@ -99,3 +105,51 @@ mod v2 {
 		}
 	}
 }
+
+mod v5 {
+	use super::*;
+
+	/// Migration implementation that moves the fixed reward distribution configuration into the
+	/// beneficiary registry
+	pub struct MigrateToV5<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> MigrateToV5<T> {
+		pub fn on_runtime_upgrade() -> Weight {
+			let mut weight_writes = 0;
+			let mut weight_reads = 1;
+
+			let current = Pallet::<T>::current_storage_version();
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+			if onchain_version < current {
+				log!(info, "Enter and do the migration, {:?} < {:?}", onchain_version, current);
+				if RewardDistributionConfigStorage::<T>::exists() {
+					weight_reads += 1;
+					// keep paying out the same percentages through the runtime's handler
+					let beneficiaries =
+						RewardDistributionConfigStorage::<T>::get().into_beneficiaries();
+					match BeneficiariesOf::<T>::try_from(beneficiaries) {
+						Ok(beneficiaries) if beneficiaries_are_consistent(&beneficiaries) => {
+							Beneficiaries::<T>::put(beneficiaries);
+							RewardDistributionConfigStorage::<T>::kill();
+							weight_writes += 2;
+						},
+						_ => {
+							// keep the old configuration and the storage version, so that the
+							// migration is retried by the next runtime upgrade
+							log!(error, "Reward distribution configuration cannot be migrated");
+							return T::DbWeight::get().reads_writes(weight_reads, weight_writes)
+						},
+					}
+				} else {
+					log!(warn, "No reward distribution configuration to migrate");
+				}
+
+				current.put::<Pallet<T>>();
+				weight_writes += 1;
+				log!(info, "Migrating to {:?} Done.", current);
+			}
+			T::DbWeight::get().reads_writes(weight_reads, weight_writes)
+		}
+	}
+}
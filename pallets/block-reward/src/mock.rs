@@ -1,4 +1,7 @@
-use crate::{self as pallet_block_reward, NegativeImbalanceOf};
+use crate::{
+	self as pallet_block_reward, BeneficiaryId, NegativeImbalanceOf, COLLATORS_DELEGATORS,
+	CORETIME, DEPIN_INCENTIVIZATION, DEPIN_STAKING, SUBSIDIZATION_POOL,
+};
 
 use frame_support::{
 	construct_runtime, parameter_types,
@@ -119,31 +122,16 @@ pub struct BeneficiaryPayout();
 impl pallet_block_reward::BeneficiaryPayout<NegativeImbalanceOf<TestRuntime>>
 	for BeneficiaryPayout
 {
-	fn treasury(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(&TREASURY_POT.into_account_truncating(), reward);
-	}
-
-	fn collators_delegators(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(&COLLATOR_DELEGATOR_POT.into_account_truncating(), reward);
-	}
-
-	fn coretime(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(&CORETIME_POT.into_account_truncating(), reward);
-	}
-
-	fn subsidization_pool(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(&SUBSIDIZATION_POT.into_account_truncating(), reward);
-	}
-
-	fn depin_staking(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(&DE_PINSTAKING_ACCOUNT.into_account_truncating(), reward);
-	}
-
-	fn depin_incentivization(reward: NegativeImbalanceOf<TestRuntime>) {
-		Balances::resolve_creating(
-			&DE_PININCENTIVIZATION_ACCOUNT.into_account_truncating(),
-			reward,
-		);
+	fn payout(beneficiary: &BeneficiaryId, reward: NegativeImbalanceOf<TestRuntime>) {
+		let pot = match *beneficiary {
+			COLLATORS_DELEGATORS => COLLATOR_DELEGATOR_POT,
+			CORETIME => CORETIME_POT,
+			SUBSIDIZATION_POOL => SUBSIDIZATION_POT,
+			DEPIN_STAKING => DE_PINSTAKING_ACCOUNT,
+			DEPIN_INCENTIVIZATION => DE_PININCENTIVIZATION_ACCOUNT,
+			_ => TREASURY_POT,
+		};
+		Balances::resolve_creating(&pot.into_account_truncating(), reward);
 	}
 }
 
@@ -172,12 +160,15 @@ impl inflation_manager::Config for TestRuntime {
 	type WeightInfo = inflation_manager::weights::WeightInfo<TestRuntime>;
 	type DoInitializeAt = InitializeInflationAt;
 	type BlockRewardBeforeInitialize = BlockRewardBeforeInitialize;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type UnixTime = Timestamp;
 }
 
 impl pallet_block_reward::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type BeneficiaryPayout = BeneficiaryPayout;
+	type MaxBeneficiaries = ConstU32<8>;
 	type WeightInfo = pallet_block_reward::weights::WeightInfo<TestRuntime>;
 }
 
@@ -198,7 +189,8 @@ impl ExternalityBuilder {
 			.assimilate_storage(&mut storage)
 			.ok();
 		pallet_block_reward::GenesisConfig::<TestRuntime> {
-			reward_config: pallet_block_reward::RewardDistributionConfig::default(),
+			beneficiaries: pallet_block_reward::RewardDistributionConfig::default()
+				.into_beneficiaries(),
 			_phantom: Default::default(),
 		}
 		.assimilate_storage(&mut storage)
@@ -1,6 +1,7 @@
 use super::{pallet::Error, Event, *};
 use frame_support::{
 	assert_noop, assert_ok,
+	storage::{storage_prefix, unhashed},
 	traits::{Currency, OnTimestampSet},
 };
 use mock::*;
@@ -13,7 +14,16 @@ use sp_runtime::{
 #[test]
 fn default_reward_distribution_config_is_consitent() {
 	let reward_config = RewardDistributionConfig::default();
-	assert!(reward_config.is_consistent());
+	assert!(is_consistent(&reward_config));
+}
+
+#[test]
+fn beneficiaries_not_consistent_with_duplicates() {
+	let mut beneficiaries = RewardDistributionConfig::default().into_beneficiaries();
+	beneficiaries[1].id = TREASURY;
+	assert!(!beneficiaries_are_consistent(&beneficiaries));
+
+	assert!(!beneficiaries_are_consistent(&[]));
 }
 
 #[test]
@@ -27,7 +37,7 @@ fn reward_distribution_config_is_consistent() {
 		depin_staking_percent: Zero::zero(),
 		depin_incentivization_percent: Zero::zero(),
 	};
-	assert!(reward_config.is_consistent());
+	assert!(is_consistent(&reward_config));
 
 	// 2
 	let reward_config = RewardDistributionConfig {
@@ -38,7 +48,7 @@ fn reward_distribution_config_is_consistent() {
 		depin_staking_percent: Perbill::from_percent(50),
 		depin_incentivization_percent: Perbill::from_percent(50),
 	};
-	assert!(reward_config.is_consistent());
+	assert!(is_consistent(&reward_config));
 
 	// 3
 	let reward_config = RewardDistributionConfig {
@@ -49,7 +59,7 @@ fn reward_distribution_config_is_consistent() {
 		depin_staking_percent: Zero::zero(),
 		depin_incentivization_percent: Zero::zero(),
 	};
-	assert!(!reward_config.is_consistent());
+	assert!(!is_consistent(&reward_config));
 
 	// 4
 	// 100%
@@ -61,7 +71,7 @@ fn reward_distribution_config_is_consistent() {
 		depin_staking_percent: Perbill::from_percent(31),
 		depin_incentivization_percent: Perbill::from_percent(31),
 	};
-	assert!(reward_config.is_consistent());
+	assert!(is_consistent(&reward_config));
 }
 
 #[test]
@@ -71,7 +81,7 @@ fn reward_distribution_config_not_consistent() {
 		treasury_percent: Perbill::from_percent(100),
 		..Default::default()
 	};
-	assert!(!reward_config.is_consistent());
+	assert!(!is_consistent(&reward_config));
 
 	// 2
 	// 99%
@@ -83,7 +93,7 @@ fn reward_distribution_config_not_consistent() {
 		depin_staking_percent: Perbill::from_percent(20),
 		depin_incentivization_percent: Perbill::from_percent(20),
 	};
-	assert!(!reward_config.is_consistent());
+	assert!(!is_consistent(&reward_config));
 
 	// 3
 	// 101%
@@ -95,7 +105,7 @@ fn reward_distribution_config_not_consistent() {
 		depin_staking_percent: Perbill::from_percent(20),
 		depin_incentivization_percent: Perbill::from_percent(20),
 	};
-	assert!(!reward_config.is_consistent());
+	assert!(!is_consistent(&reward_config));
 }
 
 #[test]
@@ -112,9 +122,9 @@ pub fn set_configuration_fails() {
 			treasury_percent: Perbill::from_percent(100),
 			..Default::default()
 		};
-		assert!(!reward_config.is_consistent());
+		assert!(!is_consistent(&reward_config));
 		assert_noop!(
-			BlockReward::set_configuration(RuntimeOrigin::root(), reward_config),
+			BlockReward::set_configuration(RuntimeOrigin::root(), beneficiaries(&reward_config)),
 			Error::<TestRuntime>::InvalidDistributionConfiguration,
 		);
 	})
@@ -132,14 +142,17 @@ pub fn set_configuration_is_ok() {
 			depin_staking_percent: Perbill::from_percent(14),
 			depin_incentivization_percent: Perbill::from_percent(14),
 		};
-		assert!(reward_config.is_consistent());
+		assert!(is_consistent(&reward_config));
 
-		assert_ok!(BlockReward::set_configuration(RuntimeOrigin::root(), reward_config.clone()));
+		assert_ok!(BlockReward::set_configuration(
+			RuntimeOrigin::root(),
+			beneficiaries(&reward_config)
+		));
 		System::assert_last_event(mock::RuntimeEvent::BlockReward(
-			Event::DistributionConfigurationChanged(reward_config.clone()),
+			Event::DistributionConfigurationChanged(beneficiaries(&reward_config)),
 		));
 
-		assert_eq!(RewardDistributionConfigStorage::<TestRuntime>::get(), reward_config);
+		assert_eq!(Beneficiaries::<TestRuntime>::get(), beneficiaries(&reward_config));
 	})
 }
 
@@ -179,8 +192,11 @@ pub fn reward_distribution_as_expected() {
 			depin_staking_percent: Perbill::from_percent(20),
 			depin_incentivization_percent: Perbill::from_percent(20),
 		};
-		assert!(reward_config.is_consistent());
-		assert_ok!(BlockReward::set_configuration(RuntimeOrigin::root(), reward_config.clone()));
+		assert!(is_consistent(&reward_config));
+		assert_ok!(BlockReward::set_configuration(
+			RuntimeOrigin::root(),
+			beneficiaries(&reward_config)
+		));
 
 		// Issue rewards a couple of times and verify distribution is as expected
 		for _block in 1..=100 {
@@ -206,8 +222,11 @@ pub fn reward_distribution_no_adjustable_part() {
 			depin_staking_percent: Perbill::from_percent(50),
 			depin_incentivization_percent: Perbill::from_percent(25),
 		};
-		assert!(reward_config.is_consistent());
-		assert_ok!(BlockReward::set_configuration(RuntimeOrigin::root(), reward_config.clone()));
+		assert!(is_consistent(&reward_config));
+		assert_ok!(BlockReward::set_configuration(
+			RuntimeOrigin::root(),
+			beneficiaries(&reward_config)
+		));
 
 		// no adjustable part so we don't expect rewards to change with TVL percentage
 		let const_rewards = Rewards::calculate(&reward_config);
@@ -248,6 +267,180 @@ pub fn on_unbalanceds() {
 	})
 }
 
+#[test]
+pub fn reward_distribution_to_pots() {
+	ExternalityBuilder::build().execute_with(|| {
+		const POT_A: PalletId = PalletId(*b"pot/aaaa");
+		const POT_B: PalletId = PalletId(*b"pot/bbbb");
+		let reward_config: BeneficiariesOf<TestRuntime> = vec![
+			BeneficiaryConfig {
+				id: *b"benefita",
+				percent: Perbill::from_percent(30),
+				destination: BeneficiaryDestination::Pot(POT_A.0),
+			},
+			BeneficiaryConfig {
+				id: *b"benefitb",
+				percent: Perbill::from_percent(45),
+				destination: BeneficiaryDestination::Pot(POT_B.0),
+			},
+			BeneficiaryConfig {
+				id: DEPIN_STAKING,
+				percent: Perbill::from_percent(25),
+				destination: BeneficiaryDestination::Handler,
+			},
+		]
+		.try_into()
+		.unwrap();
+		assert_ok!(BlockReward::set_configuration(RuntimeOrigin::root(), reward_config));
+
+		let amount = 1_000_000_001 as Balance;
+		BlockReward::on_unbalanced(<TestRuntime as Config>::Currency::issue(amount));
+
+		let free_balance = |pot: PalletId| {
+			<TestRuntime as Config>::Currency::free_balance(&pot.into_account_truncating())
+		};
+		// the first beneficiary receives the rounding remainder
+		assert_eq!(free_balance(POT_A), 300_000_001);
+		assert_eq!(free_balance(POT_B), 450_000_000);
+		assert_eq!(free_balance(DE_PINSTAKING_ACCOUNT), 250_000_000);
+		assert!(free_balance(TREASURY_POT).is_zero());
+	})
+}
+
+#[test]
+pub fn reward_below_existential_deposit_goes_to_treasury() {
+	ExternalityBuilder::build().execute_with(|| {
+		const POT_A: PalletId = PalletId(*b"pot/aaaa");
+		const POT_B: PalletId = PalletId(*b"pot/bbbb");
+		let reward_config: BeneficiariesOf<TestRuntime> = vec![
+			BeneficiaryConfig {
+				id: *b"benefita",
+				percent: Perbill::from_percent(99),
+				destination: BeneficiaryDestination::Pot(POT_A.0),
+			},
+			BeneficiaryConfig {
+				id: *b"benefitb",
+				percent: Perbill::from_percent(1),
+				destination: BeneficiaryDestination::Pot(POT_B.0),
+			},
+		]
+		.try_into()
+		.unwrap();
+		assert_ok!(BlockReward::set_configuration(RuntimeOrigin::root(), reward_config));
+
+		let free_balance = |pot: PalletId| {
+			<TestRuntime as Config>::Currency::free_balance(&pot.into_account_truncating())
+		};
+		<TestRuntime as Config>::Currency::make_free_balance_be(
+			&TREASURY_POT.into_account_truncating(),
+			EXISTENTIAL_DEPOSIT,
+		);
+
+		// the share of the second pot cannot create its account
+		BlockReward::on_unbalanced(<TestRuntime as Config>::Currency::issue(100));
+		assert_eq!(free_balance(POT_A), 99);
+		assert!(free_balance(POT_B).is_zero());
+		assert_eq!(free_balance(TREASURY_POT), EXISTENTIAL_DEPOSIT + 1);
+
+		// once the pot exists, it receives its share
+		<TestRuntime as Config>::Currency::make_free_balance_be(
+			&POT_B.into_account_truncating(),
+			EXISTENTIAL_DEPOSIT,
+		);
+		BlockReward::on_unbalanced(<TestRuntime as Config>::Currency::issue(100));
+		assert_eq!(free_balance(POT_A), 198);
+		assert_eq!(free_balance(POT_B), EXISTENTIAL_DEPOSIT + 1);
+		assert_eq!(free_balance(TREASURY_POT), EXISTENTIAL_DEPOSIT + 1);
+	})
+}
+
+#[test]
+pub fn set_configuration_fails_with_duplicates() {
+	ExternalityBuilder::build().execute_with(|| {
+		let mut reward_config = beneficiaries(&RewardDistributionConfig::default());
+		reward_config[2].id = CORETIME;
+		reward_config[3].id = CORETIME;
+		assert_noop!(
+			BlockReward::set_configuration(RuntimeOrigin::root(), reward_config),
+			Error::<TestRuntime>::InvalidDistributionConfiguration,
+		);
+	})
+}
+
+#[test]
+pub fn migrate_reward_distribution_config_to_beneficiaries() {
+	ExternalityBuilder::build().execute_with(|| {
+		let reward_config = RewardDistributionConfig {
+			treasury_percent: Perbill::from_percent(10),
+			collators_delegators_percent: Perbill::from_percent(40),
+			coretime_percent: Perbill::from_percent(2),
+			subsidization_pool_percent: Perbill::from_percent(8),
+			depin_staking_percent: Perbill::from_percent(20),
+			depin_incentivization_percent: Perbill::from_percent(20),
+		};
+		let old_key = storage_prefix(b"BlockReward", b"RewardDistributionConfigStorage");
+		unhashed::put(&old_key, &reward_config);
+		Beneficiaries::<TestRuntime>::kill();
+		StorageVersion::new(4).put::<BlockReward>();
+
+		migrations::on_runtime_upgrade::<TestRuntime>();
+
+		assert_eq!(BlockReward::on_chain_storage_version(), StorageVersion::new(5));
+		assert_eq!(Beneficiaries::<TestRuntime>::get(), beneficiaries(&reward_config));
+		assert!(!unhashed::exists(&old_key));
+
+		// payouts are the same as before the migration
+		let init_balance_state = FreeBalanceSnapshot::new();
+		let rewards = Rewards::calculate(&reward_config);
+		BlockReward::on_timestamp_set(0);
+		init_balance_state.assert_distribution(&FreeBalanceSnapshot::new(), &rewards);
+	})
+}
+
+#[test]
+pub fn migrate_without_reward_distribution_config() {
+	ExternalityBuilder::build().execute_with(|| {
+		let beneficiaries = Beneficiaries::<TestRuntime>::get();
+		StorageVersion::new(4).put::<BlockReward>();
+
+		migrations::on_runtime_upgrade::<TestRuntime>();
+
+		assert_eq!(BlockReward::on_chain_storage_version(), StorageVersion::new(5));
+		assert_eq!(Beneficiaries::<TestRuntime>::get(), beneficiaries);
+		assert!(beneficiaries_are_consistent(&beneficiaries));
+	})
+}
+
+#[test]
+pub fn migrate_keeps_inconsistent_reward_distribution_config() {
+	ExternalityBuilder::build().execute_with(|| {
+		let reward_config = RewardDistributionConfig {
+			treasury_percent: Perbill::from_percent(50),
+			..Default::default()
+		};
+		assert!(!is_consistent(&reward_config));
+		let old_key = storage_prefix(b"BlockReward", b"RewardDistributionConfigStorage");
+		unhashed::put(&old_key, &reward_config);
+		let beneficiaries = Beneficiaries::<TestRuntime>::get();
+		StorageVersion::new(4).put::<BlockReward>();
+
+		migrations::on_runtime_upgrade::<TestRuntime>();
+
+		// nothing is lost and the migration is retried with the next upgrade
+		assert_eq!(BlockReward::on_chain_storage_version(), StorageVersion::new(4));
+		assert_eq!(Beneficiaries::<TestRuntime>::get(), beneficiaries);
+		assert_eq!(unhashed::get::<RewardDistributionConfig>(&old_key), Some(reward_config));
+	})
+}
+
+fn beneficiaries(reward_config: &RewardDistributionConfig) -> BeneficiariesOf<TestRuntime> {
+	reward_config.clone().into_beneficiaries().try_into().unwrap()
+}
+
+fn is_consistent(reward_config: &RewardDistributionConfig) -> bool {
+	beneficiaries_are_consistent(&reward_config.clone().into_beneficiaries())
+}
+
 /// Represents free balance snapshot at a specific point in time
 #[derive(PartialEq, Eq, Clone, RuntimeDebug)]
 struct FreeBalanceSnapshot {
@@ -2,7 +2,7 @@
 
 use frame_support::{pallet_prelude::*, traits::Currency};
 use sp_runtime::{traits::CheckedAdd, Perbill};
-use sp_std::vec;
+use sp_std::{vec, vec::Vec};
 
 use crate::pallet::Config as PalletConfig;
 
@@ -15,25 +15,64 @@ pub(crate) type NegativeImbalanceOf<T> = <<T as PalletConfig>::Currency as Curre
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 
-/// Defines functions used to payout the beneficiaries of block rewards
-pub trait BeneficiaryPayout<Imbalance> {
-	/// Payout reward to the treasury
-	fn treasury(reward: Imbalance);
+/// The bounded list of beneficiaries of this pallet.
+pub type BeneficiariesOf<T> = BoundedVec<BeneficiaryConfig, <T as PalletConfig>::MaxBeneficiaries>;
 
-	/// Payout reward to the collators
-	fn collators_delegators(reward: Imbalance);
+/// Identifier of a beneficiary of block rewards
+pub type BeneficiaryId = [u8; 8];
 
-	/// Payout LP users
-	fn coretime(reward: Imbalance);
+/// Identifiers of the beneficiaries which were fixed before the beneficiary registry
+pub const TREASURY: BeneficiaryId = *b"treasury";
+pub const COLLATORS_DELEGATORS: BeneficiaryId = *b"coldeleg";
+pub const CORETIME: BeneficiaryId = *b"coretime";
+pub const SUBSIDIZATION_POOL: BeneficiaryId = *b"subsidiz";
+pub const DEPIN_STAKING: BeneficiaryId = *b"depinstk";
+pub const DEPIN_INCENTIVIZATION: BeneficiaryId = *b"depinicv";
 
-	/// Payout Machines
-	fn subsidization_pool(reward: Imbalance);
+/// Defines the custom handler used to payout beneficiaries of block rewards
+pub trait BeneficiaryPayout<Imbalance> {
+	/// Payout reward to the beneficiary with the given identifier
+	fn payout(beneficiary: &BeneficiaryId, reward: Imbalance);
+}
+
+/// Where the share of a beneficiary is paid to
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum BeneficiaryDestination {
+	/// Deposit into the account of the pallet with the given `PalletId`
+	Pot([u8; 8]),
+	/// Hand over to the `BeneficiaryPayout` handler of the runtime
+	Handler,
+}
+
+/// A beneficiary of block rewards and transaction fees.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeneficiaryConfig {
+	/// Identifier of the beneficiary
+	pub id: BeneficiaryId,
+	/// Percentage of reward that goes to the beneficiary
+	#[codec(compact)]
+	pub percent: Perbill,
+	/// Where the reward is paid to
+	pub destination: BeneficiaryDestination,
+}
 
-	/// Payout DePIN staking rewards
-	fn depin_staking(reward: Imbalance);
+/// `true` if all beneficiaries are unique and their percentages sum up to `one whole`, `false`
+/// otherwise.
+pub fn beneficiaries_are_consistent(beneficiaries: &[BeneficiaryConfig]) -> bool {
+	let mut accumulator = Perbill::zero();
+	for (index, beneficiary) in beneficiaries.iter().enumerate() {
+		if beneficiaries[..index].iter().any(|other| other.id == beneficiary.id) {
+			return false
+		}
+		match accumulator.checked_add(&beneficiary.percent) {
+			Some(sum) => accumulator = sum,
+			None => return false,
+		}
+	}
 
-	/// Payout DePIN incentivization
-	fn depin_incentivization(reward: Imbalance);
+	Perbill::one() == accumulator
 }
 
 /// After next next version, we can remove this RewardDistributionConfigV0
@@ -77,8 +116,9 @@ impl Default for RewardDistributionConfigV0 {
 	}
 }
 
+/// After next next version, we can remove this RewardDistributionConfig
 /// List of configuration parameters used to calculate reward distribution portions for all the
-/// beneficiaries.
+/// beneficiaries, replaced by the beneficiary registry.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct RewardDistributionConfig {
@@ -118,31 +158,23 @@ impl Default for RewardDistributionConfig {
 }
 
 impl RewardDistributionConfig {
-	/// `true` if sum of all percentages is `one whole`, `false` otherwise.
-	pub fn is_consistent(&self) -> bool {
-		// TODO: perhaps this can be writen in a more cleaner way?
-		// experimental-only `try_reduce` could be used but it's not available
-		// https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.try_reduce
-
-		let variables = vec![
-			&self.treasury_percent,
-			&self.collators_delegators_percent,
-			&self.coretime_percent,
-			&self.subsidization_pool_percent,
-			&self.depin_staking_percent,
-			&self.depin_incentivization_percent,
-		];
-
-		let mut accumulator = Perbill::zero();
-		for config_param in variables {
-			let result = accumulator.checked_add(config_param);
-			if let Some(mid_result) = result {
-				accumulator = mid_result;
-			} else {
-				return false
-			}
-		}
-
-		Perbill::one() == accumulator
+	/// The beneficiaries of this configuration, all paid by the `BeneficiaryPayout` handler.
+	/// The treasury comes first, so it keeps receiving the rounding remainder.
+	pub fn into_beneficiaries(self) -> Vec<BeneficiaryConfig> {
+		vec![
+			(TREASURY, self.treasury_percent),
+			(COLLATORS_DELEGATORS, self.collators_delegators_percent),
+			(CORETIME, self.coretime_percent),
+			(SUBSIDIZATION_POOL, self.subsidization_pool_percent),
+			(DEPIN_STAKING, self.depin_staking_percent),
+			(DEPIN_INCENTIVIZATION, self.depin_incentivization_percent),
+		]
+		.into_iter()
+		.map(|(id, percent)| BeneficiaryConfig {
+			id,
+			percent,
+			destination: BeneficiaryDestination::Handler,
+		})
+		.collect()
 	}
 }
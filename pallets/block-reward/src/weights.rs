@@ -31,14 +31,14 @@ use core::marker::PhantomData;
 /// Weight functions for `pallet_block_reward`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
-	/// Storage: BlockReward RewardDistributionConfigStorage (r:0 w:1)
-	/// Proof: BlockReward RewardDistributionConfigStorage (max_values: Some(1), max_size: Some(24), added: 519, mode: MaxEncodedLen)
+	/// Storage: BlockReward Beneficiaries (r:0 w:1)
+	/// Proof: BlockReward Beneficiaries (max_values: Some(1), max_size: Some(355), added: 850, mode: MaxEncodedLen)
 	fn set_configuration() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
-		// Minimum execution time: 14_455_000 picoseconds.
-		Weight::from_parts(14_680_000, 0)
+		// Minimum execution time: 16_012_000 picoseconds.
+		Weight::from_parts(16_473_000, 0)
 			.saturating_add(Weight::from_parts(0, 0))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
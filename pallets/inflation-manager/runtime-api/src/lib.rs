@@ -0,0 +1,29 @@
+//! Runtime API definition for the inflation-manager pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+pub use inflation_manager::types::{InflationParameters, IssuanceProjection};
+
+sp_api::decl_runtime_apis! {
+	pub trait InflationManagerApi<BlockNumber, Balance>
+	where
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// The inflation parameters of the current year.
+		fn get_inflation_parameters() -> InflationParameters;
+		/// The current year of inflation, 0 before the TGE.
+		fn get_current_year() -> u128;
+		/// The rewards issued in every block of the current year.
+		fn get_block_rewards() -> Balance;
+		/// The block at which the next year starts and the inflation
+		/// parameters are recalculated.
+		fn get_next_recalculation_at() -> BlockNumber;
+		/// The projected inflation of the next `years` years, at most
+		/// `MAX_PROJECTION_YEARS`.
+		fn get_issuance_projection(years: u32) -> Vec<IssuanceProjection<Balance>>;
+	}
+}
@@ -18,6 +18,26 @@ benchmarks! {
 		assert_eq!(CurrencyOf::<T>::free_balance(&dest), 1000);
 	}
 
+	set_inflation_configuration {
+		CurrentYear::<T>::put(1);
+		let inflation_configuration = InflationConfigurationT::default();
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.expect("UpdateOrigin has a successful origin q.e.d.");
+	}: _<T::RuntimeOrigin>(origin, inflation_configuration.clone(), ApplyConfiguration::Immediately)
+	verify {
+		assert_eq!(InflationConfiguration::<T>::get(), inflation_configuration);
+	}
+
+	set_recalculation_at {
+		CurrentYear::<T>::put(1);
+		let do_recalculation_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.expect("UpdateOrigin has a successful origin q.e.d.");
+	}: _<T::RuntimeOrigin>(origin, do_recalculation_at)
+	verify {
+		assert_eq!(DoRecalculationAt::<T>::get(), do_recalculation_at);
+	}
+
 }
 
 #[cfg(test)]
@@ -9,8 +9,8 @@ use frame_support::traits::ExistenceRequirement::AllowDeath;
 use frame_system::{ensure_root, pallet_prelude::OriginFor};
 use sp_runtime::traits::AccountIdConversion;
 pub use types::{
-	BalanceOf, InflationConfiguration as InflationConfigurationT,
-	InflationParameters as InflationParametersT,
+	ApplyConfiguration, BalanceOf, InflationConfiguration as InflationConfigurationT,
+	InflationParameters as InflationParametersT, IssuanceProjection, YearIssuance,
 };
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -28,13 +28,21 @@ mod tests;
 
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, IsType},
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Currency, IsType, UnixTime},
 };
 use peaq_primitives_xcm::Balance;
-use sp_runtime::{traits::BlockNumberProvider, Perbill};
-use sp_std::cmp::Ordering;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{BlockNumberProvider, SaturatedConversion},
+	DispatchError, Perbill, Rounding,
+};
+use sp_std::{cmp::Ordering, vec::Vec};
 
 pub const BLOCKS_PER_YEAR: peaq_primitives_xcm::BlockNumber = 365 * 24 * 60 * 60 / 12_u32;
+pub const MILLISECS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
+/// Upper bound of years for the issuance projection
+pub const MAX_PROJECTION_YEARS: u32 = 100;
 const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 #[frame_support::pallet]
@@ -75,6 +83,13 @@ pub mod pallet {
 
 		/// BlockRewards to distribute till delayed TGE kicks in
 		type BlockRewardBeforeInitialize: Get<Balance>;
+
+		/// The origin which can change the inflation configuration and the block of the next
+		/// recalculation
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Time source to measure the actual block time, usually pallet_timestamp
+		type UnixTime: UnixTime;
 	}
 
 	/// Inflation kicks off with these parameters
@@ -106,6 +121,35 @@ pub mod pallet {
 	#[pallet::getter(fn block_rewards)]
 	pub type BlockRewards<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	#[pallet::type_value]
+	pub fn DefaultBlocksPerYear<T: Config>() -> T::BlockNumber {
+		T::BlockNumber::from(BLOCKS_PER_YEAR)
+	}
+
+	/// Number of blocks expected in a year, corrected each year by the measured block time
+	#[pallet::storage]
+	#[pallet::getter(fn blocks_per_year)]
+	pub type BlocksPerYear<T: Config> =
+		StorageValue<_, T::BlockNumber, ValueQuery, DefaultBlocksPerYear<T>>;
+
+	/// Block and unix timestamp in milliseconds at which the current year started
+	#[pallet::storage]
+	#[pallet::getter(fn year_started_at)]
+	pub type YearStartedAt<T: Config> = StorageValue<_, (T::BlockNumber, u64), OptionQuery>;
+
+	/// Issuance of the current year and its deviation from the targeted issuance, which is
+	/// corrected in the next year
+	#[pallet::storage]
+	#[pallet::getter(fn current_year_issuance)]
+	pub type CurrentYearIssuance<T: Config> =
+		StorageValue<_, YearIssuance<Balance, T::BlockNumber>, OptionQuery>;
+
+	/// Inflation configuration which replaces the current one at the next recalculation
+	#[pallet::storage]
+	#[pallet::getter(fn pending_inflation_configuration)]
+	pub type PendingInflationConfiguration<T: Config> =
+		StorageValue<_, InflationConfigurationT, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -122,12 +166,37 @@ pub mod pallet {
 		BlockRewardsUpdated {
 			block_rewards: Balance,
 		},
+		// Inflation configuration which will be applied at the next recalculation
+		InflationConfigurationScheduled {
+			inflation_configuration: InflationConfigurationT,
+			do_recalculation_at: T::BlockNumber,
+		},
+		// Block at which the inflation parameters are recalculated next
+		RecalculationScheduled {
+			do_recalculation_at: T::BlockNumber,
+		},
+		// Issuance targeted and rewards issued in the past year, the deviation is corrected in
+		// the new year
+		IssuanceCorrected {
+			target: Balance,
+			issued: Balance,
+		},
+		// Number of blocks per year corrected by the average block time (ms) of the past year
+		InflationCorrected {
+			previous_blocks_per_year: T::BlockNumber,
+			blocks_per_year: T::BlockNumber,
+			average_block_time: u64,
+		},
 	}
 
 	/// Error for evm accounts module.
 	#[pallet::error]
 	pub enum Error<T> {
 		FiscalYearUninitialized,
+		/// The inflation stagnation year has to be at least the first year
+		InvalidInflationConfiguration,
+		/// The recalculation has to happen after the current block
+		RecalculationInThePast,
 	}
 
 	#[pallet::genesis_config]
@@ -172,10 +241,11 @@ pub mod pallet {
 			// if we're at the end of a year or initializing inflation
 			let target_block = DoRecalculationAt::<T>::get();
 			if now == target_block {
+				let now_ms: u64 = T::UnixTime::now().as_millis().saturated_into();
 				let current_year = CurrentYear::<T>::get();
 				let new_year = current_year + 1;
 
-				let inflation_config = InflationConfiguration::<T>::get();
+				let mut inflation_config = InflationConfiguration::<T>::get();
 				let mut inflation_parameters = InflationParameters::<T>::get();
 
 				// update current year
@@ -186,28 +256,45 @@ pub mod pallet {
 					Self::fund_difference_balances();
 				}
 
-				match new_year.cmp(&inflation_config.inflation_stagnation_year) {
-					Ordering::Less => {
-						inflation_parameters = Self::update_inflation_parameters(&inflation_config);
-						InflationParameters::<T>::put(inflation_parameters.clone());
-					},
-					Ordering::Equal => {
-						inflation_parameters = InflationParametersT {
-							inflation_rate: inflation_config.inflation_stagnation_rate,
-							disinflation_rate: Perbill::one(),
-						};
-						InflationParameters::<T>::put(inflation_parameters.clone());
-					},
-					Ordering::Greater => {},
+				// a configuration scheduled by governance applies from the new year on
+				let pending_config = PendingInflationConfiguration::<T>::take();
+				let config_changed = pending_config.is_some();
+				if let Some(pending_config) = pending_config {
+					InflationConfiguration::<T>::put(pending_config.clone());
+					Self::deposit_event(Event::InflationConfigurationSet {
+						inflation_configuration: pending_config.clone(),
+					});
+					inflation_config = pending_config;
 				}
 
+				// after the stagnation year the parameters only change with the configuration
+				if new_year <= inflation_config.inflation_stagnation_year || config_changed {
+					inflation_parameters =
+						Self::inflation_parameters_of_current_year(&inflation_config);
+					InflationParameters::<T>::put(inflation_parameters.clone());
+				}
+
+				// the deviation of the past year from its targeted issuance is carried over
+				let (deficit, surplus) = Self::close_year_issuance(now, now_ms);
+
+				// adjust the length of the new year to the block time of the past one
+				Self::correct_blocks_per_year(now, now_ms);
+
 				// set the flag to calculate inflation parameters after a year(in blocks)
-				let target_block = now + T::BlockNumber::from(BLOCKS_PER_YEAR);
+				let target_block = now + BlocksPerYear::<T>::get();
 				DoRecalculationAt::<T>::put(target_block);
 
 				// calculate block rewards for new year
-				let block_rewards = Self::rewards_per_block(&inflation_parameters);
+				let yearly_target = Self::yearly_issuance(&inflation_parameters);
+				let block_rewards =
+					Self::corrected_rewards_per_block(yearly_target, deficit, surplus);
 				BlockRewards::<T>::put(block_rewards);
+				CurrentYearIssuance::<T>::put(YearIssuance {
+					yearly_target,
+					target: deficit,
+					issued: surplus,
+					accrued_at: (now, now_ms),
+				});
 
 				// log this change
 				Self::deposit_event(Event::BlockRewardsUpdated { block_rewards });
@@ -235,17 +322,210 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Replace the inflation configuration.
+		///
+		/// With `ApplyConfiguration::Immediately` the inflation parameters and block rewards of
+		/// the current year are recomputed right away, otherwise the configuration is applied at
+		/// the next recalculation.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_inflation_configuration())]
+		pub fn set_inflation_configuration(
+			origin: OriginFor<T>,
+			inflation_configuration: InflationConfigurationT,
+			apply: ApplyConfiguration,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(inflation_configuration.is_valid(), Error::<T>::InvalidInflationConfiguration);
+
+			match apply {
+				ApplyConfiguration::Immediately => {
+					// parameters can only be computed once the first year has started
+					ensure!(CurrentYear::<T>::get() > 0, Error::<T>::FiscalYearUninitialized);
+
+					PendingInflationConfiguration::<T>::kill();
+					InflationConfiguration::<T>::put(inflation_configuration.clone());
+					Self::deposit_event(Event::InflationConfigurationSet {
+						inflation_configuration: inflation_configuration.clone(),
+					});
+
+					let inflation_parameters =
+						Self::inflation_parameters_of_current_year(&inflation_configuration);
+					InflationParameters::<T>::put(inflation_parameters.clone());
+
+					// the rewards issued so far count towards the targets of the previous
+					// parameters, a deviation is corrected in the next year
+					let now = frame_system::Pallet::<T>::block_number();
+					let now_ms: u64 = T::UnixTime::now().as_millis().saturated_into();
+					let yearly_target = Self::yearly_issuance(&inflation_parameters);
+					CurrentYearIssuance::<T>::mutate(|issuance| {
+						if let Some(issuance) = issuance {
+							Self::accrue_issuance(issuance, BlockRewards::<T>::get(), now, now_ms);
+							issuance.yearly_target = yearly_target;
+						}
+					});
+
+					let block_rewards = Self::rewards_per_block(&inflation_parameters);
+					BlockRewards::<T>::put(block_rewards);
+
+					Self::deposit_event(Event::InflationParametersUpdated {
+						inflation_parameters,
+						block_rewards,
+						current_year: CurrentYear::<T>::get(),
+					});
+					Self::deposit_event(Event::BlockRewardsUpdated { block_rewards });
+				},
+				ApplyConfiguration::FromNextYear => {
+					PendingInflationConfiguration::<T>::put(inflation_configuration.clone());
+					Self::deposit_event(Event::InflationConfigurationScheduled {
+						inflation_configuration,
+						do_recalculation_at: DoRecalculationAt::<T>::get(),
+					});
+				},
+			}
+
+			Ok(().into())
+		}
+
+		/// Set the block at which the next year starts and the inflation parameters are
+		/// recalculated. The following years last `BlocksPerYear` blocks each again.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_recalculation_at())]
+		pub fn set_recalculation_at(
+			origin: OriginFor<T>,
+			do_recalculation_at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::schedule_recalculation(do_recalculation_at)?;
+
+			Ok(().into())
+		}
+
+		/// Postpone the next recalculation of the inflation parameters by `delay` blocks.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_recalculation_at())]
+		pub fn delay_recalculation(
+			origin: OriginFor<T>,
+			delay: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let do_recalculation_at = DoRecalculationAt::<T>::get().saturating_add(delay);
+			Self::schedule_recalculation(do_recalculation_at)?;
+
+			Ok(().into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
 		/// calculate inflationary tokens per block
-		/// Weight Reads: 1
+		/// Weight Reads: 2
 		pub fn rewards_per_block(inflation_parameters: &InflationParametersT) -> Balance {
-			let total_issuance = T::Currency::total_issuance();
-			let rewards_total = inflation_parameters.inflation_rate * total_issuance;
+			let blocks_per_year: Balance = BlocksPerYear::<T>::get().saturated_into();
 
 			// return rewards per block
-			rewards_total / Balance::from(BLOCKS_PER_YEAR)
+			Self::yearly_issuance(inflation_parameters) / blocks_per_year.max(1)
+		}
+
+		/// Issuance targeted for a year of time by the inflation parameters
+		/// Weight Reads: 1
+		pub fn yearly_issuance(inflation_parameters: &InflationParametersT) -> Balance {
+			inflation_parameters.inflation_rate * T::Currency::total_issuance()
+		}
+
+		/// Tokens per block to issue the targeted issuance of a year plus the deficit, or minus
+		/// the surplus, of the past years. At most twice the targeted issuance is issued in a
+		/// year, a larger deficit is carried over to the following years.
+		/// Weight Reads: 1
+		fn corrected_rewards_per_block(
+			yearly_target: Balance,
+			deficit: Balance,
+			surplus: Balance,
+		) -> Balance {
+			let blocks_per_year: Balance = BlocksPerYear::<T>::get().saturated_into();
+			let rewards_total = yearly_target
+				.saturating_add(deficit)
+				.saturating_sub(surplus)
+				.min(yearly_target.saturating_mul(2));
+
+			rewards_total / blocks_per_year.max(1)
+		}
+
+		/// Add the rewards issued and the issuance targeted since the issuance was last accrued
+		/// up to `now`. Without a timestamp at the last accrual, the target is accrued by the
+		/// number of blocks instead of the elapsed time.
+		/// Weight Reads: 1
+		fn accrue_issuance(
+			issuance: &mut YearIssuance<Balance, T::BlockNumber>,
+			block_rewards: Balance,
+			now: T::BlockNumber,
+			now_ms: u64,
+		) {
+			let (accrued_block, accrued_ms) = issuance.accrued_at;
+			let blocks: Balance = now.saturating_sub(accrued_block).saturated_into();
+			let (elapsed, year): (Balance, Balance) = if accrued_ms == 0 {
+				(blocks, BlocksPerYear::<T>::get().saturated_into())
+			} else {
+				(now_ms.saturating_sub(accrued_ms).into(), MILLISECS_PER_YEAR.into())
+			};
+			let target = multiply_by_rational_with_rounding(
+				issuance.yearly_target,
+				elapsed,
+				year.max(1),
+				Rounding::Down,
+			)
+			.unwrap_or(Balance::MAX);
+
+			issuance.target = issuance.target.saturating_add(target);
+			issuance.issued = issuance.issued.saturating_add(block_rewards.saturating_mul(blocks));
+			issuance.accrued_at = (now, now_ms);
+		}
+
+		/// Accrue the issuance of the ending year and return its deficit and surplus towards
+		/// the targeted issuance.
+		/// Weight Reads: 3, Writes: 1
+		fn close_year_issuance(now: T::BlockNumber, now_ms: u64) -> (Balance, Balance) {
+			let Some(mut issuance) = CurrentYearIssuance::<T>::take() else { return (0, 0) };
+			Self::accrue_issuance(&mut issuance, BlockRewards::<T>::get(), now, now_ms);
+
+			Self::deposit_event(Event::IssuanceCorrected {
+				target: issuance.target,
+				issued: issuance.issued,
+			});
+			issuance.deviation()
+		}
+
+		/// Measure the average block time of the ending year and correct the number of blocks
+		/// per year, so that a year of blocks lasts a year of time again. The correction is
+		/// bounded to half and twice `BLOCKS_PER_YEAR` to not overreact to stalled block
+		/// production.
+		/// Weight Reads: 1, Writes: 2
+		fn correct_blocks_per_year(now: T::BlockNumber, now_ms: u64) {
+			let Some((start_block, start_ms)) = YearStartedAt::<T>::mutate(|year_started_at| {
+				year_started_at.replace((now, now_ms))
+			}) else {
+				return
+			};
+
+			let blocks: u64 = now.saturating_sub(start_block).saturated_into();
+			let elapsed_ms = now_ms.saturating_sub(start_ms);
+			// the timestamp is not set in the genesis block
+			if start_ms == 0 || blocks == 0 || elapsed_ms == 0 {
+				return
+			}
+
+			let measured =
+				u128::from(blocks) * u128::from(MILLISECS_PER_YEAR) / u128::from(elapsed_ms);
+			let bounded =
+				measured.clamp(u128::from(BLOCKS_PER_YEAR / 2), u128::from(BLOCKS_PER_YEAR) * 2);
+			let blocks_per_year: T::BlockNumber = bounded.saturated_into();
+
+			let previous_blocks_per_year = BlocksPerYear::<T>::get();
+			BlocksPerYear::<T>::put(blocks_per_year);
+			Self::deposit_event(Event::InflationCorrected {
+				previous_blocks_per_year,
+				blocks_per_year,
+				average_block_time: elapsed_ms / blocks,
+			});
 		}
 
 		/// We do not expect this to underflow/overflow
@@ -269,6 +549,119 @@ pub mod pallet {
 			InflationParametersT { inflation_rate, disinflation_rate }
 		}
 
+		/// Inflation parameters of the current year for the given configuration
+		/// Weight Reads: 1
+		pub fn inflation_parameters_of_current_year(
+			inflation_config: &InflationConfigurationT,
+		) -> InflationParametersT {
+			match CurrentYear::<T>::get().cmp(&inflation_config.inflation_stagnation_year) {
+				Ordering::Less => Self::update_inflation_parameters(inflation_config),
+				Ordering::Equal | Ordering::Greater => InflationParametersT {
+					inflation_rate: inflation_config.inflation_stagnation_rate,
+					disinflation_rate: Perbill::one(),
+				},
+			}
+		}
+
+		/// Project the inflation of the next `years` years, starting with the year after the
+		/// current one. The issuance grows by the block rewards of every block, a configuration
+		/// scheduled by governance is taken into account. Blocks are expected to be produced at
+		/// the pace of the current `BlocksPerYear`. All storage changes are rolled back.
+		pub fn issuance_projection(years: u32) -> Vec<IssuanceProjection<Balance>> {
+			let years = years.min(MAX_PROJECTION_YEARS);
+			let now = frame_system::Pallet::<T>::block_number();
+			let now_ms: u64 = T::UnixTime::now().as_millis().saturated_into();
+			let account = T::PotId::get().into_account_truncating();
+
+			let projection: Result<_, DispatchError> = with_transaction(|| {
+				let mut projection = Vec::with_capacity(years as usize);
+				let mut inflation_config = InflationConfiguration::<T>::get();
+				let mut block_rewards = BlockRewards::<T>::get();
+				let mut year_issuance = CurrentYearIssuance::<T>::get();
+				let (mut block, mut block_ms) = (now, now_ms);
+				let mut year_end = DoRecalculationAt::<T>::get();
+
+				for _ in 0..years {
+					// issue the rewards of the remaining blocks of the year
+					let remaining_blocks: Balance = year_end.saturating_sub(block).saturated_into();
+					let rewards = block_rewards.saturating_mul(remaining_blocks);
+					let _ = T::Currency::deposit_creating(&account, rewards);
+
+					// the remaining blocks take their expected time, without a timestamp the
+					// target is accrued by blocks anyway
+					if block_ms != 0 {
+						let blocks_per_year: Balance = BlocksPerYear::<T>::get().saturated_into();
+						let remaining_ms = multiply_by_rational_with_rounding(
+							remaining_blocks,
+							MILLISECS_PER_YEAR.into(),
+							blocks_per_year.max(1),
+							Rounding::Down,
+						)
+						.unwrap_or_default();
+						block_ms = block_ms.saturating_add(remaining_ms.saturated_into());
+					}
+					block = year_end;
+					let (deficit, surplus) = year_issuance
+						.as_mut()
+						.map(|issuance| {
+							Self::accrue_issuance(issuance, block_rewards, block, block_ms);
+							issuance.deviation()
+						})
+						.unwrap_or_default();
+
+					let current_year = CurrentYear::<T>::get();
+					// the delayed TGE is the only recalculation in year 0
+					if current_year == 0 {
+						Self::fund_difference_balances();
+					}
+					let year = current_year + 1;
+					CurrentYear::<T>::put(year);
+
+					if let Some(pending_config) = PendingInflationConfiguration::<T>::take() {
+						inflation_config = pending_config;
+					}
+					let inflation_parameters =
+						Self::inflation_parameters_of_current_year(&inflation_config);
+					let yearly_target = Self::yearly_issuance(&inflation_parameters);
+					block_rewards =
+						Self::corrected_rewards_per_block(yearly_target, deficit, surplus);
+					year_issuance = Some(YearIssuance {
+						yearly_target,
+						target: deficit,
+						issued: surplus,
+						accrued_at: (block, block_ms),
+					});
+
+					projection.push(IssuanceProjection {
+						year,
+						total_issuance: T::Currency::total_issuance(),
+						inflation_parameters,
+						block_rewards,
+					});
+					year_end = block.saturating_add(BlocksPerYear::<T>::get());
+				}
+
+				TransactionOutcome::Rollback(Ok(projection))
+			});
+
+			projection.unwrap_or_default()
+		}
+
+		/// Move the next recalculation to the given block
+		/// Weight Reads: 2, Writes: 1
+		fn schedule_recalculation(do_recalculation_at: T::BlockNumber) -> DispatchResult {
+			// moving the delayed TGE would skip funding the total issuance
+			ensure!(CurrentYear::<T>::get() > 0, Error::<T>::FiscalYearUninitialized);
+			ensure!(
+				do_recalculation_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::RecalculationInThePast
+			);
+
+			DoRecalculationAt::<T>::put(do_recalculation_at);
+			Self::deposit_event(Event::RecalculationScheduled { do_recalculation_at });
+			Ok(())
+		}
+
 		pub fn fund_difference_balances() {
 			let account = T::PotId::get().into_account_truncating();
 			let now_total_issuance = T::Currency::total_issuance();
@@ -309,18 +702,30 @@ pub mod pallet {
 			weight_writes += 1;
 
 			// set the flag to calculate inflation parameters after a year(in blocks)
-			let racalculation_target_block = current_block + T::BlockNumber::from(BLOCKS_PER_YEAR);
+			let racalculation_target_block = current_block + BlocksPerYear::<T>::get();
+			weight_reads += 1;
 
 			// Update recalculation flag
 			DoRecalculationAt::<T>::put(racalculation_target_block);
 			weight_writes += 1;
 
 			let block_rewards = Pallet::<T>::rewards_per_block(&inflation_parameters);
-			weight_reads += 1;
+			weight_reads += 2;
 
 			BlockRewards::<T>::put(block_rewards);
 			weight_writes += 1;
 
+			// track the issuance of the first year to correct its deviation in the next one
+			let now_ms: u64 = T::UnixTime::now().as_millis().saturated_into();
+			CurrentYearIssuance::<T>::put(YearIssuance {
+				yearly_target: Pallet::<T>::yearly_issuance(&inflation_parameters),
+				target: 0,
+				issued: 0,
+				accrued_at: (current_block, now_ms),
+			});
+			weight_reads += 2;
+			weight_writes += 1;
+
 			T::DbWeight::get().reads_writes(weight_reads, weight_writes)
 		}
 
@@ -126,6 +126,8 @@ impl inflation_manager::Config for TestRuntime {
 	type WeightInfo = weights::WeightInfo<TestRuntime>;
 	type DoInitializeAt = InitializeInflationAt;
 	type BlockRewardBeforeInitialize = BlockRewardBeforeInitialize;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type UnixTime = Timestamp;
 }
 pub struct ExternalityBuilder {
 	// endowed accounts with balances
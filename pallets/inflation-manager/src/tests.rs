@@ -1,5 +1,5 @@
 use super::*;
-use frame_support::assert_noop;
+use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use mock::*;
 use peaq_primitives_xcm::BlockNumber;
@@ -232,6 +232,347 @@ fn inflation_parameters_correctness_as_expected() {
 	})
 }
 
+#[test]
+fn set_inflation_configuration_immediately() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		System::set_block_number(do_initialize_at);
+		InflationManager::on_finalize(do_initialize_at);
+
+		let inflation_configuration = new_inflation_configuration();
+		assert_ok!(InflationManager::set_inflation_configuration(
+			RawOrigin::Root.into(),
+			inflation_configuration.clone(),
+			ApplyConfiguration::Immediately,
+		));
+
+		let expected_inflation_parameters = InflationParametersT {
+			inflation_rate: Perbill::from_percent(5),
+			disinflation_rate: Perbill::one(),
+		};
+		let snapshot = InflationManagerSnapshot::new();
+		assert_eq!(snapshot.inflation_configuration, inflation_configuration);
+		assert_eq!(snapshot.inflation_parameters, expected_inflation_parameters);
+		assert_eq!(snapshot.current_year, 1);
+		assert_eq!(
+			snapshot.block_rewards,
+			InflationManager::rewards_per_block(&expected_inflation_parameters)
+		);
+		assert_eq!(InflationManager::pending_inflation_configuration(), None);
+		System::assert_has_event(RuntimeEvent::InflationManager(
+			Event::InflationConfigurationSet { inflation_configuration },
+		));
+	})
+}
+
+#[test]
+fn set_inflation_configuration_from_next_year() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		System::set_block_number(do_initialize_at);
+		InflationManager::on_finalize(do_initialize_at);
+		let snapshot_before = InflationManagerSnapshot::new();
+
+		let inflation_configuration = new_inflation_configuration();
+		assert_ok!(InflationManager::set_inflation_configuration(
+			RawOrigin::Root.into(),
+			inflation_configuration.clone(),
+			ApplyConfiguration::FromNextYear,
+		));
+		System::assert_last_event(RuntimeEvent::InflationManager(
+			Event::InflationConfigurationScheduled {
+				inflation_configuration: inflation_configuration.clone(),
+				do_recalculation_at: snapshot_before.do_recalculation_at.into(),
+			},
+		));
+
+		// nothing changes before the next year starts
+		assert_eq!(InflationManagerSnapshot::new(), snapshot_before);
+		assert_eq!(
+			InflationManager::pending_inflation_configuration(),
+			Some(inflation_configuration.clone())
+		);
+
+		let snapshot =
+			InflationManagerSnapshot::take_snapshot_at(snapshot_before.do_recalculation_at);
+		assert_eq!(snapshot.current_year, 2);
+		assert_eq!(snapshot.inflation_configuration, inflation_configuration);
+		assert_eq!(
+			snapshot.inflation_parameters,
+			InflationParametersT {
+				inflation_rate: Perbill::from_percent(5) * Perbill::from_percent(80),
+				disinflation_rate: Perbill::from_percent(80),
+			}
+		);
+		assert_eq!(InflationManager::pending_inflation_configuration(), None);
+	})
+}
+
+#[test]
+fn set_inflation_configuration_fails_as_expected() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			InflationManager::set_inflation_configuration(
+				RuntimeOrigin::signed(1),
+				new_inflation_configuration(),
+				ApplyConfiguration::Immediately,
+			),
+			BadOrigin
+		);
+
+		// parameters cannot be computed before the TGE
+		assert_noop!(
+			InflationManager::set_inflation_configuration(
+				RawOrigin::Root.into(),
+				new_inflation_configuration(),
+				ApplyConfiguration::Immediately,
+			),
+			Error::<TestRuntime>::FiscalYearUninitialized
+		);
+
+		let mut inflation_configuration = new_inflation_configuration();
+		inflation_configuration.inflation_stagnation_year = 0;
+		assert_noop!(
+			InflationManager::set_inflation_configuration(
+				RawOrigin::Root.into(),
+				inflation_configuration,
+				ApplyConfiguration::FromNextYear,
+			),
+			Error::<TestRuntime>::InvalidInflationConfiguration
+		);
+	})
+}
+
+#[test]
+fn set_and_delay_recalculation() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		// the delayed TGE cannot be moved
+		assert_noop!(
+			InflationManager::set_recalculation_at(RawOrigin::Root.into(), 100),
+			Error::<TestRuntime>::FiscalYearUninitialized
+		);
+
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		System::set_block_number(do_initialize_at);
+		InflationManager::on_finalize(do_initialize_at);
+
+		assert_noop!(
+			InflationManager::set_recalculation_at(RuntimeOrigin::signed(1), 100),
+			BadOrigin
+		);
+		assert_noop!(
+			InflationManager::set_recalculation_at(RawOrigin::Root.into(), do_initialize_at),
+			Error::<TestRuntime>::RecalculationInThePast
+		);
+
+		assert_ok!(InflationManager::set_recalculation_at(RawOrigin::Root.into(), 100));
+		assert_eq!(InflationManager::do_recalculation_at(), 100);
+		System::assert_last_event(RuntimeEvent::InflationManager(Event::RecalculationScheduled {
+			do_recalculation_at: 100,
+		}));
+
+		assert_ok!(InflationManager::delay_recalculation(RawOrigin::Root.into(), 50));
+		assert_eq!(InflationManager::do_recalculation_at(), 150);
+
+		let snapshot = InflationManagerSnapshot::take_snapshot_at(150);
+		assert_eq!(snapshot.current_year, 2);
+		assert_eq!(snapshot.do_recalculation_at, 150 + BLOCKS_PER_YEAR);
+	})
+}
+
+#[test]
+fn issuance_projection_follows_yearly_recalculation() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		let now = 5;
+		System::set_block_number(now);
+
+		let snapshot_before = InflationManagerSnapshot::new();
+		let total_issuance_before = Balances::total_issuance();
+		let projection = InflationManager::issuance_projection(3);
+
+		// the projection does not change any storage
+		assert_eq!(InflationManagerSnapshot::new(), snapshot_before);
+		assert_eq!(Balances::total_issuance(), total_issuance_before);
+		assert_eq!(projection.len(), 3);
+
+		// issue the block rewards block by block and compare with the projection
+		let account: AccountId = <TestRuntime as Config>::PotId::get().into_account_truncating();
+		let mut block = now;
+		for (i, year) in projection.into_iter().enumerate() {
+			let do_recalculation_at = InflationManager::do_recalculation_at();
+			let rewards = InflationManager::block_rewards() *
+				Balance::from((do_recalculation_at - block) as u32);
+			let _ = Balances::deposit_creating(&account, rewards);
+
+			let snapshot = InflationManagerSnapshot::take_snapshot_at(do_recalculation_at as u32);
+			assert_eq!(year.year, i as u128 + 1);
+			assert_eq!(year.total_issuance, Balances::total_issuance());
+			assert_eq!(year.inflation_parameters, snapshot.inflation_parameters);
+			assert_eq!(year.block_rewards, snapshot.block_rewards);
+			block = do_recalculation_at;
+		}
+		assert_eq!(block, do_initialize_at + 2 * BLOCKS_PER_YEAR as u64);
+	})
+}
+
+#[test]
+fn issuance_projection_is_bounded() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			InflationManager::issuance_projection(MAX_PROJECTION_YEARS + 1).len(),
+			MAX_PROJECTION_YEARS as usize
+		);
+	})
+}
+
+#[test]
+fn blocks_per_year_corrected_by_block_time() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		// year 1 starts at the TGE
+		System::set_block_number(do_initialize_at);
+		Timestamp::set_timestamp(do_initialize_at * 12_000);
+		InflationManager::on_finalize(do_initialize_at);
+		assert_eq!(InflationManager::year_started_at(), Some((do_initialize_at, 120_000)));
+		let yearly_target = InflationManager::current_year_issuance().unwrap().yearly_target;
+		let block_rewards = InflationManager::block_rewards();
+
+		// blocks took 18s instead of 12s during year 1
+		let year_2_at = do_initialize_at + BLOCKS_PER_YEAR as u64;
+		System::set_block_number(year_2_at);
+		Timestamp::set_timestamp(120_000 + BLOCKS_PER_YEAR as u64 * 18_000);
+		InflationManager::on_finalize(year_2_at);
+
+		// year 1 lasted one and a half years, the missing issuance is added to year 2
+		let target = yearly_target * 3 / 2;
+		let issued = block_rewards * Balance::from(BLOCKS_PER_YEAR);
+		System::assert_has_event(RuntimeEvent::InflationManager(Event::IssuanceCorrected {
+			target,
+			issued,
+		}));
+
+		let blocks_per_year = BLOCKS_PER_YEAR as u64 * 2 / 3;
+		assert_eq!(InflationManager::blocks_per_year(), blocks_per_year);
+		assert_eq!(InflationManager::do_recalculation_at(), year_2_at + blocks_per_year);
+		assert_eq!(
+			InflationManager::block_rewards(),
+			(InflationManager::inflation_parameters().inflation_rate * Balances::total_issuance() +
+				target - issued) / Balance::from(blocks_per_year as u32)
+		);
+		System::assert_has_event(RuntimeEvent::InflationManager(Event::InflationCorrected {
+			previous_blocks_per_year: BLOCKS_PER_YEAR as u64,
+			blocks_per_year,
+			average_block_time: 18_000,
+		}));
+	})
+}
+
+#[test]
+fn blocks_per_year_correction_is_bounded() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		System::set_block_number(do_initialize_at);
+		Timestamp::set_timestamp(120_000);
+		InflationManager::on_finalize(do_initialize_at);
+
+		// block production stalled for a long time
+		let year_2_at = do_initialize_at + BLOCKS_PER_YEAR as u64;
+		System::set_block_number(year_2_at);
+		Timestamp::set_timestamp(120_000 + 5 * MILLISECS_PER_YEAR);
+		InflationManager::on_finalize(year_2_at);
+		assert_eq!(InflationManager::blocks_per_year(), BLOCKS_PER_YEAR as u64 / 2);
+	})
+}
+
+#[test]
+fn blocks_per_year_not_corrected_without_timestamp() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		InflationManagerSnapshot::take_snapshot_at(do_initialize_at as u32);
+		InflationManagerSnapshot::take_snapshot_at(do_initialize_at as u32 + BLOCKS_PER_YEAR);
+
+		assert_eq!(InflationManager::blocks_per_year(), BLOCKS_PER_YEAR as u64);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::InflationManager(Event::InflationCorrected { .. })
+		)));
+	})
+}
+
+#[test]
+fn issuance_converges_to_target_with_irregular_block_times() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let account: AccountId = <TestRuntime as Config>::PotId::get().into_account_truncating();
+		let mut now = <TestRuntime as Config>::DoInitializeAt::get();
+		let mut now_ms = 120_000;
+		System::set_block_number(now);
+		Timestamp::set_timestamp(now_ms);
+		InflationManager::on_finalize(now);
+
+		let (mut targeted, mut issued): (Balance, Balance) = (0, 0);
+		// average block time (ms) of each year, block production settles at 14s
+		for block_time in [18_000, 9_000, 15_000, 14_000, 14_000, 14_000, 14_000] {
+			let yearly_target = InflationManager::current_year_issuance().unwrap().yearly_target;
+			let blocks = InflationManager::do_recalculation_at() - now;
+			let elapsed = blocks * block_time;
+			let rewards = InflationManager::block_rewards() * Balance::from(blocks);
+			let _ = Balances::deposit_creating(&account, rewards);
+			targeted += yearly_target * Balance::from(elapsed) / Balance::from(MILLISECS_PER_YEAR);
+			issued += rewards;
+
+			now += blocks;
+			now_ms += elapsed;
+			System::set_block_number(now);
+			Timestamp::set_timestamp(now_ms);
+			InflationManager::on_finalize(now);
+		}
+
+		// the deviation of the irregular years has been corrected
+		let year_issuance = InflationManager::current_year_issuance().unwrap();
+		let (deficit, surplus) = year_issuance.deviation();
+		assert!(deficit.max(surplus) <= Perbill::from_parts(1_000) * year_issuance.yearly_target);
+		assert!(targeted.abs_diff(issued) <= Perbill::from_parts(1_000) * targeted);
+	})
+}
+
+#[test]
+fn issuance_correction_is_bounded() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let do_initialize_at = <TestRuntime as Config>::DoInitializeAt::get();
+		System::set_block_number(do_initialize_at);
+		Timestamp::set_timestamp(120_000);
+		InflationManager::on_finalize(do_initialize_at);
+
+		// block production stalled for a long time
+		let year_2_at = do_initialize_at + BLOCKS_PER_YEAR as u64;
+		System::set_block_number(year_2_at);
+		Timestamp::set_timestamp(120_000 + 5 * MILLISECS_PER_YEAR);
+		InflationManager::on_finalize(year_2_at);
+
+		// at most twice the targeted issuance is issued, the rest of the deficit is carried over
+		let year_issuance = InflationManager::current_year_issuance().unwrap();
+		let blocks_per_year = Balance::from(InflationManager::blocks_per_year() as u32);
+		assert_eq!(
+			InflationManager::block_rewards(),
+			year_issuance.yearly_target * 2 / blocks_per_year
+		);
+		assert!(year_issuance.target > year_issuance.yearly_target * 3);
+		assert_eq!(year_issuance.issued, 0);
+	})
+}
+
+fn new_inflation_configuration() -> InflationConfigurationT {
+	InflationConfigurationT {
+		inflation_parameters: InflationParametersT {
+			inflation_rate: Perbill::from_percent(5),
+			disinflation_rate: Perbill::from_percent(20),
+		},
+		inflation_stagnation_rate: Perbill::from_percent(2),
+		inflation_stagnation_year: 10,
+	}
+}
+
 /// Represents inflation manager storage snapshot at current block
 #[derive(PartialEq, Eq, Clone, RuntimeDebug)]
 struct InflationManagerSnapshot {
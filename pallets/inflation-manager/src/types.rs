@@ -2,7 +2,7 @@ use crate::pallet::Config as PalletConfig;
 use frame_support::{pallet_prelude::*, traits::Currency};
 
 use scale_info::TypeInfo;
-use sp_runtime::Perbill;
+use sp_runtime::{traits::Saturating, Perbill};
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type BalanceOf<T> = <<T as PalletConfig>::Currency as Currency<AccountIdOf<T>>>::Balance;
@@ -32,6 +32,62 @@ pub struct InflationConfiguration {
 	pub inflation_stagnation_year: u128,
 }
 
+/// Point in time from which a new inflation configuration applies.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApplyConfiguration {
+	/// Recompute the inflation parameters and block rewards of the current year right away.
+	Immediately,
+	/// Keep the current inflation parameters until the next recalculation.
+	FromNextYear,
+}
+
+impl InflationConfiguration {
+	/// Whether the configuration can be used to compute inflation parameters.
+	pub fn is_valid(&self) -> bool {
+		// year 1 is the first year of inflation
+		self.inflation_stagnation_year > 0
+	}
+}
+
+/// Projected inflation of a single year.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct IssuanceProjection<Balance> {
+	/// The year the projection is for.
+	pub year: u128,
+	/// The total issuance at the beginning of the year.
+	pub total_issuance: Balance,
+	/// The inflation parameters of the year.
+	pub inflation_parameters: InflationParameters,
+	/// The rewards issued in every block of the year.
+	pub block_rewards: Balance,
+}
+
+/// Issuance of the current year, tracked to correct the block rewards of the next year by the
+/// deviation of the issued rewards from the targeted issuance.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct YearIssuance<Balance, BlockNumber> {
+	/// The issuance targeted for a year of time by the current inflation parameters.
+	pub yearly_target: Balance,
+	/// The issuance targeted since the start of the year, including the deficit of the past
+	/// years.
+	pub target: Balance,
+	/// The rewards issued since the start of the year, including the surplus of the past years.
+	pub issued: Balance,
+	/// The block and unix timestamp in milliseconds up to which the issuance is accrued.
+	pub accrued_at: (BlockNumber, u64),
+}
+
+impl<Balance: Copy + Saturating, BlockNumber> YearIssuance<Balance, BlockNumber> {
+	/// The deficit and surplus of the issued rewards towards the targeted issuance, one of
+	/// which is zero.
+	pub fn deviation(&self) -> (Balance, Balance) {
+		(self.target.saturating_sub(self.issued), self.issued.saturating_sub(self.target))
+	}
+}
+
 impl Default for InflationConfiguration {
 	fn default() -> Self {
 		Self {
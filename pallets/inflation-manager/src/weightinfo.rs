@@ -4,4 +4,6 @@ use frame_support::weights::Weight;
 
 pub trait WeightInfo {
 	fn transfer_all_pot() -> Weight;
+	fn set_inflation_configuration() -> Weight;
+	fn set_recalculation_at() -> Weight;
 }
@@ -45,4 +45,40 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Storage: InflationManager CurrentYear (r:1 w:0)
+	/// Proof: InflationManager CurrentYear (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Balances TotalIssuance (r:1 w:0)
+	/// Proof: Balances TotalIssuance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: InflationManager PendingInflationConfiguration (r:0 w:1)
+	/// Proof: InflationManager PendingInflationConfiguration (max_values: Some(1), max_size: Some(28), added: 523, mode: MaxEncodedLen)
+	/// Storage: InflationManager InflationConfiguration (r:0 w:1)
+	/// Proof: InflationManager InflationConfiguration (max_values: Some(1), max_size: Some(28), added: 523, mode: MaxEncodedLen)
+	/// Storage: InflationManager InflationParameters (r:0 w:1)
+	/// Proof: InflationManager InflationParameters (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: InflationManager BlockRewards (r:0 w:1)
+	/// Proof: InflationManager BlockRewards (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_inflation_configuration() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `145`
+		//  Estimated: `1501`
+		// Minimum execution time: 24_717_000 picoseconds.
+		Weight::from_parts(25_362_000, 0)
+			.saturating_add(Weight::from_parts(0, 1501))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: InflationManager CurrentYear (r:1 w:0)
+	/// Proof: InflationManager CurrentYear (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: InflationManager DoRecalculationAt (r:1 w:1)
+	/// Proof: InflationManager DoRecalculationAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_recalculation_at() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `129`
+		//  Estimated: `1501`
+		// Minimum execution time: 14_206_000 picoseconds.
+		Weight::from_parts(14_618_000, 0)
+			.saturating_add(Weight::from_parts(0, 1501))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
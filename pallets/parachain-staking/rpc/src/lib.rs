@@ -0,0 +1,141 @@
+//! RPC interface for the parachain-staking pallet.
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::Codec;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+pub use parachain_staking_runtime_api::{
+	ParachainStakingApi as ParachainStakingRuntimeApi, RewardRecord, StakingRates,
+};
+
+#[rpc(client, server)]
+pub trait ParachainStakingApi<BlockHash, AccountId, Balance> {
+	/// The rewards an account is projected to receive as collator and
+	/// delegator for the current session.
+	#[method(name = "parachainStaking_getProjectedRewards")]
+	fn get_projected_rewards(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Balance>;
+
+	/// The current staking rates of collators and delegators.
+	#[method(name = "parachainStaking_getStakingRates")]
+	fn get_staking_rates(&self, at: Option<BlockHash>) -> RpcResult<StakingRates>;
+
+	/// The number of blocks each collator has authored in the current session.
+	#[method(name = "parachainStaking_getCollatorBlocks")]
+	fn get_collator_blocks(&self, at: Option<BlockHash>) -> RpcResult<Vec<(AccountId, u32)>>;
+
+	/// The rewards an account has earned as collator and delegator which can
+	/// be claimed.
+	#[method(name = "parachainStaking_getClaimableRewards")]
+	fn get_claimable_rewards(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Balance>;
+
+	/// The rewards an account has received in the most recent sessions,
+	/// oldest first.
+	#[method(name = "parachainStaking_getRewardHistory")]
+	fn get_reward_history(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<RewardRecord<AccountId, Balance>>>;
+}
+
+/// A struct that implements the [`ParachainStakingApiServer`].
+pub struct ParachainStaking<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> ParachainStaking<C, B> {
+	/// Create new `ParachainStaking` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for failed runtime calls.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<C, Block, AccountId, Balance>
+	ParachainStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for ParachainStaking<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ParachainStakingRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec,
+{
+	fn get_projected_rewards(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_projected_rewards(at, account).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_staking_rates(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<StakingRates> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_staking_rates(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_collator_blocks(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(AccountId, u32)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_collator_blocks(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_claimable_rewards(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_claimable_rewards(at, account).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_reward_history(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RewardRecord<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_reward_history(at, account).map_err(runtime_error_into_rpc_err)
+	}
+}
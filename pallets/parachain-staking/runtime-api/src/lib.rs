@@ -0,0 +1,32 @@
+//! Runtime API definition for the parachain-staking pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+pub use parachain_staking::types::{RewardRecord, StakingRates};
+
+sp_api::decl_runtime_apis! {
+	pub trait ParachainStakingApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The rewards an account is projected to receive as collator and
+		/// delegator for the current session.
+		fn get_projected_rewards(account: AccountId) -> Balance;
+		/// The current staking rates of collators and delegators.
+		fn get_staking_rates() -> StakingRates;
+		/// The number of blocks each collator has authored in the current
+		/// session.
+		fn get_collator_blocks() -> Vec<(AccountId, u32)>;
+		/// The rewards an account has earned as collator and delegator which
+		/// can be claimed.
+		fn get_claimable_rewards(account: AccountId) -> Balance;
+		/// The rewards an account has received in the most recent sessions,
+		/// oldest first.
+		fn get_reward_history(account: AccountId) -> Vec<RewardRecord<AccountId, Balance>>;
+	}
+}
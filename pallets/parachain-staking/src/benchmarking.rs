@@ -18,15 +18,23 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 //! Benchmarking
-use crate::{types::RoundInfo, *};
+use crate::{
+	types::{RoundInfo, SessionRewardPerStake, UnappliedSlash},
+	*,
+};
 use frame_benchmarking::v1::{account, benchmarks, impl_benchmark_test_suite};
 use frame_support::{
 	assert_ok,
-	traits::{Currency, Get, OnInitialize},
+	traits::{Currency, EnsureOrigin, Get, OnInitialize},
+	BoundedVec,
 };
 use frame_system::{Pallet as System, RawOrigin};
 use pallet_session::Pallet as Session;
-use sp_runtime::traits::{One, SaturatedConversion, StaticLookup};
+use sp_runtime::{
+	traits::{One, SaturatedConversion, StaticLookup, Zero},
+	Percent,
+};
+use sp_staking::SessionIndex;
 use sp_std::{convert::TryInto, vec::Vec};
 
 const COLLATOR_ACCOUNT_SEED: u32 = 0;
@@ -557,6 +565,82 @@ benchmarks! {
 		assert_eq!(<MaxCollatorCandidateStake<T>>::get(), new);
 	}
 
+	set_commission {
+		let candidates = setup_collator_candidates::<T>(T::MinCollators::get(), None);
+		let candidate = candidates[0].clone();
+		let commission = T::MaxCollatorCommission::get();
+	}: _(RawOrigin::Signed(candidate.clone()), commission)
+	verify {
+		assert_eq!(<CandidatePool<T>>::get(&candidate).unwrap().commission, commission);
+		assert_eq!(<LastCommissionChange<T>>::get(&candidate), Some(<Round<T>>::get().current));
+	}
+
+	set_auto_compound {
+		let candidates = setup_collator_candidates::<T>(T::MinCollators::get(), None);
+		let candidate = candidates[0].clone();
+		let delegator = fill_delegators::<T>(1, candidate.clone(), 0u32)[0].clone();
+		let percentage = Percent::from_percent(50);
+	}: _(RawOrigin::Signed(delegator.clone()), T::Lookup::unlookup(candidate.clone()), percentage)
+	verify {
+		assert_eq!(<AutoCompound<T>>::get(&delegator, &candidate), percentage);
+	}
+
+	cancel_deferred_slash {
+		let s in 1 .. T::MaxTopCandidates::get();
+
+		let candidates = setup_collator_candidates::<T>(s, None);
+		let session: SessionIndex = 1;
+		let slashes: BoundedVec<_, T::MaxTopCandidates> = candidates
+			.iter()
+			.map(|collator| UnappliedSlash {
+				collator: collator.clone(),
+				own: T::MinCollatorStake::get(),
+				others: Default::default(),
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("Did not exceed MaxTopCandidates q.e.d.");
+		for collator in candidates.iter() {
+			<DeselectedCandidates<T>>::insert(collator, session);
+		}
+		<UnappliedSlashes<T>>::insert(session, slashes);
+		let indices = (0..s).collect::<Vec<u32>>();
+		let origin = T::SlashCancelOrigin::try_successful_origin()
+			.expect("SlashCancelOrigin has a successful origin q.e.d.");
+	}: _<T::RuntimeOrigin>(origin, session, indices)
+	verify {
+		assert!(<UnappliedSlashes<T>>::get(session).is_empty());
+		assert!(<DeselectedCandidates<T>>::get(&candidates[0]).is_none());
+	}
+
+	claim_rewards {
+		let candidates = setup_collator_candidates::<T>(T::MinCollators::get(), None);
+		let collator = candidates[0].clone();
+		let delegator = fill_delegators::<T>(1, collator.clone(), 0u32)[0].clone();
+		let rewards = T::MinDelegatorStake::get();
+
+		// the delegation has earned its stake since it was last settled, half of it is restaked
+		<AutoCompound<T>>::insert(&delegator, &collator, Percent::from_percent(50));
+		<RewardPerStake<T>>::insert(&collator, REWARD_PER_STAKE_SCALE);
+		let session = Session::<T>::current_index().saturating_sub(1);
+		let len = T::MaxRewardHistory::get();
+		if !len.is_zero() {
+			<RewardPerStakeHistory<T>>::insert(&collator, session % len, SessionRewardPerStake {
+				session,
+				increase: REWARD_PER_STAKE_SCALE,
+				reward_per_stake: REWARD_PER_STAKE_SCALE,
+			});
+		}
+		let pot = Pallet::<T>::account_id();
+		T::Currency::make_free_balance_be(&pot, rewards * 10u32.into());
+		<UnclaimedRewards<T>>::put(rewards);
+	}: _(RawOrigin::Signed(delegator.clone()))
+	verify {
+		assert!(<Rewards<T>>::get(&delegator).is_zero());
+		assert!(<UnclaimedRewards<T>>::get().is_zero());
+		assert_eq!(<DelegationRewardPerStake<T>>::get(&delegator, &collator), REWARD_PER_STAKE_SCALE);
+	}
+
 	// [Post-launch TODO]: Activate after increasing MaxCollatorsPerDelegator to at least 2. Expected to throw otherwise.
 	// delegate_another_candidate {
 	// 	// we need at least 2 collators
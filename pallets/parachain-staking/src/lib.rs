@@ -95,6 +95,23 @@
 //!   number. Multiple locks always operate over the same funds, so they "overlay" rather than
 //!   "stack"
 //!
+//! - **Rewards:** At the end of each session, the rewards of collators are credited to their
+//!   claimable rewards. The rewards of delegators are added to the reward per staked unit of their
+//!   collator and settled lazily, i.e. credited to their claimable rewards when they claim them or
+//!   change their delegation. The auto-compound share of the settled rewards of a delegation is
+//!   restaked. Credited rewards remain in the staking pot until they are claimed by calling
+//!   `claim_rewards`. Each credited reward is also recorded in the reward history of the account,
+//!   which keeps the rewards of the most recent `MaxRewardHistory` sessions.
+//!
+//! - **Offence:** A collator did not author any block in a session for which it was selected. A
+//!   share of its stake is slashed after `SlashDeferDuration` sessions and it is not selected for
+//!   the next `OffenceDeselectDuration` sessions. Collator candidates and delegations whose stake
+//!   falls below `MinCollatorStake` or `MinDelegatorStake` because of the slash are removed, their
+//!   remaining stake being unstaked.
+//!
+//!   Slashing equivocating collators is out of scope of this pallet. AuRa on the parachain does not
+//!   report equivocations, so there is no offence to slash for. Being offline is the only offence.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -127,6 +144,15 @@
 //!   candidate's total stake by the provided amount down to `MinDelegatorStake`.
 //! - `unlock_unstaked` - Attempt to unlock previously unstaked balance from any account. Succeeds
 //!   if at least one unstake call happened at least `StakeDuration` blocks ago.
+//! - `set_commission` - Set the share a collator candidate takes off the rewards of their
+//!   delegators, up to `MaxCollatorCommission`. Can be changed at most once per round and applies
+//!   from the next round on.
+//! - `set_auto_compound` - Set the share of the rewards of a delegation which is automatically
+//!   restaked into the same collator candidate at the end of each session.
+//! - `cancel_deferred_slash` - Cancel slashes of offline collators before they are applied. Only
+//!   callable by `SlashCancelOrigin`.
+//! - `claim_rewards` - Pay out the staking rewards which have been credited to the caller as
+//!   collator and delegator.
 //!
 //! ## Genesis config
 //!
@@ -167,10 +193,11 @@ pub mod pallet {
 	use frame_support::{
 		assert_ok,
 		pallet_prelude::*,
-		storage::bounded_btree_map::BoundedBTreeMap,
+		storage::{bounded_btree_map::BoundedBTreeMap, with_storage_layer},
 		traits::{
-			Currency, EstimateNextSessionRotation, ExistenceRequirement::KeepAlive, Get,
-			LockIdentifier, LockableCurrency, ReservableCurrency, StorageVersion, WithdrawReasons,
+			Currency, EnsureOrigin, EstimateNextSessionRotation, ExistenceRequirement::KeepAlive,
+			Get, LockIdentifier, LockableCurrency, OnUnbalanced, ReservableCurrency,
+			StorageVersion, WithdrawReasons,
 		},
 		BoundedVec, PalletId,
 	};
@@ -178,12 +205,14 @@ pub mod pallet {
 	use pallet_balances::{BalanceLock, Locks};
 	use pallet_session::ShouldEndSession;
 	use scale_info::TypeInfo;
+	use sp_core::U256;
 	use sp_runtime::{
+		helpers_128bit::multiply_by_rational_with_rounding,
 		traits::{
 			AccountIdConversion, CheckedAdd, CheckedMul, CheckedSub, Convert, One,
 			SaturatedConversion, Saturating, StaticLookup, Zero,
 		},
-		Permill,
+		Perbill, Percent, Permill, Rounding,
 	};
 	use sp_staking::SessionIndex;
 	use sp_std::{convert::TryInto, fmt::Debug, prelude::*};
@@ -192,7 +221,9 @@ pub mod pallet {
 		set::OrderedSet,
 		types::{
 			BalanceOf, Candidate, CandidateOf, CandidateStatus, DelegationCounter, Delegator,
-			ReplacedDelegator, Reward, RoundInfo, Stake, StakeOf, TotalStake,
+			NegativeImbalanceOf, ReplacedDelegator, Reward, RewardRecord, RewardRecordOf,
+			RoundInfo, SessionRewardPerStake, Stake, StakeOf, StakingRates, TotalStake,
+			UnappliedSlash, UnappliedSlashOf,
 		},
 		weightinfo::WeightInfo,
 	};
@@ -202,8 +233,12 @@ pub mod pallet {
 	pub(crate) const OLD_STAKING_ID: LockIdentifier = *b"kiltpstk";
 	pub(crate) const STAKING_ID: LockIdentifier = *b"peaqstak";
 
+	/// The scale of the reward per staked unit of delegators, such that the
+	/// rewards of small stakes do not round to zero.
+	pub(crate) const REWARD_PER_STAKE_SCALE: u128 = 10u128.pow(30);
+
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(10);
 
 	/// Pallet for parachain staking.
 	#[pallet::pallet]
@@ -324,6 +359,48 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxUnstakeRequests: Get<u32>;
 
+		/// Maximum commission a collator candidate can take off the rewards of
+		/// their delegators.
+		#[pallet::constant]
+		type MaxCollatorCommission: Get<Perbill>;
+
+		/// The share of the stake which is slashed from a collator that did
+		/// not author any block in a session in which it was selected. This is
+		/// the only offence slashed for, equivocations are not.
+		///
+		/// Setting this to zero disables the detection of offline collators.
+		#[pallet::constant]
+		type OfflineSlashFraction: Get<Perbill>;
+
+		/// Whether the delegators of an offline collator are slashed by the
+		/// same fraction as the collator.
+		#[pallet::constant]
+		type SlashDelegators: Get<bool>;
+
+		/// The number of sessions after the offence until a slash is applied.
+		///
+		/// During this window, the slash can be cancelled by the
+		/// `SlashCancelOrigin`. It should be shorter than `StakeDuration` so
+		/// that funds cannot be unlocked before the slash is applied.
+		#[pallet::constant]
+		type SlashDeferDuration: Get<SessionIndex>;
+
+		/// The number of sessions for which an offline collator is excluded
+		/// from the selection of collators.
+		#[pallet::constant]
+		type OffenceDeselectDuration: Get<SessionIndex>;
+
+		/// The number of most recent sessions whose rewards are kept in the
+		/// reward history of an account.
+		#[pallet::constant]
+		type MaxRewardHistory: Get<u32>;
+
+		/// The origin which can cancel a deferred slash.
+		type SlashCancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Handler for the slashed funds.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
 		/// Account Identifier from which the internal Pot is generated.
 		#[pallet::constant]
 		type PotId: Get<PalletId>;
@@ -419,6 +496,21 @@ pub mod pallet {
 		StakeNotFound,
 		/// Cannot unlock when Unstaked is empty.
 		UnstakingIsEmpty,
+		/// The commission is above the maximum allowed as set in the pallet's
+		/// configuration.
+		CommissionAboveMax,
+		/// The collator candidate has already changed their commission in the
+		/// current round.
+		CommissionAlreadyChanged,
+		/// No slash indices were provided.
+		EmptySlashIndices,
+		/// The provided slash indices are not sorted in ascending order or
+		/// contain duplicates.
+		NotSortedAndUnique,
+		/// There is no deferred slash at one of the provided indices.
+		InvalidSlashIndex,
+		/// The account has no rewards to claim.
+		RewardsNotFound,
 	}
 
 	#[pallet::event]
@@ -493,7 +585,7 @@ pub mod pallet {
 		/// \[account, collator candidate's account, old amount of delegators'
 		/// funds staked, new amount of delegators' funds staked\]
 		DelegatorLeftCollator(T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
-		/// A collator or a delegator has received a reward.
+		/// A collator or a delegator has claimed their rewards.
 		/// \[account, amount of reward\]
 		Rewarded(T::AccountId, BalanceOf<T>),
 		/// The maximum number of collator candidates selected in future
@@ -503,6 +595,32 @@ pub mod pallet {
 		/// \[round number, first block in the current round, old value, new
 		/// value\]
 		BlocksPerRoundSet(SessionIndex, T::BlockNumber, T::BlockNumber, T::BlockNumber),
+		/// A collator candidate has changed the commission taken off the
+		/// rewards of their delegators. \[collator's account, old commission,
+		/// new commission\]
+		CollatorCommissionChanged(T::AccountId, Perbill, Perbill),
+		/// A delegator has changed the share of their rewards which is
+		/// restaked automatically. \[delegator's account, collator's account,
+		/// new auto-compound percentage\]
+		AutoCompoundSet(T::AccountId, T::AccountId, Percent),
+		/// A part of a delegator's reward was restaked automatically.
+		/// \[delegator's account, collator's account, amount restaked, new
+		/// delegation stake\]
+		Compounded(T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// A selected collator did not author any block in a session and a
+		/// slash has been deferred. \[collator's account, session of the
+		/// offence, session in which the slash is applied\]
+		OffenceReported(T::AccountId, SessionIndex, SessionIndex),
+		/// A collator candidate was excluded from the selection of collators.
+		/// \[collator's account, session from which on the candidate can be
+		/// selected again\]
+		CandidateDeselected(T::AccountId, SessionIndex),
+		/// An account has been slashed. \[account, amount slashed\]
+		Slashed(T::AccountId, BalanceOf<T>),
+		/// A deferred slash has been cancelled.
+		/// \[session in which the slash would have been applied, collator's
+		/// account\]
+		SlashCancelled(SessionIndex, T::AccountId),
 	}
 
 	#[pallet::hooks]
@@ -565,6 +683,122 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The share of the rewards of a delegation which is automatically
+	/// restaked into the same collator candidate.
+	///
+	/// It maps from a delegator and a collator candidate to the percentage.
+	/// The rewards of delegations without an entry have to be claimed.
+	#[pallet::storage]
+	#[pallet::getter(fn auto_compound)]
+	pub(crate) type AutoCompound<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		Percent,
+		ValueQuery,
+	>;
+
+	/// The staking rewards which have been credited to an account as
+	/// collator or delegator but have not been claimed yet.
+	///
+	/// It maps from an account to the claimable amount.
+	#[pallet::storage]
+	#[pallet::getter(fn rewards)]
+	pub(crate) type Rewards<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The sum of all credited rewards which have not been claimed yet.
+	///
+	/// These funds remain in the staking pot and are excluded from the
+	/// rewards which are issued at the end of a session.
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_rewards)]
+	pub(crate) type UnclaimedRewards<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The fractions of rewards, scaled by `REWARD_PER_STAKE_SCALE`, which
+	/// are included in the unclaimed rewards but are not owed to anyone.
+	///
+	/// The rewards of the delegators are rounded up when they are added to
+	/// the unclaimed rewards and rounded down when a delegation is settled.
+	/// Once the fractions add up to whole units, these are released from
+	/// the unclaimed rewards at the end of a session and issued again.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_dust)]
+	pub(crate) type RewardDust<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// The cumulative reward per staked unit of the delegators of a collator
+	/// candidate, scaled by `REWARD_PER_STAKE_SCALE`.
+	///
+	/// It is increased at the end of each session in which the candidate
+	/// authored blocks. The rewards of a delegation are settled from the
+	/// increase since it was last settled.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_stake)]
+	pub(crate) type RewardPerStake<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u128, ValueQuery>;
+
+	/// The increase of the reward per staked unit of the delegators of a
+	/// collator candidate in the most recent sessions.
+	///
+	/// It maps from a collator candidate and a slot to the increase in a
+	/// session. Like the ones of [RewardHistory], the slots form a ring buffer
+	/// of `MaxRewardHistory` sessions. The rewards of a delegation are
+	/// recorded per session from it when the delegation is settled.
+	#[pallet::storage]
+	pub(crate) type RewardPerStakeHistory<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		SessionRewardPerStake,
+		OptionQuery,
+	>;
+
+	/// The reward per staked unit of a collator candidate at which the
+	/// rewards of a delegation were last settled.
+	///
+	/// It maps from a delegator and a collator candidate to the scaled
+	/// reward per staked unit.
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_reward_per_stake)]
+	pub(crate) type DelegationRewardPerStake<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		u128,
+		ValueQuery,
+	>;
+
+	/// The rewards an account received in the most recent sessions.
+	///
+	/// It maps from an account and a slot to the rewards of a session, one
+	/// per collator. The slots form a ring buffer of `MaxRewardHistory`
+	/// sessions, the rewards of a session are recorded into slot
+	/// `session % MaxRewardHistory` and overwrite the ones of the session
+	/// recorded there before.
+	#[pallet::storage]
+	pub(crate) type RewardHistory<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		BoundedVec<RewardRecordOf<T>, T::MaxCollatorsPerDelegator>,
+		ValueQuery,
+	>;
+
+	/// The most recent session recorded in the reward history of an
+	/// account, i.e. the head of its ring buffer.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_history_head)]
+	pub(crate) type RewardHistoryHead<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
 	/// The staking information for a candidate.
 	///
 	/// It maps from an account to its information.
@@ -643,6 +877,71 @@ pub mod pallet {
 	#[pallet::getter(fn new_round_forced)]
 	pub(crate) type ForceNewRound<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	/// The round in which a collator candidate changed their commission for
+	/// the last time.
+	///
+	/// It maps from an account to the round index.
+	#[pallet::storage]
+	#[pallet::getter(fn last_commission_change)]
+	pub(crate) type LastCommissionChange<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	/// The commission of the collators of a session, as it was when the
+	/// session started. The rewards of the session are paid out with it.
+	///
+	/// It maps from a session index and an account to the commission. The
+	/// entries are removed when the session ends.
+	#[pallet::storage]
+	#[pallet::getter(fn session_commission)]
+	pub(crate) type SessionCommission<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Twox64Concat,
+		T::AccountId,
+		Perbill,
+		OptionQuery,
+	>;
+
+	/// The collators which were selected for a session.
+	///
+	/// It maps from a session index to the collators which were assigned to
+	/// author blocks in that session. The entry is removed when the session
+	/// ends.
+	#[pallet::storage]
+	#[pallet::getter(fn session_collators)]
+	pub(crate) type SessionCollators<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		BoundedVec<T::AccountId, T::MaxTopCandidates>,
+		ValueQuery,
+	>;
+
+	/// Slashes which have been reported but not yet applied.
+	///
+	/// It maps from the session at the end of which the slashes are applied
+	/// to the slashes.
+	#[pallet::storage]
+	#[pallet::getter(fn unapplied_slashes)]
+	pub(crate) type UnappliedSlashes<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		BoundedVec<UnappliedSlashOf<T>, T::MaxTopCandidates>,
+		ValueQuery,
+	>;
+
+	/// Collator candidates which are excluded from the selection of
+	/// collators because of an offence.
+	///
+	/// It maps from an account to the first session in which it can be
+	/// selected again.
+	#[pallet::storage]
+	#[pallet::getter(fn deselected_until)]
+	pub(crate) type DeselectedCandidates<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub stakers: GenesisStaker<T>,
@@ -1475,6 +1774,12 @@ pub mod pallet {
 			CandidatePool::<T>::insert(&collator, state);
 			DelegatorState::<T>::insert(&acc, delegator_state);
 			<LastDelegation<T>>::insert(&acc, delegation_counter);
+			// the delegation only earns rewards from now on
+			DelegationRewardPerStake::<T>::insert(
+				&acc,
+				&collator,
+				RewardPerStake::<T>::get(&collator),
+			);
 
 			// update or clear storage of potentially kicked delegator
 			Self::update_kicked_delegator_storage(maybe_kicked_delegator);
@@ -1623,6 +1928,12 @@ pub mod pallet {
 			CandidatePool::<T>::insert(&collator, state);
 			DelegatorState::<T>::insert(&acc, delegator);
 			<LastDelegation<T>>::insert(&acc, delegation_counter);
+			// the delegation only earns rewards from now on
+			DelegationRewardPerStake::<T>::insert(
+				&acc,
+				&collator,
+				RewardPerStake::<T>::get(&collator),
+			);
 
 			// update or clear storage of potentially kicked delegator
 			Self::update_kicked_delegator_storage(maybe_kicked_delegator);
@@ -1731,13 +2042,16 @@ pub mod pallet {
 		/// If not in the set of candidates, staking enough funds allows the
 		/// collator candidate to be added to it.
 		///
+		/// The rewards of the delegator are settled before the stake changes.
+		///
 		/// Emits `DelegatorStakedMore`.
 		///
 		/// # <weight>
 		/// Weight: O(N) + O(D) where N is `MaxSelectedCandidates` bounded
 		/// by `MaxTopCandidates` and D the number of total delegators for
 		/// this collator bounded by `MaxCollatorsPerDelegator`.
-		/// bounded by `MaxUnstakeRequests`.
+		/// bounded by `MaxUnstakeRequests`, plus settling the rewards of the
+		/// delegator.
 		/// - Reads: [Origin Account], DelegatorState, BlockNumber, Unstaking, Locks, TopCandidates,
 		///   CandidatePool, MaxSelectedCandidates
 		/// - Writes: Unstaking, Locks, DelegatorState, CandidatePool, TotalCollatorStake
@@ -1747,6 +2061,7 @@ pub mod pallet {
 			T::MaxTopCandidates::get(),
 			T::MaxDelegatorsPerCollator::get(),
 			T::MaxUnstakeRequests::get().saturated_into::<u32>())
+			.saturating_add(Pallet::<T>::max_settle_weight())
 		)]
 		pub fn delegator_stake_more(
 			origin: OriginFor<T>,
@@ -1757,6 +2072,7 @@ pub mod pallet {
 			ensure!(!more.is_zero(), Error::<T>::ValStakeZero);
 
 			let candidate = T::Lookup::lookup(candidate)?;
+			Self::settle_delegator(&delegator);
 			let mut delegations =
 				DelegatorState::<T>::get(&delegator).ok_or(Error::<T>::DelegatorNotFound)?;
 			let mut collator =
@@ -1798,11 +2114,14 @@ pub mod pallet {
 				before_total,
 				after,
 			));
-			Ok(Some(<T as crate::pallet::Config>::WeightInfo::delegator_stake_more(
-				n,
-				T::MaxDelegatorsPerCollator::get(),
-				unstaking_len,
-			))
+			Ok(Some(
+				<T as crate::pallet::Config>::WeightInfo::delegator_stake_more(
+					n,
+					T::MaxDelegatorsPerCollator::get(),
+					unstaking_len,
+				)
+				.saturating_add(Self::max_settle_weight()),
+			)
 			.into())
 		}
 
@@ -1821,10 +2140,12 @@ pub mod pallet {
 		/// The resulting total amount of funds staked must be within the
 		/// allowed range as set in the pallet's configuration.
 		///
+		/// The rewards of the delegator are settled before the stake changes.
+		///
 		/// Emits `DelegatorStakedLess`.
 		///
 		/// # <weight>
-		/// Weight: O(1)
+		/// Weight: O(1), plus settling the rewards of the delegator.
 		/// - Reads: [Origin Account], DelegatorState, BlockNumber, Unstaking, TopCandidates,
 		///   CandidatePool, MaxSelectedCandidates
 		/// - Writes: Unstaking, DelegatorState, CandidatePool, TotalCollatorStake
@@ -1833,7 +2154,8 @@ pub mod pallet {
 		#[pallet::weight(<T as crate::pallet::Config>::WeightInfo::delegator_stake_less(
 			T::MaxTopCandidates::get(),
 			T::MaxDelegatorsPerCollator::get()
-		))]
+		)
+		.saturating_add(Pallet::<T>::max_settle_weight()))]
 		pub fn delegator_stake_less(
 			origin: OriginFor<T>,
 			candidate: <T::Lookup as StaticLookup>::Source,
@@ -1843,6 +2165,7 @@ pub mod pallet {
 			ensure!(!less.is_zero(), Error::<T>::ValStakeZero);
 
 			let candidate = T::Lookup::lookup(candidate)?;
+			Self::settle_delegator(&delegator);
 			let mut delegations =
 				DelegatorState::<T>::get(&delegator).ok_or(Error::<T>::DelegatorNotFound)?;
 			let mut collator =
@@ -1886,10 +2209,13 @@ pub mod pallet {
 				before_total,
 				after,
 			));
-			Ok(Some(<T as crate::pallet::Config>::WeightInfo::delegator_stake_less(
-				n,
-				T::MaxDelegatorsPerCollator::get(),
-			))
+			Ok(Some(
+				<T as crate::pallet::Config>::WeightInfo::delegator_stake_less(
+					n,
+					T::MaxDelegatorsPerCollator::get(),
+				)
+				.saturating_add(Self::max_settle_weight()),
+			)
 			.into())
 		}
 
@@ -1919,6 +2245,198 @@ pub mod pallet {
 			Ok(Some(<T as crate::pallet::Config>::WeightInfo::unlock_unstaked(unstaking_len))
 				.into())
 		}
+
+		/// Set the commission a collator candidate takes off the rewards of
+		/// their delegators.
+		///
+		/// The new commission must not exceed `MaxCollatorCommission` and
+		/// can be changed at most once per round. It is applied from the next
+		/// round on, the rewards of a round being paid out with the commission
+		/// it started with.
+		///
+		/// Emits `CollatorCommissionChanged`.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// - Reads: [Origin Account], CandidatePool, LastCommissionChange, Round
+		/// - Writes: CandidatePool, LastCommissionChange
+		/// # </weight>
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as crate::pallet::Config>::WeightInfo::set_commission())]
+		pub fn set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResult {
+			let collator = ensure_signed(origin)?;
+			ensure!(commission <= T::MaxCollatorCommission::get(), Error::<T>::CommissionAboveMax);
+
+			let mut state =
+				CandidatePool::<T>::get(&collator).ok_or(Error::<T>::CandidateNotFound)?;
+			let now = <Round<T>>::get().current;
+			ensure!(
+				<LastCommissionChange<T>>::get(&collator).map_or(true, |round| round < now),
+				Error::<T>::CommissionAlreadyChanged
+			);
+
+			// *** No Fail beyond this point ***
+
+			let old = state.commission;
+			state.set_commission(commission);
+			CandidatePool::<T>::insert(&collator, state);
+			<LastCommissionChange<T>>::insert(&collator, now);
+
+			Self::deposit_event(Event::CollatorCommissionChanged(collator, old, commission));
+			Ok(())
+		}
+
+		/// Set the share of the rewards of a delegation which is automatically
+		/// restaked into the same collator candidate.
+		///
+		/// The restaked amount is capped such that the stake of the
+		/// delegation does not exceed `MaxCollatorCandidateStake`. Any part
+		/// of the reward which is not restaked can be claimed.
+		///
+		/// Setting the percentage to zero disables auto-compounding. The
+		/// rewards earned before the change are settled with the previous
+		/// percentage.
+		///
+		/// Emits `AutoCompoundSet`.
+		///
+		/// # <weight>
+		/// Weight: O(C) where C is the number of delegations for this delegator
+		/// which is bounded by `MaxCollatorsPerDelegator`, plus settling the
+		/// rewards of the delegator.
+		/// - Reads: [Origin Account], DelegatorState
+		/// - Writes: AutoCompound
+		/// # </weight>
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T as crate::pallet::Config>::WeightInfo::set_auto_compound()
+			.saturating_add(Pallet::<T>::max_settle_weight()))]
+		pub fn set_auto_compound(
+			origin: OriginFor<T>,
+			collator: <T::Lookup as StaticLookup>::Source,
+			percentage: Percent,
+		) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let collator = T::Lookup::lookup(collator)?;
+			let state =
+				DelegatorState::<T>::get(&delegator).ok_or(Error::<T>::DelegatorNotFound)?;
+			ensure!(
+				state.delegations.linear_search(&Stake::from(collator.clone())).is_ok(),
+				Error::<T>::DelegationNotFound
+			);
+
+			// *** No Fail beyond this point ***
+
+			Self::settle_delegator(&delegator);
+			if percentage.is_zero() {
+				<AutoCompound<T>>::remove(&delegator, &collator);
+			} else {
+				<AutoCompound<T>>::insert(&delegator, &collator, percentage);
+			}
+
+			Self::deposit_event(Event::AutoCompoundSet(delegator, collator, percentage));
+			Ok(())
+		}
+
+		/// Cancel deferred slashes of offline collators before they are
+		/// applied. The collators are allowed to be selected again.
+		///
+		/// The dispatch origin must be `SlashCancelOrigin`.
+		///
+		/// Emits `SlashCancelled` for each cancelled slash.
+		///
+		/// # <weight>
+		/// Weight: O(S) where S is the number of unapplied slashes in the
+		/// session which is bounded by `MaxTopCandidates`.
+		/// - Reads: UnappliedSlashes
+		/// - Writes: UnappliedSlashes, S * DeselectedCandidates
+		/// # </weight>
+		#[pallet::call_index(21)]
+		#[pallet::weight(<T as crate::pallet::Config>::WeightInfo::cancel_deferred_slash(
+			T::MaxTopCandidates::get()
+		))]
+		pub fn cancel_deferred_slash(
+			origin: OriginFor<T>,
+			session: SessionIndex,
+			slash_indices: Vec<u32>,
+		) -> DispatchResultWithPostInfo {
+			T::SlashCancelOrigin::ensure_origin(origin)?;
+			ensure!(!slash_indices.is_empty(), Error::<T>::EmptySlashIndices);
+			ensure!(
+				slash_indices.windows(2).all(|pair| pair[0] < pair[1]),
+				Error::<T>::NotSortedAndUnique
+			);
+
+			let mut unapplied = UnappliedSlashes::<T>::get(session);
+			let num_slashes = unapplied.len().saturated_into::<u32>();
+			ensure!(
+				slash_indices.last().map_or(false, |index| *index < num_slashes),
+				Error::<T>::InvalidSlashIndex
+			);
+
+			// *** No Fail beyond this point ***
+
+			for index in slash_indices.into_iter().rev() {
+				let slash = unapplied.remove(index.saturated_into::<usize>());
+				DeselectedCandidates::<T>::remove(&slash.collator);
+				Self::deposit_event(Event::SlashCancelled(session, slash.collator));
+			}
+			if unapplied.is_empty() {
+				UnappliedSlashes::<T>::remove(session);
+			} else {
+				UnappliedSlashes::<T>::insert(session, unapplied);
+			}
+
+			Ok(Some(<T as crate::pallet::Config>::WeightInfo::cancel_deferred_slash(num_slashes))
+				.into())
+		}
+
+		/// Claim the staking rewards which have been credited to the origin
+		/// as collator and delegator.
+		///
+		/// The rewards of the origin's delegations are settled first and
+		/// their auto-compound share is restaked. The remaining rewards are
+		/// paid out from the staking pot.
+		///
+		/// Emits `Rewarded` if any rewards are paid out.
+		/// Emits `Compounded` for each restaked reward.
+		///
+		/// # <weight>
+		/// Weight: O(C * (N + D)) where C is the number of delegations of the
+		/// origin bounded by `MaxCollatorsPerDelegator`, N is
+		/// `MaxSelectedCandidates` bounded by `MaxTopCandidates` and D is the
+		/// number of delegators of a candidate bounded by
+		/// `MaxDelegatorsPerCollator`.
+		/// - Reads: Rewards, UnclaimedRewards, 2 * Balance, DelegatorState, CurrentIndex, C *
+		///   (RewardPerStake, DelegationRewardPerStake, RewardHistoryHead, RewardHistory,
+		///   AutoCompound, CandidatePool, MaxCollatorCandidateStake, Unstaking, Locks,
+		///   TopCandidates, MaxSelectedCandidates)
+		/// - Writes: Rewards, UnclaimedRewards, 2 * Balance, C * (DelegationRewardPerStake,
+		///   RewardHistoryHead, RewardHistory, DelegatorState, CandidatePool, Unstaking, Locks,
+		///   TopCandidates, TotalCollatorStake)
+		/// # </weight>
+		#[pallet::call_index(22)]
+		#[pallet::weight(Pallet::<T>::max_settle_weight())]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let settled = Self::settle_delegator(&who);
+			let rewards = <Rewards<T>>::get(&who);
+			// the settled rewards might have been restaked entirely
+			if rewards.is_zero() {
+				ensure!(!settled.is_zero(), Error::<T>::RewardsNotFound);
+				return Ok(())
+			}
+
+			T::Currency::transfer(&Self::account_id(), &who, rewards, KeepAlive)?;
+
+			// *** No Fail beyond this point ***
+
+			<Rewards<T>>::remove(&who);
+			<UnclaimedRewards<T>>::mutate(|unclaimed| {
+				*unclaimed = unclaimed.saturating_sub(rewards)
+			});
+
+			Self::deposit_event(Event::Rewarded(who, rewards));
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -2207,6 +2725,7 @@ pub mod pallet {
 
 			// we don't unlock immediately
 			Self::prep_unstake(&delegator, delegator_stake, false)?;
+			Self::settle_removed_delegation(&delegator, &collator, delegator_stake);
 
 			// update top candidates and total amount at stake
 			if state.is_active() {
@@ -2247,6 +2766,7 @@ pub mod pallet {
 
 			// we don't unlock immediately
 			Self::prep_unstake(&delegation.owner, delegation.amount, true)?;
+			Self::settle_removed_delegation(&delegation.owner, collator, delegation.amount);
 
 			// return state if not empty for later removal after all checks have passed
 			if state.delegations.is_empty() {
@@ -2274,11 +2794,13 @@ pub mod pallet {
 		/// prioritize collators over candidates.
 		///
 		/// # <weight>
-		/// Weight: O(1)
-		/// - Reads: TopCandidates, MaxSelectedCandidates
+		/// Weight: O(N) where N is the number of top candidates bounded by
+		/// `MaxTopCandidates`
+		/// - Reads: TopCandidates, MaxSelectedCandidates, CurrentIndex, N * DeselectedCandidates
 		/// # </weight>
 		pub fn selected_candidates() -> BoundedVec<T::AccountId, T::MaxTopCandidates> {
 			let candidates = TopCandidates::<T>::get();
+			let session = pallet_session::Pallet::<T>::current_index();
 
 			// Should never fail since WASM usize are 32bits and native are either 32 or 64
 			let top_n = MaxSelectedCandidates::<T>::get().saturated_into::<usize>();
@@ -2288,6 +2810,7 @@ pub mod pallet {
 			// Choose the top MaxSelectedCandidates qualified candidates
 			let collators = candidates
 				.into_iter()
+				.filter(|x| !Self::is_deselected(&x.owner, session))
 				.take(top_n)
 				.filter(|x| x.amount >= T::MinCollatorStake::get())
 				.map(|x| x.owner)
@@ -2296,6 +2819,12 @@ pub mod pallet {
 			collators.try_into().expect("Did not extend Collators q.e.d.")
 		}
 
+		/// Check whether a candidate is excluded from the selection of
+		/// collators in the given session because of an offence.
+		fn is_deselected(acc: &T::AccountId, session: SessionIndex) -> bool {
+			DeselectedCandidates::<T>::get(acc).map_or(false, |until| session < until)
+		}
+
 		/// Attempts to add the stake to the set of delegators of a collator
 		/// which already reached its maximum size by removing an already
 		/// existing delegator with less staked value. If the given staked
@@ -2470,16 +2999,19 @@ pub mod pallet {
 
 		/// Clear the CandidatePool of the candidate and remove all delegations
 		/// to the candidate. Moreover, prepare unstaking for the candidate and
-		/// their former delegations.
+		/// their former delegations, and settle the pending rewards of the
+		/// latter.
 		///
 		/// # <weight>
-		/// Weight: O(D + U) where D is the number of delegators of the collator
-		/// candidate bounded by `MaxDelegatorsPerCollator` and U is the
-		/// number of locked unstaking requests bounded by `MaxUnstakeRequests`.
-		/// - Reads: BlockNumber, D * DelegatorState, D * Unstaking
-		/// - Writes: D * DelegatorState, (D + 1) * Unstaking
-		/// - Kills: CandidatePool, DelegatorState for all delegators which only delegated to the
-		///   candidate
+		/// Weight: O(D * (U + H * C)) where D is the number of delegators of
+		/// the collator candidate bounded by `MaxDelegatorsPerCollator`, U is
+		/// the number of locked unstaking requests bounded by
+		/// `MaxUnstakeRequests`, H is `MaxRewardHistory` and C is
+		/// `MaxCollatorsPerDelegator`.
+		/// - Reads: BlockNumber, D * DelegatorState, D * Unstaking, D * settle_removed_delegation
+		/// - Writes: D * DelegatorState, (D + 1) * Unstaking, D * settle_removed_delegation
+		/// - Kills: CandidatePool, RewardPerStake, H * RewardPerStakeHistory, DelegatorState for
+		///   all delegators which only delegated to the candidate
 		/// # </weight>
 		fn remove_candidate(
 			collator: &T::AccountId,
@@ -2489,6 +3021,7 @@ pub mod pallet {
 			for stake in &state.delegators[..] {
 				// prepare unstaking of delegator
 				Self::prep_unstake(&stake.owner, stake.amount, true)?;
+				Self::settle_removed_delegation(&stake.owner, collator, stake.amount);
 				// remove delegation from delegator state
 				if let Some(mut delegator) = DelegatorState::<T>::get(&stake.owner) {
 					if let Some(remaining) = delegator.rm_delegation(collator) {
@@ -2523,6 +3056,13 @@ pub mod pallet {
 				.map(pallet_session::Pallet::<T>::disable_index);
 
 			CandidatePool::<T>::remove(collator);
+			<LastCommissionChange<T>>::remove(collator);
+			RewardPerStake::<T>::remove(collator);
+			let _ = RewardPerStakeHistory::<T>::clear_prefix(
+				collator,
+				T::MaxRewardHistory::get(),
+				None,
+			);
 			Ok(())
 		}
 
@@ -2579,54 +3119,462 @@ pub mod pallet {
 			Ok(unstaking_len)
 		}
 
-		/// Process the coinbase rewards for the production of a new block.
+		/// Credit the rewards for the production of blocks by `collator` in
+		/// the ended session to the claimable rewards of an account and record
+		/// them in the account's reward history.
 		///
 		/// # <weight>
-		/// Weight: O(1)
-		/// - Reads: Balance
-		/// - Writes: Balance
+		/// Weight: O(C) where C is `MaxCollatorsPerDelegator`.
+		/// - Reads: Rewards, RewardHistoryHead, RewardHistory
+		/// - Writes: Rewards, RewardHistoryHead, RewardHistory
 		/// # </weight>
-		fn do_reward(pot: &T::AccountId, who: &T::AccountId, reward: BalanceOf<T>) {
-			if let Ok(_success) = T::Currency::transfer(pot, who, reward, KeepAlive) {
-				Self::deposit_event(Event::Rewarded(who.clone(), reward));
+		fn do_reward(
+			who: &T::AccountId,
+			collator: &T::AccountId,
+			reward: BalanceOf<T>,
+			session: SessionIndex,
+		) {
+			if reward.is_zero() {
+				return
 			}
+			<Rewards<T>>::mutate(who, |rewards| *rewards = rewards.saturating_add(reward));
+			Self::record_reward(who, collator, reward, session);
 		}
 
-		/// Checks whether a delegator can still delegate in this round, e.g.,
-		/// if they have not delegated MaxDelegationsPerRound many times
-		/// already in this round.
+		/// Record a reward in the reward history of an account.
+		///
+		/// Rewards of the same session and collator are added up. Rewards of
+		/// sessions which are older than the ones kept in the history are
+		/// not recorded.
 		///
 		/// # <weight>
-		/// Weight: O(1)
-		/// - Reads: LastDelegation, Round
+		/// Weight: O(C) where C is `MaxCollatorsPerDelegator`.
+		/// - Reads: RewardHistoryHead, RewardHistory
+		/// - Writes: RewardHistoryHead, RewardHistory
 		/// # </weight>
-		fn get_delegation_counter(
-			delegator: &T::AccountId,
-		) -> Result<DelegationCounter, DispatchError> {
-			let last_delegation = <LastDelegation<T>>::get(delegator);
-			let round = <Round<T>>::get();
-
-			let counter =
-				if last_delegation.round < round.current { 0u32 } else { last_delegation.counter };
-
-			ensure!(
-				T::MaxDelegationsPerRound::get() > counter,
-				Error::<T>::DelegationsPerRoundExceeded
-			);
+		fn record_reward(
+			who: &T::AccountId,
+			collator: &T::AccountId,
+			reward: BalanceOf<T>,
+			session: SessionIndex,
+		) {
+			let len = T::MaxRewardHistory::get();
+			if len.is_zero() {
+				return
+			}
+			let head = <RewardHistoryHead<T>>::get(who).unwrap_or_default();
+			if session.saturating_add(len) <= head {
+				return
+			}
 
-			Ok(DelegationCounter { round: round.current, counter: counter.saturating_add(1) })
+			<RewardHistory<T>>::mutate(who, session % len, |records| {
+				// the slot is reused for the session once it held the one `len` sessions
+				// before
+				if records.first().map_or(false, |record| record.session != session) {
+					records.clear();
+				}
+				if let Some(record) = records.iter_mut().find(|record| &record.collator == collator)
+				{
+					record.amount = record.amount.saturating_add(reward);
+				} else if let Err(record) = records.try_push(RewardRecord {
+					session,
+					collator: collator.clone(),
+					amount: reward,
+				}) {
+					// an account earns the rewards of a session from at most as many collators
+					// as it can delegate to at once, the rewards of any other are merged into
+					// the last record rather than dropped
+					if let Some(last) = records.last_mut() {
+						last.amount = last.amount.saturating_add(record.amount);
+					}
+				}
+			});
+			<RewardHistoryHead<T>>::insert(who, head.max(session));
 		}
 
-		// [Post-launch TODO] Think about Collator stake or total stake?
-		// /// Attempts to add a collator candidate to the set of collator
-		// /// candidates which already reached its maximum size. On success,
-		// /// another collator with the minimum total stake is removed from the
-		// /// set. On failure, an error is returned. removing an already existing
-		// fn check_collator_candidate_inclusion(
-		// 	stake: Stake<T::AccountId, BalanceOf<T>>,
-		// 	mut candidates: OrderedSet<Stake<T::AccountId, BalanceOf<T>>,
-		// T::MaxTopCandidates>, ) -> Result<(), DispatchError> {
-		// 	todo!()
+		/// Restake the auto-compound share of a delegator's settled reward
+		/// into the delegated collator candidate.
+		///
+		/// The restaked amount is transferred from the staking pot and
+		/// deducted from the claimable and the unclaimed rewards. It is capped
+		/// such that the stake of the delegation does not exceed
+		/// `MaxCollatorCandidateStake`. Whatever cannot be restaked remains
+		/// claimable by the delegator.
+		///
+		/// Returns the restaked amount.
+		///
+		/// # <weight>
+		/// Weight: O(N + D) where N is `MaxSelectedCandidates` bounded by
+		/// `MaxTopCandidates` and D is the number of delegators for this
+		/// candidate bounded by `MaxDelegatorsPerCollator`.
+		/// - Reads: AutoCompound, DelegatorState, CandidatePool, MaxCollatorCandidateStake,
+		///   Balance, Rewards, UnclaimedRewards, Unstaking, Locks, TopCandidates,
+		///   MaxSelectedCandidates
+		/// - Writes: DelegatorState, CandidatePool, Balance, Rewards, UnclaimedRewards, Unstaking,
+		///   Locks, TopCandidates, TotalCollatorStake
+		/// # </weight>
+		fn do_compound(
+			pot: &T::AccountId,
+			delegator: &T::AccountId,
+			collator: &T::AccountId,
+			reward: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let percentage = <AutoCompound<T>>::get(delegator, collator);
+			if percentage.is_zero() {
+				return Zero::zero()
+			}
+			let (Some(mut delegations), Some(mut state)) =
+				(DelegatorState::<T>::get(delegator), CandidatePool::<T>::get(collator))
+			else {
+				return Zero::zero()
+			};
+			if state.is_leaving() {
+				return Zero::zero()
+			}
+
+			let Some(staked) = (&delegations.delegations)
+				.into_iter()
+				.find(|stake| stake.owner == *collator)
+				.map(|stake| stake.amount)
+			else {
+				return Zero::zero()
+			};
+			let headroom = MaxCollatorCandidateStake::<T>::get().saturating_sub(staked);
+			let amount = (percentage * reward).min(headroom);
+			if amount.is_zero() {
+				return Zero::zero()
+			}
+			let Some(delegation) = delegations.inc_delegation(collator.clone(), amount) else {
+				return Zero::zero()
+			};
+
+			// the restaked amount leaves the pot and is locked right away
+			if T::Currency::transfer(pot, delegator, amount, KeepAlive).is_err() {
+				return Zero::zero()
+			}
+			<Rewards<T>>::mutate(delegator, |rewards| *rewards = rewards.saturating_sub(amount));
+			<UnclaimedRewards<T>>::mutate(|unclaimed| {
+				*unclaimed = unclaimed.saturating_sub(amount)
+			});
+			if Self::increase_lock(delegator, delegations.total, amount).is_err() {
+				return amount
+			}
+
+			let CandidateOf::<T, _> { stake: before_stake, total: before_total, .. } = state;
+			state.inc_delegator(delegator.clone(), amount);
+			if state.is_active() {
+				Self::update_top_candidates(
+					collator.clone(),
+					before_stake,
+					// safe because total >= stake
+					before_total - before_stake,
+					state.stake,
+					state.total - state.stake,
+				);
+			}
+			CandidatePool::<T>::insert(collator, state);
+			DelegatorState::<T>::insert(delegator, delegations);
+
+			Self::deposit_event(Event::Compounded(
+				delegator.clone(),
+				collator.clone(),
+				amount,
+				delegation,
+			));
+			amount
+		}
+
+		/// Credit the rewards a delegation has earned since it was last
+		/// settled to the claimable rewards of the delegator and return them.
+		///
+		/// The rewards are recorded in the reward history of the delegator
+		/// per session in which they were earned.
+		///
+		/// The fraction of a unit by which the rewards are rounded down is
+		/// added to the reward dust.
+		///
+		/// # <weight>
+		/// Weight: O(H * C) where H is `MaxRewardHistory` and C is
+		/// `MaxCollatorsPerDelegator`.
+		/// - Reads: RewardPerStake, DelegationRewardPerStake, RewardDust, Rewards,
+		///   record_delegation_rewards
+		/// - Writes: DelegationRewardPerStake, RewardDust, Rewards, record_delegation_rewards
+		/// # </weight>
+		fn settle_delegation(
+			delegator: &T::AccountId,
+			collator: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let reward_per_stake = RewardPerStake::<T>::get(collator);
+			let settled = DelegationRewardPerStake::<T>::mutate(delegator, collator, |settled| {
+				sp_std::mem::replace(settled, reward_per_stake)
+			});
+			let increase = reward_per_stake.saturating_sub(settled);
+			let reward = Self::reward_of_stake(amount, increase);
+			let remainder = Self::reward_remainder_of_stake(amount, increase);
+			if !remainder.is_zero() {
+				RewardDust::<T>::mutate(|dust| *dust = dust.saturating_add(remainder));
+			}
+
+			if !reward.is_zero() {
+				<Rewards<T>>::mutate(delegator, |rewards| {
+					*rewards = rewards.saturating_add(reward)
+				});
+				Self::record_delegation_rewards(delegator, collator, amount, settled);
+			}
+			reward
+		}
+
+		/// Record the rewards a delegation has earned since it was settled at
+		/// the reward per staked unit `settled` in the reward history of the
+		/// delegator, per session in which they were earned.
+		///
+		/// The rewards of each session are the ones up to its end less the
+		/// ones up to its start, such that they add up to the settled
+		/// rewards. Rewards of sessions which are older than the ones kept in
+		/// the history are not recorded.
+		///
+		/// # <weight>
+		/// Weight: O(H * C) where H is `MaxRewardHistory` and C is
+		/// `MaxCollatorsPerDelegator`.
+		/// - Reads: CurrentIndex, H * (RewardPerStakeHistory, RewardHistoryHead, RewardHistory)
+		/// - Writes: H * (RewardHistoryHead, RewardHistory)
+		/// # </weight>
+		fn record_delegation_rewards(
+			delegator: &T::AccountId,
+			collator: &T::AccountId,
+			amount: BalanceOf<T>,
+			settled: u128,
+		) {
+			let len = T::MaxRewardHistory::get();
+			let last = pallet_session::Pallet::<T>::current_index().saturating_sub(1);
+			let first = last.saturating_add(1).saturating_sub(len);
+
+			// the range is empty if no history is kept
+			for session in first..=last {
+				let Some(increase) = RewardPerStakeHistory::<T>::get(collator, session % len)
+					.filter(|increase| {
+						increase.session == session && increase.reward_per_stake > settled
+					})
+				else {
+					continue
+				};
+				let start =
+					increase.reward_per_stake.saturating_sub(increase.increase).max(settled);
+				let reward = Self::reward_of_stake(amount, increase.reward_per_stake - settled)
+					.saturating_sub(Self::reward_of_stake(amount, start - settled));
+				if !reward.is_zero() {
+					Self::record_reward(delegator, collator, reward, session);
+				}
+			}
+		}
+
+		/// Settle the rewards of all delegations of a delegator and restake
+		/// their auto-compound share.
+		///
+		/// Returns the settled rewards, including the restaked ones.
+		///
+		/// # <weight>
+		/// Weight: O(C * (N + D)) where C is the number of delegations of the
+		/// delegator bounded by `MaxCollatorsPerDelegator`, N is
+		/// `MaxSelectedCandidates` bounded by `MaxTopCandidates` and D is the
+		/// number of delegators of a candidate bounded by
+		/// `MaxDelegatorsPerCollator`.
+		/// - Reads: DelegatorState, C * settle_delegation, C * do_compound
+		/// - Writes: C * settle_delegation, C * do_compound
+		/// # </weight>
+		fn settle_delegator(delegator: &T::AccountId) -> BalanceOf<T> {
+			let Some(state) = DelegatorState::<T>::get(delegator) else { return Zero::zero() };
+			let pot = Self::account_id();
+
+			state.delegations.into_iter().fold(BalanceOf::<T>::zero(), |settled, stake| {
+				let reward = Self::settle_delegation(delegator, &stake.owner, stake.amount);
+				if !reward.is_zero() {
+					Self::do_compound(&pot, delegator, &stake.owner, reward);
+				}
+				settled.saturating_add(reward)
+			})
+		}
+
+		/// Settle the rewards of a delegation which is removed without
+		/// restaking them and clear its reward state.
+		///
+		/// # <weight>
+		/// Weight: O(H * C) where H is `MaxRewardHistory` and C is
+		/// `MaxCollatorsPerDelegator`.
+		/// - Reads: settle_delegation
+		/// - Writes: settle_delegation
+		/// - Kills: DelegationRewardPerStake, AutoCompound
+		/// # </weight>
+		fn settle_removed_delegation(
+			delegator: &T::AccountId,
+			collator: &T::AccountId,
+			amount: BalanceOf<T>,
+		) {
+			Self::settle_delegation(delegator, collator, amount);
+			DelegationRewardPerStake::<T>::remove(delegator, collator);
+			<AutoCompound<T>>::remove(delegator, collator);
+		}
+
+		/// Return the rewards of `stake` for an increase of the reward per
+		/// staked unit by `reward_per_stake`.
+		///
+		/// No read/write from DB
+		fn reward_of_stake(stake: BalanceOf<T>, reward_per_stake: u128) -> BalanceOf<T> {
+			multiply_by_rational_with_rounding(
+				stake.saturated_into::<u128>(),
+				reward_per_stake,
+				REWARD_PER_STAKE_SCALE,
+				Rounding::Down,
+			)
+			.unwrap_or_else(Zero::zero)
+			.saturated_into()
+		}
+
+		/// Return the fraction of a unit, scaled by `REWARD_PER_STAKE_SCALE`,
+		/// by which the rewards of `stake` for an increase of the reward per
+		/// staked unit by `reward_per_stake` are rounded down.
+		///
+		/// No read/write from DB
+		fn reward_remainder_of_stake(stake: BalanceOf<T>, reward_per_stake: u128) -> u128 {
+			let product = U256::from(stake.saturated_into::<u128>())
+				.saturating_mul(U256::from(reward_per_stake));
+			(product % U256::from(REWARD_PER_STAKE_SCALE)).low_u128()
+		}
+
+		/// Increase the reward per staked unit of the delegators of a
+		/// collator candidate by their rewards for the blocks it authored in
+		/// the ended session, after the candidate's commission is deducted.
+		/// The increase is kept in the history of the candidate's reward per
+		/// staked unit.
+		///
+		/// Returns the rewards of all delegators of the candidate, rounded up.
+		/// The fraction of a unit they are rounded up by is added to the
+		/// reward dust.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// - Reads: RewardPerStake, RewardDust
+		/// - Writes: RewardPerStake, RewardPerStakeHistory, RewardDust
+		/// # </weight>
+		fn accumulate_delegator_rewards(
+			session: SessionIndex,
+			state: &Candidate<T::AccountId, BalanceOf<T>, T::MaxDelegatorsPerCollator>,
+			block_num: u32,
+			total_staking_in_session: BalanceOf<T>,
+			issue_number: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			// safe because total >= stake
+			let delegated = state.total - state.stake;
+			if delegated.is_zero() || total_staking_in_session.is_zero() {
+				return Zero::zero()
+			}
+
+			// the collator's commission is taken off the delegators' rewards
+			let issue_number = issue_number.saturating_sub(state.commission * issue_number);
+			let reward_per_stake = multiply_by_rational_with_rounding(
+				issue_number.saturated_into::<u128>().saturating_mul(block_num.into()),
+				REWARD_PER_STAKE_SCALE,
+				total_staking_in_session.saturated_into::<u128>(),
+				Rounding::Down,
+			)
+			.unwrap_or_else(Zero::zero);
+			let total = RewardPerStake::<T>::mutate(&state.id, |acc| {
+				*acc = acc.saturating_add(reward_per_stake);
+				*acc
+			});
+			let len = T::MaxRewardHistory::get();
+			if !len.is_zero() {
+				RewardPerStakeHistory::<T>::insert(
+					&state.id,
+					session % len,
+					SessionRewardPerStake {
+						session,
+						increase: reward_per_stake,
+						reward_per_stake: total,
+					},
+				);
+			}
+
+			let rewards = Self::reward_of_stake(delegated, reward_per_stake);
+			let remainder = Self::reward_remainder_of_stake(delegated, reward_per_stake);
+			if remainder.is_zero() {
+				return rewards
+			}
+			// the rounding of each delegation is accounted for when it is settled
+			RewardDust::<T>::mutate(|dust| {
+				*dust = dust.saturating_add(REWARD_PER_STAKE_SCALE - remainder)
+			});
+			rewards.saturating_add(One::one())
+		}
+
+		/// Return the rewards of the delegations of an account which have not
+		/// been settled yet.
+		///
+		/// # <weight>
+		/// Weight: O(C) where C is the number of delegations of the account
+		/// bounded by `MaxCollatorsPerDelegator`.
+		/// - Reads: DelegatorState, C * (RewardPerStake, DelegationRewardPerStake)
+		/// # </weight>
+		fn pending_rewards(delegator: &T::AccountId) -> BalanceOf<T> {
+			let Some(state) = DelegatorState::<T>::get(delegator) else { return Zero::zero() };
+
+			state.delegations.into_iter().fold(BalanceOf::<T>::zero(), |pending, stake| {
+				let reward_per_stake = RewardPerStake::<T>::get(&stake.owner)
+					.saturating_sub(DelegationRewardPerStake::<T>::get(delegator, &stake.owner));
+				pending.saturating_add(Self::reward_of_stake(stake.amount, reward_per_stake))
+			})
+		}
+
+		/// The maximum weight of settling the rewards of a delegator, see
+		/// `settle_delegator`.
+		pub(crate) fn max_settle_weight() -> Weight {
+			// the benchmark records the rewards of a single session, each other session of the
+			// history reads RewardPerStakeHistory, RewardHistoryHead and RewardHistory and
+			// writes the latter two
+			let history = T::DbWeight::get()
+				.reads_writes(3, 2)
+				.saturating_mul(T::MaxRewardHistory::get().saturating_sub(1).into());
+			<T as crate::pallet::Config>::WeightInfo::claim_rewards()
+				.saturating_add(history)
+				.saturating_mul(T::MaxCollatorsPerDelegator::get().into())
+		}
+
+		/// Checks whether a delegator can still delegate in this round, e.g.,
+		/// if they have not delegated MaxDelegationsPerRound many times
+		/// already in this round.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// - Reads: LastDelegation, Round
+		/// # </weight>
+		fn get_delegation_counter(
+			delegator: &T::AccountId,
+		) -> Result<DelegationCounter, DispatchError> {
+			let last_delegation = <LastDelegation<T>>::get(delegator);
+			let round = <Round<T>>::get();
+
+			let counter =
+				if last_delegation.round < round.current { 0u32 } else { last_delegation.counter };
+
+			ensure!(
+				T::MaxDelegationsPerRound::get() > counter,
+				Error::<T>::DelegationsPerRoundExceeded
+			);
+
+			Ok(DelegationCounter { round: round.current, counter: counter.saturating_add(1) })
+		}
+
+		// [Post-launch TODO] Think about Collator stake or total stake?
+		// /// Attempts to add a collator candidate to the set of collator
+		// /// candidates which already reached its maximum size. On success,
+		// /// another collator with the minimum total stake is removed from the
+		// /// set. On failure, an error is returned. removing an already existing
+		// fn check_collator_candidate_inclusion(
+		// 	stake: Stake<T::AccountId, BalanceOf<T>>,
+		// 	mut candidates: OrderedSet<Stake<T::AccountId, BalanceOf<T>>,
+		// T::MaxTopCandidates>, ) -> Result<(), DispatchError> {
+		// 	todo!()
 		// }
 
 		// Public only for testing purpose
@@ -2665,7 +3613,7 @@ pub mod pallet {
 				.checked_mul(&stake.total.checked_sub(&delegator_sum).unwrap_or_else(Zero::zero))
 				.unwrap_or_else(Zero::zero);
 			let percentage = Perquintill::from_rational(nominator, total_staking_in_session);
-			if percentage.is_zero() {
+			let own_reward = if percentage.is_zero() {
 				log::error!(
 					"Error in collator calculation: block_num {:?} stake.total {:?} delegator_sum
 					{:?} total_staking_in_session {:?}",
@@ -2679,10 +3627,24 @@ pub mod pallet {
 					nominator,
 					percentage
 				);
-				Reward { owner: stake.id.clone(), amount: Zero::zero() }
+				Zero::zero()
 			} else {
-				Reward { owner: stake.id.clone(), amount: percentage * issue_number }
-			}
+				percentage * issue_number
+			};
+
+			// the collator additionally receives its commission on every delegator's reward
+			let commission =
+				(&stake.delegators).into_iter().fold(BalanceOf::<T>::zero(), |acc, x| {
+					let reward = Self::get_delegator_gross_reward(
+						x.amount,
+						block_num,
+						total_staking_in_session,
+						issue_number,
+					);
+					acc.saturating_add(stake.commission * reward)
+				});
+
+			Reward { owner: stake.id.clone(), amount: own_reward.saturating_add(commission) }
 		}
 
 		// Public only for testing purpose
@@ -2696,25 +3658,16 @@ pub mod pallet {
 			let inner = (&stake.delegators)
 				.into_iter()
 				.map(|x| {
-					// issue_number = block_num * (delegator.stake) / total_staking_in_session
-					let nominator = T::CurrencyBalance::from(block_num)
-						.checked_mul(&x.amount)
-						.unwrap_or_else(Zero::zero);
-					let percentage =
-						Perquintill::from_rational(nominator, total_staking_in_session);
-					if percentage.is_zero() {
-						log::info!(
-							"Delegator calculation: block_num {:?} amount {:?} total_staking_in_session {:?}",
-							block_num, x.amount, total_staking_in_session
-						);
-						log::info!(
-							"Delegator calculation: nominator {:?} percentage {:?}",
-							nominator,
-							percentage
-						);
-						Reward { owner: x.owner.clone(), amount: Zero::zero() }
-					} else {
-						Reward { owner: x.owner.clone(), amount: percentage * issue_number }
+					let reward = Self::get_delegator_gross_reward(
+						x.amount,
+						block_num,
+						total_staking_in_session,
+						issue_number,
+					);
+					// the collator's commission is taken off the delegator's reward
+					Reward {
+						owner: x.owner.clone(),
+						amount: reward.saturating_sub(stake.commission * reward),
 					}
 				})
 				.collect::<Vec<Reward<T::AccountId, BalanceOf<T>>>>();
@@ -2722,13 +3675,70 @@ pub mod pallet {
 			inner.try_into().expect("Did not extend vec q.e.d.")
 		}
 
-		fn peaq_reward_mechanism_impl() {
+		/// Calculate the reward of a single delegation before the collator's
+		/// commission is deducted.
+		///
+		/// No read/write from DB
+		fn get_delegator_gross_reward(
+			amount: BalanceOf<T>,
+			block_num: u32,
+			total_staking_in_session: BalanceOf<T>,
+			issue_number: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			// issue_number = block_num * (delegator.stake) / total_staking_in_session
+			let nominator = T::CurrencyBalance::from(block_num)
+				.checked_mul(&amount)
+				.unwrap_or_else(Zero::zero);
+			let percentage = Perquintill::from_rational(nominator, total_staking_in_session);
+			if percentage.is_zero() {
+				log::info!(
+					"Delegator calculation: block_num {:?} amount {:?} total_staking_in_session {:?}",
+					block_num,
+					amount,
+					total_staking_in_session
+				);
+				log::info!(
+					"Delegator calculation: nominator {:?} percentage {:?}",
+					nominator,
+					percentage
+				);
+				Zero::zero()
+			} else {
+				percentage * issue_number
+			}
+		}
+
+		/// Credit the rewards of the collators which authored blocks in the
+		/// ended session and add the rewards of their delegators to the
+		/// reward per staked unit of each collator.
+		///
+		/// # <weight>
+		/// Weight: O(N) where N is the number of collators which authored
+		/// blocks bounded by `MaxTopCandidates`.
+		/// - Reads: Balance, RewardDust, UnclaimedRewards, N * (CollatorBlock, CandidatePool,
+		///   SessionCommission, Rewards, RewardHistoryHead, RewardHistory, RewardPerStake,
+		///   RewardDust)
+		/// - Writes: RewardDust, UnclaimedRewards, N * (CollatorBlock, Rewards, RewardHistoryHead,
+		///   RewardHistory, RewardPerStake, RewardPerStakeHistory, RewardDust, SessionCommission)
+		/// # </weight>
+		fn peaq_reward_mechanism_impl(end_index: SessionIndex) {
 			let mut reads = Weight::from_parts(0, 1);
 			let mut writes = Weight::from_parts(0, 1);
 
 			let pot = Self::account_id();
+			// the unclaimed rewards of previous sessions are still owed to their recipients,
+			// except for the whole units of dust their rounding left over
+			let dust = RewardDust::<T>::get();
+			let released: BalanceOf<T> = (dust / REWARD_PER_STAKE_SCALE).saturated_into();
+			if !released.is_zero() {
+				RewardDust::<T>::put(dust % REWARD_PER_STAKE_SCALE);
+				writes = writes.saturating_add(Weight::from_parts(1_u64, 0));
+			}
+			reads = reads.saturating_add(Weight::from_parts(1_u64, 0));
+			let mut unclaimed = <UnclaimedRewards<T>>::get().saturating_sub(released);
 			let issue_number = T::Currency::free_balance(&pot)
 				.checked_sub(&T::Currency::minimum_balance())
+				.and_then(|free| free.checked_sub(&unclaimed))
 				.unwrap_or_else(Zero::zero);
 
 			let (in_reads, total_staking_in_session) = Self::get_total_collator_staking_num();
@@ -2737,7 +3747,11 @@ pub mod pallet {
 			// Here we also remove the all collator block after the iteration
 			CollatorBlock::<T>::iter().drain().for_each(|(collator, block_num)| {
 				// Get the delegator's staking number
-				if let Some(state) = CandidatePool::<T>::get(collator.clone()) {
+				if let Some(mut state) = CandidatePool::<T>::get(collator.clone()) {
+					// the commission the session started with applies to all of its rewards
+					if let Some(commission) = SessionCommission::<T>::get(end_index, &collator) {
+						state.set_commission(commission);
+					}
 					let now_reward = Self::get_collator_reward_per_session(
 						&state,
 						block_num,
@@ -2745,26 +3759,32 @@ pub mod pallet {
 						issue_number,
 					);
 
-					Self::do_reward(&pot, &now_reward.owner, now_reward.amount);
-					reads = reads.saturating_add(Weight::from_parts(1_u64, 0));
-					writes = writes.saturating_add(Weight::from_parts(1_u64, 0));
+					Self::do_reward(&now_reward.owner, &collator, now_reward.amount, end_index);
+					unclaimed = unclaimed.saturating_add(now_reward.amount);
 
-					let now_rewards = Self::get_delgators_reward_per_session(
+					// the rewards of the delegators are settled when they claim them or change
+					// their delegations
+					let delegators_reward = Self::accumulate_delegator_rewards(
+						end_index,
 						&state,
 						block_num,
 						total_staking_in_session,
 						issue_number,
 					);
-
-					let len = now_rewards.len().saturated_into::<u64>();
-					now_rewards.into_iter().for_each(|x| {
-						Self::do_reward(&pot, &x.owner, x.amount);
-					});
-					reads = reads.saturating_add(Weight::from_parts(len, 0));
-					writes = writes.saturating_add(Weight::from_parts(len, 0));
+					unclaimed = unclaimed.saturating_add(delegators_reward);
+					// SessionCommission, Rewards, RewardHistoryHead, RewardHistory,
+					// RewardPerStake, RewardPerStakeHistory and RewardDust
+					reads = reads.saturating_add(Weight::from_parts(6_u64, 0));
+					writes = writes.saturating_add(Weight::from_parts(6_u64, 0));
 				}
 				reads = reads.saturating_add(Weight::from_parts(1_u64, 0));
 			});
+			<UnclaimedRewards<T>>::put(unclaimed);
+			reads = reads.saturating_add(Weight::from_parts(1_u64, 0));
+			writes = writes.saturating_add(Weight::from_parts(1_u64, 0));
+
+			let removed = SessionCommission::<T>::clear_prefix(end_index, u32::MAX, None);
+			writes = writes.saturating_add(Weight::from_parts(removed.unique.into(), 0));
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
 				T::DbWeight::get().reads_writes(reads.ref_time(), writes.ref_time()),
@@ -2772,10 +3792,450 @@ pub mod pallet {
 			);
 		}
 
+		/// Report a slash for each collator which was selected for the ended
+		/// session but did not author any block. The slash is applied after
+		/// `SlashDeferDuration` sessions and the collator is excluded from the
+		/// selection of collators for `OffenceDeselectDuration` sessions.
+		///
+		/// Must be called before [CollatorBlock] is drained.
+		///
+		/// # <weight>
+		/// Weight: O(N * D) where N is the number of collators in the session
+		/// bounded by `MaxTopCandidates` and D is the number of delegators per
+		/// collator bounded by `MaxDelegatorsPerCollator`.
+		/// - Reads: SessionCollators, N * CollatorBlock, N * CandidatePool, DeselectedCandidates,
+		///   UnappliedSlashes
+		/// - Writes: SessionCollators, UnappliedSlashes, N * DeselectedCandidates
+		/// # </weight>
+		fn report_offline_collators(end_index: SessionIndex) -> Weight {
+			let collators = SessionCollators::<T>::take(end_index);
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+
+			let fraction = T::OfflineSlashFraction::get();
+			if fraction.is_zero() {
+				return T::DbWeight::get().reads_writes(reads, writes)
+			}
+
+			// lift expired deselections
+			let expired = DeselectedCandidates::<T>::iter()
+				.filter(|(_, until)| *until <= end_index.saturating_add(1))
+				.map(|(acc, _)| acc)
+				.collect::<Vec<_>>();
+			reads = reads.saturating_add(expired.len().saturated_into::<u64>().saturating_add(1));
+			let mut changed = !expired.is_empty();
+			for acc in expired {
+				DeselectedCandidates::<T>::remove(&acc);
+				writes = writes.saturating_add(1);
+			}
+
+			let apply_at = end_index.saturating_add(T::SlashDeferDuration::get());
+			let deselect_until =
+				end_index.saturating_add(1).saturating_add(T::OffenceDeselectDuration::get());
+			for collator in collators {
+				reads = reads.saturating_add(1);
+				if CollatorBlock::<T>::contains_key(&collator) {
+					continue
+				}
+				reads = reads.saturating_add(1);
+				let Some(state) = CandidatePool::<T>::get(&collator) else { continue };
+
+				let others = if T::SlashDelegators::get() {
+					state
+						.delegators
+						.into_iter()
+						.map(|stake| (stake.owner, fraction * stake.amount))
+						.filter(|(_, amount)| !amount.is_zero())
+						.collect::<Vec<_>>()
+						.try_into()
+						.expect("Did not extend the delegators of the candidate q.e.d.")
+				} else {
+					Default::default()
+				};
+				let slash = UnappliedSlash {
+					collator: collator.clone(),
+					own: fraction * state.stake,
+					others,
+				};
+
+				reads = reads.saturating_add(1);
+				writes = writes.saturating_add(2);
+				if UnappliedSlashes::<T>::try_mutate(apply_at, |slashes| slashes.try_push(slash))
+					.is_err()
+				{
+					log::error!("Could not defer the slash of offline collator {:?}", collator);
+				} else {
+					Self::deposit_event(Event::OffenceReported(
+						collator.clone(),
+						end_index,
+						apply_at,
+					));
+				}
+				DeselectedCandidates::<T>::insert(&collator, deselect_until);
+				Self::deposit_event(Event::CandidateDeselected(collator, deselect_until));
+				changed = true;
+			}
+
+			let mut weight = T::DbWeight::get().reads_writes(reads, writes);
+			if changed {
+				let (reads, writes) = Self::update_total_stake();
+				weight = weight
+					.saturating_add(T::DbWeight::get().reads_writes(reads.into(), writes.into()));
+			}
+			weight
+		}
+
+		/// Apply all deferred slashes which are due at the end of the given
+		/// session.
+		///
+		/// Delegations and collator candidates whose stake falls below the
+		/// minimum because of the slash are removed.
+		///
+		/// # <weight>
+		/// Weight: O(S * D) where S is the number of slashes bounded by
+		/// `MaxTopCandidates` and D is the number of slashed delegators per
+		/// slash bounded by `MaxDelegatorsPerCollator`.
+		/// - Reads: UnappliedSlashes, S * D * (CandidatePool, DelegatorState, Unstaking, Locks,
+		///   TopCandidates)
+		/// - Writes: UnappliedSlashes, S * D * (CandidatePool, DelegatorState, Unstaking, Locks,
+		///   TopCandidates, Balance)
+		/// # </weight>
+		fn apply_slashes(end_index: SessionIndex) -> Weight {
+			let slashes = UnappliedSlashes::<T>::take(end_index);
+			let mut count = 0u64;
+			let mut weight = Weight::zero();
+			for slash in slashes {
+				// delegators first, as removing the collator also removes its delegations
+				for (delegator, amount) in slash.others {
+					Self::do_slash(&delegator, &slash.collator, amount);
+					weight = weight
+						.saturating_add(Self::remove_stake_below_min(&delegator, &slash.collator));
+					count = count.saturating_add(1);
+				}
+				Self::do_slash(&slash.collator, &slash.collator, slash.own);
+				weight = weight
+					.saturating_add(Self::remove_stake_below_min(&slash.collator, &slash.collator));
+				count = count.saturating_add(1);
+			}
+			weight.saturating_add(T::DbWeight::get().reads_writes(
+				count.saturating_mul(5).saturating_add(1),
+				count.saturating_mul(6).saturating_add(1),
+			))
+		}
+
+		/// Remove the stake of an account for a collator if it is below the
+		/// minimum, e.g. after a slash. A collator candidate is removed from
+		/// the pool together with its delegations, unless fewer than
+		/// `MinRequiredCollators` candidates would remain. A delegation is
+		/// revoked. The remaining stake starts unstaking.
+		///
+		/// # <weight>
+		/// Weight: O(D) where D is the number of delegators of the collator
+		/// bounded by `MaxDelegatorsPerCollator`.
+		/// - Reads: CandidatePool, DelegatorState, TopCandidates, D * (Unstaking, DelegatorState)
+		/// - Writes: CandidatePool, DelegatorState, TopCandidates, D * (Unstaking, DelegatorState,
+		///   AutoCompound)
+		/// # </weight>
+		fn remove_stake_below_min(who: &T::AccountId, collator: &T::AccountId) -> Weight {
+			let Some(state) = CandidatePool::<T>::get(collator) else {
+				return T::DbWeight::get().reads(1)
+			};
+
+			if who == collator {
+				if state.stake >= T::MinCollatorStake::get() {
+					return T::DbWeight::get().reads(1)
+				}
+				let mut candidates = TopCandidates::<T>::get();
+				if candidates.len().saturated_into::<u32>() <= T::MinRequiredCollators::get() {
+					log::warn!(
+						"Collator {:?} is below the minimum stake but is required to collate",
+						collator
+					);
+					return T::DbWeight::get().reads(2)
+				}
+
+				if let Err(e) = with_storage_layer(|| Self::remove_candidate(collator, &state)) {
+					log::error!("Could not remove slashed collator {:?}: {:?}", collator, e);
+					return T::DbWeight::get().reads(2)
+				}
+				if candidates
+					.remove(&Stake { owner: collator.clone(), amount: state.total })
+					.is_some()
+				{
+					TopCandidates::<T>::put(candidates);
+					Self::update_total_stake();
+				}
+				Self::deposit_event(Event::CollatorRemoved(collator.clone(), state.total));
+
+				let delegators = state.delegators.len().saturated_into::<u64>();
+				T::DbWeight::get().reads_writes(
+					delegators.saturating_mul(2).saturating_add(3),
+					delegators.saturating_mul(3).saturating_add(4),
+				)
+			} else {
+				let Some(mut delegator) = DelegatorState::<T>::get(who) else {
+					return T::DbWeight::get().reads(2)
+				};
+				let amount = match (&delegator.delegations)
+					.into_iter()
+					.find(|stake| stake.owner == *collator)
+					.map(|stake| stake.amount)
+				{
+					Some(amount) if amount < T::MinDelegatorStake::get() => amount,
+					_ => return T::DbWeight::get().reads(2),
+				};
+
+				let old_total = delegator.total;
+				delegator.rm_delegation(collator);
+				let removed =
+					with_storage_layer(|| Self::kick_delegation(who, collator, amount, state));
+				if let Err(e) = removed {
+					log::error!(
+						"Could not remove slashed delegation of {:?} to {:?}: {:?}",
+						who,
+						collator,
+						e
+					);
+					return T::DbWeight::get().reads(2)
+				}
+
+				if delegator.delegations.is_empty() {
+					DelegatorState::<T>::remove(who);
+					Self::deposit_event(Event::DelegatorLeft(who.clone(), old_total));
+				} else {
+					DelegatorState::<T>::insert(who, delegator);
+				}
+				T::DbWeight::get().reads_writes(5, 5)
+			}
+		}
+
+		/// Remove a delegation from the state of its collator and start
+		/// unstaking its remaining stake, if any. Unlike
+		/// `delegator_leaves_collator`, the last unstaking slot can be used.
+		fn kick_delegation(
+			delegator: &T::AccountId,
+			collator: &T::AccountId,
+			amount: BalanceOf<T>,
+			mut state: CandidateOf<T, T::MaxDelegatorsPerCollator>,
+		) -> DispatchResult {
+			state
+				.delegators
+				.remove(&Stake {
+					owner: delegator.clone(),
+					// amount is irrelevant for removal
+					amount: BalanceOf::<T>::one(),
+				})
+				.ok_or(Error::<T>::DelegatorNotFound)?;
+
+			let CandidateOf::<T, _> { stake: old_stake, total: old_total, .. } = state;
+			state.total = state.total.saturating_sub(amount);
+
+			if !amount.is_zero() {
+				Self::prep_unstake(delegator, amount, true)?;
+			}
+			Self::settle_removed_delegation(delegator, collator, amount);
+
+			if state.is_active() {
+				Self::update_top_candidates(
+					collator.clone(),
+					old_stake,
+					// safe because total >= stake
+					old_total - old_stake,
+					state.stake,
+					state.total - state.stake,
+				);
+			}
+			let new_total = state.total;
+			CandidatePool::<T>::insert(collator, state);
+
+			Self::deposit_event(Event::DelegatorLeftCollator(
+				delegator.clone(),
+				collator.clone(),
+				amount,
+				new_total,
+			));
+			Ok(())
+		}
+
+		/// Reduce the active stake of a collator candidate or of a delegation
+		/// by at most `amount` and return the reduced amount.
+		fn slash_active_stake(
+			who: &T::AccountId,
+			collator: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let Some(mut state) = CandidatePool::<T>::get(collator) else { return Zero::zero() };
+			let CandidateOf::<T, _> { stake: before_stake, total: before_total, .. } = state;
+
+			let slashed = if who == collator {
+				state.slash_stake(amount)
+			} else {
+				let Some(mut delegator) = DelegatorState::<T>::get(who) else {
+					return Zero::zero()
+				};
+				// the rewards earned with the stake before the slash are settled first
+				if let Some(stake) =
+					(&delegator.delegations).into_iter().find(|stake| stake.owner == *collator)
+				{
+					Self::settle_delegation(who, collator, stake.amount);
+				}
+				let Some(slashed) = delegator.slash_delegation(collator.clone(), amount) else {
+					return Zero::zero()
+				};
+				state.dec_delegator(who.clone(), slashed);
+				DelegatorState::<T>::insert(who, delegator);
+				slashed
+			};
+			if slashed.is_zero() {
+				return slashed
+			}
+
+			if state.is_active() {
+				Self::update_top_candidates(
+					collator.clone(),
+					before_stake,
+					// safe because total >= stake
+					before_total - before_stake,
+					state.stake,
+					state.total - state.stake,
+				);
+			}
+			CandidatePool::<T>::insert(collator, state);
+			slashed
+		}
+
+		/// Reduce the funds of an account which are waiting to be unstaked by
+		/// at most `amount`, starting with the earliest unlock, and return the
+		/// reduced amount.
+		fn slash_unstaking(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			if amount.is_zero() {
+				return amount
+			}
+			let mut remaining = amount;
+			<Unstaking<T>>::mutate(who, |unstaking| {
+				for (block_number, locked_balance) in unstaking.clone().into_iter() {
+					if remaining.is_zero() {
+						break
+					}
+					let slashed = locked_balance.min(remaining);
+					remaining = remaining.saturating_sub(slashed);
+					if slashed == locked_balance {
+						unstaking.remove(&block_number);
+					} else if let Some(balance) = unstaking.get_mut(&block_number) {
+						*balance = locked_balance.saturating_sub(slashed);
+					}
+				}
+			});
+			amount.saturating_sub(remaining)
+		}
+
 		/// Get a unique, inaccessible account id from the `PotId`.
 		pub fn account_id() -> T::AccountId {
 			T::PotId::get().into_account_truncating()
 		}
+
+		/// Calculate the rewards an account is projected to receive as
+		/// collator and delegator for the current session.
+		///
+		/// The rewards are calculated as if the session ended now and are
+		/// extrapolated from the blocks which have passed to the length of the
+		/// session, assuming the collators keep authoring blocks at the same
+		/// rate.
+		///
+		/// NOTE: This should only be used by the runtime API since it iterates
+		/// over the entire [CollatorBlock].
+		pub fn get_projected_rewards(acc: &T::AccountId) -> BalanceOf<T> {
+			let round = <Round<T>>::get();
+			let now = frame_system::Pallet::<T>::block_number();
+			let passed_blocks = now.saturating_sub(round.first);
+			if passed_blocks.is_zero() {
+				return Zero::zero()
+			}
+
+			// the whole units of dust are released when the session ends
+			let released: BalanceOf<T> =
+				(RewardDust::<T>::get() / REWARD_PER_STAKE_SCALE).saturated_into();
+			let unclaimed = <UnclaimedRewards<T>>::get().saturating_sub(released);
+			let issue_number = T::Currency::free_balance(&Self::account_id())
+				.checked_sub(&T::Currency::minimum_balance())
+				.and_then(|free| free.checked_sub(&unclaimed))
+				.unwrap_or_else(Zero::zero);
+			let (_, total_staking_in_session) = Self::get_total_collator_staking_num();
+
+			let reward = CollatorBlock::<T>::iter().fold(
+				BalanceOf::<T>::zero(),
+				|reward, (collator, block_num)| {
+					let Some(state) = CandidatePool::<T>::get(&collator) else { return reward };
+					let amount = if collator == *acc {
+						Self::get_collator_reward_per_session(
+							&state,
+							block_num,
+							total_staking_in_session,
+							issue_number,
+						)
+						.amount
+					} else {
+						Self::get_delgators_reward_per_session(
+							&state,
+							block_num,
+							total_staking_in_session,
+							issue_number,
+						)
+						.into_iter()
+						.find(|x| x.owner == *acc)
+						.map_or_else(Zero::zero, |x| x.amount)
+					};
+					reward.saturating_add(amount)
+				},
+			);
+
+			let length = BalanceOf::<T>::from(round.length.saturated_into::<u128>());
+			let passed = BalanceOf::<T>::from(passed_blocks.saturated_into::<u128>());
+			reward.saturating_mul(length) / passed
+		}
+
+		/// Calculate the shares of the total issuance which are staked by the
+		/// selected collators and delegated to them.
+		pub fn get_staking_rates() -> StakingRates {
+			let total_issuance = T::Currency::total_issuance();
+			let TotalStake { collators, delegators } = TotalCollatorStake::<T>::get();
+
+			StakingRates {
+				collator_staking_rate: Perquintill::from_rational(collators, total_issuance),
+				delegator_staking_rate: Perquintill::from_rational(delegators, total_issuance),
+			}
+		}
+
+		/// Return the number of blocks each collator has authored in the
+		/// current session.
+		pub fn get_collator_blocks() -> Vec<(T::AccountId, u32)> {
+			CollatorBlock::<T>::iter().collect()
+		}
+
+		/// Return the rewards an account has earned as collator and delegator
+		/// which can be claimed, including the rewards of its delegations
+		/// which have not been settled yet.
+		pub fn get_claimable_rewards(acc: &T::AccountId) -> BalanceOf<T> {
+			<Rewards<T>>::get(acc).saturating_add(Self::pending_rewards(acc))
+		}
+
+		/// Return the rewards an account has received in the most recent
+		/// `MaxRewardHistory` sessions, oldest first.
+		pub fn get_reward_history(acc: &T::AccountId) -> Vec<RewardRecordOf<T>> {
+			let len = T::MaxRewardHistory::get();
+			let Some(head) = <RewardHistoryHead<T>>::get(acc) else { return Vec::new() };
+			// the last ended session
+			let last = pallet_session::Pallet::<T>::current_index().saturating_sub(1).max(head);
+			let first = last.saturating_add(1).saturating_sub(len);
+
+			(first..=head)
+				.flat_map(|session| {
+					<RewardHistory<T>>::get(acc, session % len)
+						.into_iter()
+						.filter(move |record| record.session == session)
+				})
+				.collect()
+		}
 	}
 
 	impl<T> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T>
@@ -2809,17 +4269,21 @@ pub mod pallet {
 			);
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::DbWeight::get().reads(2),
+				T::DbWeight::get().reads_writes(3, 1),
 				DispatchClass::Mandatory,
 			);
 
-			let collators = Pallet::<T>::selected_candidates().to_vec();
+			let collators = Pallet::<T>::selected_candidates();
 			if collators.is_empty() {
 				// we never want to pass an empty set of collators. This would brick the chain.
 				log::error!("💥 keeping old session because of empty collator set!");
+				// the previous set keeps authoring in the new session
+				let previous = SessionCollators::<T>::get(new_index.saturating_sub(1));
+				SessionCollators::<T>::insert(new_index, previous);
 				None
 			} else {
-				Some(collators)
+				SessionCollators::<T>::insert(new_index, &collators);
+				Some(collators.to_vec())
 			}
 		}
 
@@ -2835,13 +4299,46 @@ pub mod pallet {
 		///			delegator reward = delegator reward ratio * pot balance
 		///		1.4. Transfer the reward to the collator and delegator.
 		/// 2. we need to clean up the state of the pallet.
+		/// 3. Collators which did not author any block are reported and the deferred slashes which
+		///    are due are applied.
 		fn end_session(end_index: SessionIndex) {
 			log::debug!("new_session: {:?}", end_index);
-			Self::peaq_reward_mechanism_impl();
+			let mut weight = Self::report_offline_collators(end_index);
+			Self::peaq_reward_mechanism_impl(end_index);
+			weight = weight.saturating_add(Self::apply_slashes(end_index));
+
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				weight,
+				DispatchClass::Mandatory,
+			);
 		}
 
-		fn start_session(_start_index: SessionIndex) {
-			// we too are not caring.
+		/// Snapshot the commission of the collators of the new session, such
+		/// that changes during the session only apply from the next one.
+		///
+		/// # <weight>
+		/// Weight: O(N) where N is the number of collators in the session
+		/// bounded by `MaxTopCandidates`.
+		/// - Reads: SessionCollators, N * CandidatePool
+		/// - Writes: N * SessionCommission
+		/// # </weight>
+		fn start_session(start_index: SessionIndex) {
+			let collators = SessionCollators::<T>::get(start_index);
+			let mut writes = 0u64;
+			for collator in collators.iter() {
+				if let Some(state) = CandidatePool::<T>::get(collator) {
+					SessionCommission::<T>::insert(start_index, collator, state.commission);
+					writes = writes.saturating_add(1);
+				}
+			}
+
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(
+					collators.len().saturated_into::<u64>().saturating_add(1),
+					writes,
+				),
+				DispatchClass::Mandatory,
+			);
 		}
 	}
 
@@ -17,8 +17,9 @@ use frame_support::{
 enum Versions {
 	_V7 = 7,
 	_V8 = 8,
-	#[default]
 	V9 = 9,
+	#[default]
+	V10 = 10,
 }
 
 pub(crate) fn on_runtime_upgrade<T: Config>() -> Weight {
@@ -27,14 +28,36 @@ pub(crate) fn on_runtime_upgrade<T: Config>() -> Weight {
 
 mod upgrade {
 	use super::*;
-	use crate::pallet::{OLD_STAKING_ID, STAKING_ID};
+	use crate::{
+		pallet::{CandidatePool, OLD_STAKING_ID, STAKING_ID},
+		set::OrderedSet,
+		types::{AccountIdOf, BalanceOf, Candidate, CandidateStatus, Stake},
+	};
 	use frame_support::traits::{LockableCurrency, WithdrawReasons};
 	use pallet_balances::Locks;
+	use parity_scale_codec::Decode;
+	use sp_runtime::Perbill;
 
 	#[storage_alias]
 	type RewardRateConfig<T: Config> = StorageValue<Pallet<T>, RewardRateInfo, ValueQuery>;
 
-	/// Migration implementation that deletes the old reward rate config and changes the staking ID.
+	/// Candidate layout before the commission was introduced in V10.
+	#[derive(Decode)]
+	struct CandidateV9<AccountId, Balance, MaxDelegatorsPerCandidate>
+	where
+		AccountId: Eq + Ord,
+		Balance: Eq + Ord,
+		MaxDelegatorsPerCandidate: Get<u32>,
+	{
+		id: AccountId,
+		stake: Balance,
+		delegators: OrderedSet<Stake<AccountId, Balance>, MaxDelegatorsPerCandidate>,
+		total: Balance,
+		status: CandidateStatus,
+	}
+
+	/// Migration implementation that deletes the old reward rate config, changes the staking ID
+	/// and adds a zero commission to all collator candidates.
 	pub struct Migrate<T>(sp_std::marker::PhantomData<T>);
 
 	impl<T: Config> Migrate<T> {
@@ -42,7 +65,7 @@ mod upgrade {
 			let mut weight_writes = 0;
 			let mut weight_reads = 0;
 			let onchain_storage_version = Pallet::<T>::on_chain_storage_version();
-			if onchain_storage_version < StorageVersion::new(Versions::default() as u16) {
+			if onchain_storage_version < StorageVersion::new(Versions::V9 as u16) {
 				// Change the STAKING_ID value
 				log::info!("Updating lock id from old staking ID to new staking ID.");
 				for (account_id, balance) in Locks::<T>::iter() {
@@ -62,9 +85,32 @@ mod upgrade {
 					}
 					weight_reads += 1;
 				}
-				StorageVersion::new(Versions::default() as u16).put::<Pallet<T>>();
 				log::info!("V9 Migrating Done.");
 			}
+			if onchain_storage_version < StorageVersion::new(Versions::V10 as u16) {
+				// Add the commission to all candidates
+				log::info!("Adding commission to collator candidates.");
+				CandidatePool::<T>::translate::<
+					CandidateV9<AccountIdOf<T>, BalanceOf<T>, T::MaxDelegatorsPerCollator>,
+					_,
+				>(|_, old| {
+					weight_reads += 1;
+					weight_writes += 1;
+					Some(Candidate {
+						id: old.id,
+						stake: old.stake,
+						delegators: old.delegators,
+						total: old.total,
+						status: old.status,
+						commission: Perbill::zero(),
+					})
+				});
+				log::info!("V10 Migrating Done.");
+			}
+			if onchain_storage_version < StorageVersion::new(Versions::default() as u16) {
+				StorageVersion::new(Versions::default() as u16).put::<Pallet<T>>();
+				weight_writes += 1;
+			}
 			T::DbWeight::get().reads_writes(weight_reads, weight_writes)
 		}
 	}
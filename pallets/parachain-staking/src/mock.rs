@@ -20,7 +20,7 @@
 #![allow(clippy::from_over_into)]
 
 use frame_support::{
-	construct_runtime, parameter_types,
+	assert_ok, construct_runtime, parameter_types,
 	traits::{Currency, GenesisBuild, OnFinalize, OnInitialize},
 	weights::Weight,
 	PalletId,
@@ -152,6 +152,12 @@ parameter_types! {
 	pub const MinDelegatorStake: Balance = 5;
 	pub const MinDelegation: Balance = 3;
 	pub const MaxUnstakeRequests: u32 = 6;
+	pub const MaxCollatorCommission: Perbill = Perbill::from_percent(50);
+	pub static OfflineSlashFraction: Perbill = Perbill::zero();
+	pub static SlashDelegators: bool = false;
+	pub const SlashDeferDuration: u32 = 1;
+	pub const OffenceDeselectDuration: u32 = 1;
+	pub const MaxRewardHistory: u32 = 3;
 }
 
 impl Config for Test {
@@ -173,6 +179,14 @@ impl Config for Test {
 	type MinDelegatorStake = MinDelegatorStake;
 	type MinDelegation = MinDelegation;
 	type MaxUnstakeRequests = MaxUnstakeRequests;
+	type MaxCollatorCommission = MaxCollatorCommission;
+	type OfflineSlashFraction = OfflineSlashFraction;
+	type SlashDelegators = SlashDelegators;
+	type SlashDeferDuration = SlashDeferDuration;
+	type OffenceDeselectDuration = OffenceDeselectDuration;
+	type MaxRewardHistory = MaxRewardHistory;
+	type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slash = ();
 	type PotId = PotId;
 	type WeightInfo = crate::weights::WeightInfo<Test>;
 }
@@ -334,6 +348,15 @@ pub(crate) fn roll_to(n: BlockNumber, authors: Vec<Option<AccountId>>) {
 	}
 }
 
+/// Claim the credited staking rewards of every account which has any.
+pub(crate) fn claim_rewards(accounts: Vec<AccountId>) {
+	for acc in accounts {
+		if StakePallet::get_claimable_rewards(&acc) > 0 {
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(acc)));
+		}
+	}
+}
+
 pub(crate) fn last_event() -> RuntimeEvent {
 	System::events().pop().expect("Event expected").event
 }
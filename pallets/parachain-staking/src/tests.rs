@@ -21,27 +21,29 @@
 use std::{convert::TryInto, iter};
 
 use frame_support::{
-	assert_noop, assert_ok, storage::bounded_btree_map::BoundedBTreeMap,
-	traits::EstimateNextSessionRotation, BoundedVec,
+	assert_noop, assert_ok,
+	storage::bounded_btree_map::BoundedBTreeMap,
+	traits::{EstimateNextSessionRotation, Get},
+	BoundedVec,
 };
 use frame_system::RawOrigin;
 use pallet_balances::{BalanceLock, Error as BalancesError, Reasons};
 use pallet_session::{SessionManager, ShouldEndSession};
-use sp_runtime::{traits::Zero, Perbill, Permill, Perquintill, SaturatedConversion};
+use sp_runtime::{traits::Zero, Perbill, Percent, Permill, Perquintill, SaturatedConversion};
 
 use crate::{
 	mock::{
-		almost_equal, events, last_event, roll_to, AccountId, Balance, Balances, BlockNumber,
-		ExtBuilder, RuntimeEvent as MetaEvent, RuntimeOrigin, Session, StakePallet, System, Test,
-		BLOCKS_PER_ROUND, BLOCK_REWARD_IN_GENESIS_SESSION, BLOCK_REWARD_IN_NORMAL_SESSION,
-		DECIMALS,
+		almost_equal, claim_rewards, events, last_event, roll_to, AccountId, Balance, Balances,
+		BlockNumber, ExtBuilder, OfflineSlashFraction, RuntimeEvent as MetaEvent, RuntimeOrigin,
+		Session, SlashDelegators, StakePallet, System, Test, BLOCKS_PER_ROUND,
+		BLOCK_REWARD_IN_GENESIS_SESSION, BLOCK_REWARD_IN_NORMAL_SESSION, DECIMALS,
 	},
 	set::OrderedSet,
 	types::{
-		BalanceOf, Candidate, CandidateStatus, DelegationCounter, Delegator, Reward, RoundInfo,
-		Stake, StakeOf, TotalStake,
+		BalanceOf, Candidate, CandidateStatus, DelegationCounter, Delegator, Reward, RewardRecord,
+		RoundInfo, Stake, StakeOf, TotalStake, UnappliedSlash,
 	},
-	CandidatePool, Config, Error, Event, STAKING_ID,
+	CandidatePool, Config, Error, Event, REWARD_PER_STAKE_SCALE, STAKING_ID,
 };
 
 #[test]
@@ -1452,6 +1454,7 @@ fn delegator_should_not_receive_rewards_after_revoking() {
 			assert_eq!(Balances::usable_balance(1), Balance::zero());
 			assert_eq!(Balances::usable_balance(2), Balance::zero());
 			roll_to(100, authors);
+			claim_rewards(vec![1, 2]);
 			assert!(Balances::usable_balance(1) > Balance::zero());
 			assert_ok!(StakePallet::unlock_unstaked(RuntimeOrigin::signed(2), 2));
 			assert_eq!(Balances::usable_balance(2), 10_000_000 * DECIMALS);
@@ -1473,6 +1476,7 @@ fn delegator_should_not_receive_rewards_after_revoking() {
 			assert_eq!(Balances::usable_balance(2), Balance::zero());
 			assert_eq!(Balances::usable_balance(3), Balance::zero());
 			roll_to(100, authors);
+			claim_rewards(vec![1, 2, 3]);
 			assert!(Balances::usable_balance(1) > Balance::zero());
 			assert!(Balances::usable_balance(2) > Balance::zero());
 			assert_ok!(StakePallet::unlock_unstaked(RuntimeOrigin::signed(3), 3));
@@ -1507,6 +1511,7 @@ fn coinbase_rewards_many_blocks_simple_check() {
 				(0u64..=end_block).map(|i| Some(i % 2 + 1)).collect();
 			// adding one is to force the session go next
 			roll_to(5, authors.clone());
+			claim_rewards((1..=5).collect());
 
 			let genesis_reward_1 = Perbill::from_float(32. / 80.) * BLOCK_REWARD_IN_GENESIS_SESSION;
 			let genesis_reward_3 = Perbill::from_float(8. / 80.) * BLOCK_REWARD_IN_GENESIS_SESSION;
@@ -1523,7 +1528,10 @@ fn coinbase_rewards_many_blocks_simple_check() {
 
 			// 2 is block author for 3 blocks, 1 is block author for 2 block
 			roll_to(10, authors.clone());
+			claim_rewards((1..=5).collect());
 			let normal_odd_total_stake: u64 = 2 * (32 + 8 + 16) + 3 * (8 + 16);
+			// the rewards of delegators are rounded down when they are settled and the
+			// remainder is issued in the next session
 
 			let normal_odd_reward_1 = Perbill::from_rational(2 * 32, normal_odd_total_stake) *
 				BLOCK_REWARD_IN_NORMAL_SESSION;
@@ -1544,21 +1552,25 @@ fn coinbase_rewards_many_blocks_simple_check() {
 				Balances::free_balance(2),
 				genesis_reward_2 + normal_odd_reward_2 + 40_000_000 * DECIMALS
 			);
-			assert_eq!(
-				Balances::free_balance(3),
-				genesis_reward_3 + normal_odd_reward_3 + 40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(4),
-				genesis_reward_4 + normal_odd_reward_4 + 20_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(5),
-				genesis_reward_5 + normal_odd_reward_5 + 20_000_000 * DECIMALS
-			);
+			assert!(almost_equal(
+				Balances::free_balance(3) - 40_000_000 * DECIMALS,
+				genesis_reward_3 + normal_odd_reward_3,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(4) - 20_000_000 * DECIMALS,
+				genesis_reward_4 + normal_odd_reward_4,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(5) - 20_000_000 * DECIMALS,
+				genesis_reward_5 + normal_odd_reward_5,
+				Perbill::from_percent(1)
+			));
 
 			// 2 is block author for 3 blocks, 1 is block author for 2 block
 			roll_to(15, authors.clone());
+			claim_rewards((1..=5).collect());
 			let normal_even_total_stake: u64 = 3 * (32 + 8 + 16) + 2 * (8 + 16);
 
 			let normal_even_reward_1 = Perbill::from_rational(3 * 32, normal_even_total_stake) *
@@ -1572,69 +1584,60 @@ fn coinbase_rewards_many_blocks_simple_check() {
 			let normal_even_reward_5 = Perbill::from_rational(2 * 16, normal_even_total_stake) *
 				BLOCK_REWARD_IN_NORMAL_SESSION;
 
-			assert_eq!(
-				Balances::free_balance(1),
-				genesis_reward_1 +
-					normal_odd_reward_1 + normal_even_reward_1 +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(2),
-				genesis_reward_2 +
-					normal_odd_reward_2 + normal_even_reward_2 +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(3),
-				genesis_reward_3 +
-					normal_odd_reward_3 + normal_even_reward_3 +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(4),
-				genesis_reward_4 +
-					normal_odd_reward_4 + normal_even_reward_4 +
-					20_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(5),
-				genesis_reward_5 +
-					normal_odd_reward_5 + normal_even_reward_5 +
-					20_000_000 * DECIMALS
-			);
+			assert!(almost_equal(
+				Balances::free_balance(1) - 40_000_000 * DECIMALS,
+				genesis_reward_1 + normal_odd_reward_1 + normal_even_reward_1,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(2) - 40_000_000 * DECIMALS,
+				genesis_reward_2 + normal_odd_reward_2 + normal_even_reward_2,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(3) - 40_000_000 * DECIMALS,
+				genesis_reward_3 + normal_odd_reward_3 + normal_even_reward_3,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(4) - 20_000_000 * DECIMALS,
+				genesis_reward_4 + normal_odd_reward_4 + normal_even_reward_4,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(5) - 20_000_000 * DECIMALS,
+				genesis_reward_5 + normal_odd_reward_5 + normal_even_reward_5,
+				Perbill::from_percent(1)
+			));
 
 			roll_to(end_block, authors.clone());
+			claim_rewards((1..=5).collect());
 			let multiply_factor = (end_block as u128 - 5) / 10;
-			assert_eq!(
-				Balances::free_balance(1),
-				genesis_reward_1 +
-					(normal_odd_reward_1 + normal_even_reward_1) * multiply_factor +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(2),
-				genesis_reward_2 +
-					(normal_odd_reward_2 + normal_even_reward_2) * multiply_factor +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(3),
-				genesis_reward_3 +
-					(normal_odd_reward_3 + normal_even_reward_3) * multiply_factor +
-					40_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(4),
-				genesis_reward_4 +
-					(normal_odd_reward_4 + normal_even_reward_4) * multiply_factor +
-					20_000_000 * DECIMALS
-			);
-			assert_eq!(
-				Balances::free_balance(5),
-				genesis_reward_5 +
-					(normal_odd_reward_5 + normal_even_reward_5) * multiply_factor +
-					20_000_000 * DECIMALS
-			);
+			assert!(almost_equal(
+				Balances::free_balance(1) - 40_000_000 * DECIMALS,
+				genesis_reward_1 + (normal_odd_reward_1 + normal_even_reward_1) * multiply_factor,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(2) - 40_000_000 * DECIMALS,
+				genesis_reward_2 + (normal_odd_reward_2 + normal_even_reward_2) * multiply_factor,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(3) - 40_000_000 * DECIMALS,
+				genesis_reward_3 + (normal_odd_reward_3 + normal_even_reward_3) * multiply_factor,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(4) - 20_000_000 * DECIMALS,
+				genesis_reward_4 + (normal_odd_reward_4 + normal_even_reward_4) * multiply_factor,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(5) - 20_000_000 * DECIMALS,
+				genesis_reward_5 + (normal_odd_reward_5 + normal_even_reward_5) * multiply_factor,
+				Perbill::from_percent(1)
+			));
 
 			// Check total issue number
 			assert!(almost_equal(
@@ -1682,6 +1685,7 @@ fn should_reward_delegators_below_min_stake() {
 			// should only reward 1
 			let total_stake_num = stake_num + delegator_stake_below_min;
 			roll_to(5, authors);
+			claim_rewards((1..=4).collect());
 			assert_eq!(
 				Balances::usable_balance(1),
 				Perquintill::from_rational(stake_num, total_stake_num) *
@@ -3063,20 +3067,24 @@ fn authorities_per_round() {
 			// roll to new round 1
 			let reward_0 = 1000;
 			roll_to(BLOCKS_PER_ROUND, authors.clone());
+			claim_rewards(vec![1]);
 			assert_eq!(Balances::free_balance(1), stake + reward_0);
 			// increase max selected candidates which will become effective in round 2
 			assert_ok!(StakePallet::set_max_selected_candidates(RuntimeOrigin::root(), 10));
 
 			// roll to new round 2
 			roll_to(BLOCKS_PER_ROUND * 2, authors.clone());
+			claim_rewards(vec![1]);
 			assert_eq!(Balances::free_balance(1), stake + reward_0 * 2);
 
 			// roll to new round 3
 			roll_to(BLOCKS_PER_ROUND * 3, authors.clone());
+			claim_rewards(vec![1]);
 			assert_eq!(Balances::free_balance(1), stake + reward_0 * 3);
 
 			// roll to new round 4
 			roll_to(BLOCKS_PER_ROUND * 4, authors);
+			claim_rewards(vec![1]);
 			assert_eq!(Balances::free_balance(1), stake + reward_0 * 4);
 		});
 }
@@ -3406,6 +3414,7 @@ fn check_claim_block_normal_wo_delegator() {
 			];
 
 			roll_to(5, authors.clone());
+			claim_rewards((1..=4).collect());
 
 			assert_eq!(
 				Balances::free_balance(1),
@@ -3430,6 +3439,7 @@ fn check_claim_block_normal_wo_delegator() {
 
 			// Cross session but only 1 is selected
 			roll_to(10, authors.clone());
+			claim_rewards((1..=4).collect());
 			assert_eq!(
 				Balances::free_balance(1),
 				Perquintill::from_float(1. / 10.) * BLOCK_REWARD_IN_GENESIS_SESSION +
@@ -3496,82 +3506,84 @@ fn check_claim_block_normal_wi_delegator() {
 			];
 
 			roll_to(5, authors.clone());
+			claim_rewards((1..=10).collect());
 
 			assert_eq!(
 				Balances::free_balance(1),
 				Perquintill::from_float(1. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(5),
-				Perquintill::from_float(5. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
-			assert_eq!(
-				Balances::free_balance(6),
-				Perquintill::from_float(6. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(5) - origin_balance,
+				Perquintill::from_float(5. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(6) - origin_balance,
+				Perquintill::from_float(6. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			assert_eq!(
 				Balances::free_balance(2),
 				Perquintill::from_float(2. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(7),
-				Perquintill::from_float(7. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(7) - origin_balance,
+				Perquintill::from_float(7. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			assert_eq!(
 				Balances::free_balance(3),
 				Perquintill::from_float(3. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(8),
-				Perquintill::from_float(8. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(8) - origin_balance,
+				Perquintill::from_float(8. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			assert_eq!(
 				Balances::free_balance(4),
 				Perquintill::from_float(4. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(9),
-				Perquintill::from_float(9. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
-			assert_eq!(
-				Balances::free_balance(10),
-				Perquintill::from_float(10. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(9) - origin_balance,
+				Perquintill::from_float(9. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(10) - origin_balance,
+				Perquintill::from_float(10. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			// Cross session but only 1 is selected
 			roll_to(10, authors.clone());
+			claim_rewards((1..=10).collect());
 
-			assert_eq!(
-				Balances::free_balance(1),
+			assert!(almost_equal(
+				Balances::free_balance(1) - origin_balance,
 				Perquintill::from_float(1. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					Perquintill::from_float(1. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION +
-					origin_balance
-			);
-			assert_eq!(
-				Balances::free_balance(5),
+					Perquintill::from_float(1. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(5) - origin_balance,
 				Perquintill::from_float(5. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					Perquintill::from_float(5. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION +
-					origin_balance
-			);
-			assert_eq!(
-				Balances::free_balance(6),
+					Perquintill::from_float(5. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(6) - origin_balance,
 				Perquintill::from_float(6. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					Perquintill::from_float(6. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION +
-					origin_balance
-			);
+					Perquintill::from_float(6. / 12.) * BLOCK_REWARD_IN_NORMAL_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			// Nothing change
 			assert_eq!(
@@ -3579,38 +3591,38 @@ fn check_claim_block_normal_wi_delegator() {
 				Perquintill::from_float(2. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(7),
-				Perquintill::from_float(7. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(7) - origin_balance,
+				Perquintill::from_float(7. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			assert_eq!(
 				Balances::free_balance(3),
 				Perquintill::from_float(3. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(8),
-				Perquintill::from_float(8. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(8) - origin_balance,
+				Perquintill::from_float(8. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 
 			assert_eq!(
 				Balances::free_balance(4),
 				Perquintill::from_float(4. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
 					origin_balance
 			);
-			assert_eq!(
-				Balances::free_balance(9),
-				Perquintill::from_float(9. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
-			assert_eq!(
-				Balances::free_balance(10),
-				Perquintill::from_float(10. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION +
-					origin_balance
-			);
+			assert!(almost_equal(
+				Balances::free_balance(9) - origin_balance,
+				Perquintill::from_float(9. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
+			assert!(almost_equal(
+				Balances::free_balance(10) - origin_balance,
+				Perquintill::from_float(10. / 55.) * BLOCK_REWARD_IN_GENESIS_SESSION,
+				Perbill::from_percent(1)
+			));
 		});
 }
 
@@ -3739,3 +3751,697 @@ fn max_candidate_stake_over_max_staking() {
 			);
 		});
 }
+
+#[test]
+fn set_commission() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000)])
+		.with_collators(vec![(1, 500), (2, 500)])
+		.with_delegators(vec![(3, 1, 400)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(StakePallet::candidate_pool(1).unwrap().commission, Perbill::zero());
+
+			assert_noop!(
+				StakePallet::set_commission(RuntimeOrigin::signed(3), Perbill::from_percent(10)),
+				Error::<Test>::CandidateNotFound
+			);
+			assert_noop!(
+				StakePallet::set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(51)),
+				Error::<Test>::CommissionAboveMax
+			);
+
+			assert_ok!(StakePallet::set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(10)
+			));
+			assert_eq!(
+				StakePallet::candidate_pool(1).unwrap().commission,
+				Perbill::from_percent(10)
+			);
+			assert_eq!(StakePallet::last_commission_change(1), Some(0));
+			assert_eq!(
+				last_event(),
+				MetaEvent::StakePallet(Event::CollatorCommissionChanged(
+					1,
+					Perbill::zero(),
+					Perbill::from_percent(10)
+				))
+			);
+
+			// only one change per round
+			assert_noop!(
+				StakePallet::set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(20)),
+				Error::<Test>::CommissionAlreadyChanged
+			);
+			// other candidates are not affected
+			assert_ok!(StakePallet::set_commission(
+				RuntimeOrigin::signed(2),
+				Perbill::from_percent(50)
+			));
+
+			roll_to(BLOCKS_PER_ROUND + 1, vec![]);
+			assert_ok!(StakePallet::set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(20)
+			));
+			assert_eq!(
+				StakePallet::candidate_pool(1).unwrap().commission,
+				Perbill::from_percent(20)
+			);
+			assert_eq!(StakePallet::last_commission_change(1), Some(1));
+		});
+}
+
+#[test]
+fn reward_per_session_with_commission() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000)])
+		.with_collators(vec![(1, 500)])
+		.with_delegators(vec![(2, 1, 600), (3, 1, 400)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(StakePallet::set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(10)
+			));
+			let state = CandidatePool::<Test>::get(1).unwrap();
+
+			let gross_2 = Perquintill::from_rational(10 as u64 * 600, 50000) * 1000;
+			let gross_3 = Perquintill::from_rational(10 as u64 * 400, 50000) * 1000;
+			let commission_2 = Perbill::from_percent(10) * gross_2;
+			let commission_3 = Perbill::from_percent(10) * gross_3;
+
+			let rewards = StakePallet::get_delgators_reward_per_session(&state, 10, 50000, 1000);
+			assert_eq!(rewards[0], Reward { owner: 2, amount: gross_2 - commission_2 });
+			assert_eq!(rewards[1], Reward { owner: 3, amount: gross_3 - commission_3 });
+
+			let reward = StakePallet::get_collator_reward_per_session(&state, 10, 50000, 1000);
+			assert_eq!(
+				reward,
+				Reward {
+					owner: 1,
+					amount: Perquintill::from_rational(10 as u64 * 500, 50000) * 1000 +
+						commission_2 + commission_3
+				}
+			);
+
+			// the commission only moves rewards between the collator and its delegators
+			assert_eq!(
+				reward.amount + rewards[0].amount + rewards[1].amount,
+				Perquintill::from_rational(10 as u64 * 500, 50000) * 1000 + gross_2 + gross_3
+			);
+		});
+}
+
+#[test]
+fn commission_applies_from_next_session() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000)])
+		.with_collators(vec![(1, 500)])
+		.with_delegators(vec![(2, 1, 500)])
+		.build()
+		.execute_with(|| {
+			let authors: Vec<Option<AccountId>> =
+				iter::once(None).chain(iter::repeat(Some(1u64)).take(10)).collect();
+
+			// the commission is raised in the middle of session 0
+			roll_to(3, authors.clone());
+			assert_ok!(StakePallet::set_commission(
+				RuntimeOrigin::signed(1),
+				Perbill::from_percent(50)
+			));
+			assert_eq!(StakePallet::session_commission(0, 1), Some(Perbill::zero()));
+
+			// session 0 is paid out without commission, both staking the same
+			roll_to(BLOCKS_PER_ROUND, authors.clone());
+			let collator_0 = StakePallet::get_claimable_rewards(&1);
+			let delegator_0 = StakePallet::get_claimable_rewards(&2);
+			assert!(!collator_0.is_zero());
+			assert!(almost_equal(collator_0, delegator_0, Perbill::from_percent(1)));
+			assert_eq!(StakePallet::session_commission(0, 1), None);
+			assert_eq!(StakePallet::session_commission(1, 1), Some(Perbill::from_percent(50)));
+
+			// the commission is taken off the rewards of session 1
+			roll_to(2 * BLOCKS_PER_ROUND, authors);
+			let collator_1 = StakePallet::get_claimable_rewards(&1) - collator_0;
+			let delegator_1 = StakePallet::get_claimable_rewards(&2) - delegator_0;
+			assert!(almost_equal(collator_1, 3 * delegator_1, Perbill::from_percent(1)));
+		});
+}
+
+#[test]
+fn set_auto_compound() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 1000), (2, 1000), (3, 1000), (4, 1000)])
+		.with_collators(vec![(1, 500), (2, 500)])
+		.with_delegators(vec![(3, 1, 400)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				StakePallet::set_auto_compound(RuntimeOrigin::signed(4), 1, Percent::one()),
+				Error::<Test>::DelegatorNotFound
+			);
+			assert_noop!(
+				StakePallet::set_auto_compound(RuntimeOrigin::signed(3), 2, Percent::one()),
+				Error::<Test>::DelegationNotFound
+			);
+
+			assert_ok!(StakePallet::set_auto_compound(
+				RuntimeOrigin::signed(3),
+				1,
+				Percent::from_percent(40)
+			));
+			assert_eq!(StakePallet::auto_compound(3, 1), Percent::from_percent(40));
+			assert_eq!(
+				last_event(),
+				MetaEvent::StakePallet(Event::AutoCompoundSet(3, 1, Percent::from_percent(40)))
+			);
+
+			// zero disables auto-compounding
+			assert_ok!(StakePallet::set_auto_compound(
+				RuntimeOrigin::signed(3),
+				1,
+				Percent::zero()
+			));
+			assert!(!crate::AutoCompound::<Test>::contains_key(3, 1));
+
+			// revoking the delegation clears the setting
+			assert_ok!(StakePallet::set_auto_compound(RuntimeOrigin::signed(3), 1, Percent::one()));
+			assert_ok!(StakePallet::leave_delegators(RuntimeOrigin::signed(3)));
+			assert!(!crate::AutoCompound::<Test>::contains_key(3, 1));
+		});
+}
+
+#[test]
+fn auto_compound_restakes_rewards() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000), (3, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(StakePallet::set_auto_compound(RuntimeOrigin::signed(2), 1, Percent::one()));
+			assert_ok!(StakePallet::set_auto_compound(
+				RuntimeOrigin::signed(3),
+				1,
+				Percent::from_percent(50)
+			));
+
+			let authors: Vec<Option<AccountId>> =
+				vec![None, Some(1u64), Some(1u64), Some(1u64), Some(1u64)];
+			roll_to(5, authors);
+
+			// rewards are only restaked once they are settled
+			assert!(!events().iter().any(|e| matches!(e, Event::Compounded(..))));
+			let reward_2 = StakePallet::get_claimable_rewards(&2);
+			let reward_3 = StakePallet::get_claimable_rewards(&3);
+			assert!(!reward_2.is_zero());
+
+			// the full reward of 2 is restaked
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(2)));
+			assert!(StakePallet::rewards(2).is_zero());
+			assert_eq!(StakePallet::delegator_state(2).unwrap().total, 1000 + reward_2);
+			assert_eq!(Balances::free_balance(2), 10_000 + reward_2);
+			assert_eq!(Balances::usable_balance(2), 10_000 - 1000);
+
+			// half of the reward of 3 is restaked, the rest is paid out
+			let restaked_3 = Percent::from_percent(50) * reward_3;
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(3)));
+			assert!(StakePallet::rewards(3).is_zero());
+			assert_eq!(StakePallet::delegator_state(3).unwrap().total, 1000 + restaked_3);
+			assert_eq!(Balances::free_balance(3), 10_000 + reward_3);
+			assert_eq!(Balances::usable_balance(3), 10_000 - 1000 + reward_3 - restaked_3);
+
+			assert_eq!(StakePallet::candidate_pool(1).unwrap().total, 3000 + reward_2 + restaked_3);
+			assert!(events().contains(&Event::Compounded(2, 1, reward_2, 1000 + reward_2)));
+			assert!(events().contains(&Event::Compounded(3, 1, restaked_3, 1000 + restaked_3)));
+		});
+}
+
+#[test]
+fn auto_compound_respects_max_candidate_stake() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(StakePallet::set_max_candidate_stake(RuntimeOrigin::root(), 1010));
+			assert_ok!(StakePallet::set_auto_compound(RuntimeOrigin::signed(2), 1, Percent::one()));
+
+			let authors: Vec<Option<AccountId>> =
+				vec![None, Some(1u64), Some(1u64), Some(1u64), Some(1u64)];
+			roll_to(5, authors);
+
+			// only the headroom of the delegation up to the maximum stake is restaked, the
+			// rest is paid out
+			let reward = StakePallet::get_claimable_rewards(&2);
+			assert!(reward > 10);
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(2)));
+			assert!(events().contains(&Event::Compounded(2, 1, 10, 1010)));
+			assert_eq!(StakePallet::candidate_pool(1).unwrap().total, 2010);
+			assert_eq!(StakePallet::delegator_state(2).unwrap().total, 1010);
+			assert_eq!(Balances::free_balance(2), 10_000 + reward);
+			assert_eq!(Balances::usable_balance(2), 10_000 - 1010 + reward);
+		});
+}
+
+#[test]
+fn offline_collator_is_slashed_after_defer_duration() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000), (2, 1000)])
+		.with_delegators(vec![(3, 2, 1000)])
+		.build()
+		.execute_with(|| {
+			OfflineSlashFraction::set(Perbill::from_percent(10));
+			SlashDelegators::set(true);
+
+			// 2 does not author any block in session 0
+			let authors: Vec<Option<AccountId>> = vec![
+				None,
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+			];
+			roll_to(5, authors.clone());
+
+			assert!(events().contains(&Event::OffenceReported(2, 0, 1)));
+			assert!(events().contains(&Event::CandidateDeselected(2, 2)));
+			assert_eq!(StakePallet::deselected_until(2), Some(2));
+			assert_eq!(
+				StakePallet::unapplied_slashes(1).into_inner(),
+				vec![UnappliedSlash {
+					collator: 2,
+					own: 100,
+					others: vec![(3, 100)].try_into().unwrap()
+				}]
+			);
+			// 2 is not selected for the next session
+			assert_eq!(StakePallet::selected_candidates().into_inner(), vec![1]);
+			// nothing is slashed yet
+			assert_eq!(StakePallet::candidate_pool(2).unwrap().total, 2000);
+
+			roll_to(10, authors);
+
+			assert!(events().contains(&Event::Slashed(2, 100)));
+			assert!(events().contains(&Event::Slashed(3, 100)));
+			assert!(StakePallet::unapplied_slashes(1).is_empty());
+			let state = StakePallet::candidate_pool(2).unwrap();
+			assert_eq!(state.stake, 900);
+			assert_eq!(state.total, 1800);
+			assert_eq!(StakePallet::delegator_state(3).unwrap().total, 900);
+			assert_eq!(
+				Balances::locks(2),
+				vec![BalanceLock { id: STAKING_ID, amount: 900, reasons: Reasons::All }]
+			);
+			assert_eq!(
+				Balances::locks(3),
+				vec![BalanceLock { id: STAKING_ID, amount: 900, reasons: Reasons::All }]
+			);
+
+			// 2 can be selected again
+			assert_eq!(StakePallet::deselected_until(2), None);
+			assert_eq!(StakePallet::selected_candidates().into_inner(), vec![1, 2]);
+		});
+}
+
+#[test]
+fn slash_applies_to_unstaking_funds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000), (2, 1000)])
+		.with_delegators(vec![(3, 2, 1000)])
+		.build()
+		.execute_with(|| {
+			OfflineSlashFraction::set(Perbill::from_percent(10));
+			SlashDelegators::set(true);
+
+			let authors: Vec<Option<AccountId>> = vec![
+				None,
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+			];
+			roll_to(5, authors.clone());
+			assert!(events().contains(&Event::OffenceReported(2, 0, 1)));
+
+			// 3 leaves before the slash is applied
+			assert_ok!(StakePallet::leave_delegators(RuntimeOrigin::signed(3)));
+			let mut unstaking: BoundedBTreeMap<
+				BlockNumber,
+				BalanceOf<Test>,
+				<Test as Config>::MaxUnstakeRequests,
+			> = BoundedBTreeMap::new();
+			assert_ok!(unstaking.try_insert(7, 1000));
+			assert_eq!(StakePallet::unstaking(3), unstaking);
+
+			roll_to(10, authors);
+
+			assert!(events().contains(&Event::Slashed(3, 100)));
+			let mut unstaking: BoundedBTreeMap<
+				BlockNumber,
+				BalanceOf<Test>,
+				<Test as Config>::MaxUnstakeRequests,
+			> = BoundedBTreeMap::new();
+			assert_ok!(unstaking.try_insert(7, 900));
+			assert_eq!(StakePallet::unstaking(3), unstaking);
+			assert_eq!(
+				Balances::locks(3),
+				vec![BalanceLock { id: STAKING_ID, amount: 900, reasons: Reasons::All }]
+			);
+			assert_eq!(Balances::free_balance(3), 9_900);
+
+			// the remaining funds can be unlocked
+			assert_ok!(StakePallet::unlock_unstaked(RuntimeOrigin::signed(3), 3));
+			assert!(Balances::locks(3).is_empty());
+		});
+}
+
+#[test]
+fn cancel_deferred_slash() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000), (2, 1000)])
+		.with_delegators(vec![(3, 2, 1000)])
+		.build()
+		.execute_with(|| {
+			OfflineSlashFraction::set(Perbill::from_percent(10));
+
+			let authors: Vec<Option<AccountId>> = vec![
+				None,
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+				Some(1u64),
+			];
+			roll_to(5, authors.clone());
+			// delegators are not slashed by default
+			assert_eq!(
+				StakePallet::unapplied_slashes(1).into_inner(),
+				vec![UnappliedSlash { collator: 2, own: 100, others: Default::default() }]
+			);
+
+			assert_noop!(
+				StakePallet::cancel_deferred_slash(RuntimeOrigin::signed(1), 1, vec![0]),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_noop!(
+				StakePallet::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![]),
+				Error::<Test>::EmptySlashIndices
+			);
+			assert_noop!(
+				StakePallet::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![0, 0]),
+				Error::<Test>::NotSortedAndUnique
+			);
+			assert_noop!(
+				StakePallet::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![1]),
+				Error::<Test>::InvalidSlashIndex
+			);
+			assert_noop!(
+				StakePallet::cancel_deferred_slash(RuntimeOrigin::root(), 2, vec![0]),
+				Error::<Test>::InvalidSlashIndex
+			);
+
+			assert_ok!(StakePallet::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![0]));
+			assert_eq!(last_event(), MetaEvent::StakePallet(Event::SlashCancelled(1, 2)));
+			assert!(StakePallet::unapplied_slashes(1).is_empty());
+			assert_eq!(StakePallet::deselected_until(2), None);
+
+			roll_to(10, authors);
+
+			assert!(!events().iter().any(|e| matches!(e, Event::Slashed(..))));
+			assert_eq!(StakePallet::candidate_pool(2).unwrap().stake, 1000);
+			assert_eq!(StakePallet::delegator_state(3).unwrap().total, 1000);
+		});
+}
+
+#[test]
+fn stake_slashed_below_minimum_is_removed() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000), (4, 10_000)])
+		.with_collators(vec![(1, 1000), (2, 1000), (3, 10)])
+		.with_delegators(vec![(4, 3, 5)])
+		.build()
+		.execute_with(|| {
+			OfflineSlashFraction::set(Perbill::from_percent(20));
+			SlashDelegators::set(true);
+
+			// 3 does not author any block
+			let authors: Vec<Option<AccountId>> = vec![
+				None,
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+				Some(2u64),
+				Some(1u64),
+			];
+			roll_to(10, authors);
+
+			assert!(events().contains(&Event::Slashed(3, 2)));
+			assert!(events().contains(&Event::Slashed(4, 1)));
+
+			// the delegation fell below MinDelegatorStake
+			assert!(events().contains(&Event::DelegatorLeft(4, 4)));
+			assert!(StakePallet::delegator_state(4).is_none());
+			assert_eq!(StakePallet::unstaking(4).into_inner().values().sum::<Balance>(), 4);
+
+			// the collator fell below MinCollatorStake
+			assert!(events().contains(&Event::CollatorRemoved(3, 8)));
+			assert!(StakePallet::candidate_pool(3).is_none());
+			assert!(StakePallet::top_candidates().into_iter().all(|stake| stake.owner != 3));
+			assert_eq!(StakePallet::unstaking(3).into_inner().values().sum::<Balance>(), 8);
+		});
+}
+
+#[test]
+fn runtime_api_projections() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			assert!(StakePallet::get_projected_rewards(&1).is_zero());
+			assert!(StakePallet::get_collator_blocks().is_empty());
+
+			let authors: Vec<Option<AccountId>> = vec![None, Some(1u64), Some(1u64)];
+			roll_to(3, authors);
+
+			assert_eq!(StakePallet::get_collator_blocks(), vec![(1, 2)]);
+
+			// collator and delegator have the same stake and there is no commission
+			let issue_number = Balances::free_balance(StakePallet::account_id()) -
+				<Test as pallet_balances::Config>::ExistentialDeposit::get();
+			let projected_1 = StakePallet::get_projected_rewards(&1);
+			let projected_2 = StakePallet::get_projected_rewards(&2);
+			assert_eq!(projected_1, projected_2);
+			assert!(almost_equal(
+				projected_1 + projected_2,
+				issue_number * BLOCKS_PER_ROUND as Balance / 3,
+				Perbill::from_perthousand(1)
+			));
+			assert!(StakePallet::get_projected_rewards(&3).is_zero());
+
+			let rates = StakePallet::get_staking_rates();
+			let total_issuance = Balances::total_issuance();
+			assert_eq!(
+				rates.collator_staking_rate,
+				Perquintill::from_rational(1000, total_issuance)
+			);
+			assert_eq!(
+				rates.delegator_staking_rate,
+				Perquintill::from_rational(1000, total_issuance)
+			);
+		});
+}
+
+#[test]
+fn claim_rewards_pays_out_credited_rewards() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				StakePallet::claim_rewards(RuntimeOrigin::signed(1)),
+				Error::<Test>::RewardsNotFound
+			);
+
+			let authors: Vec<Option<AccountId>> =
+				vec![None, Some(1u64), Some(1u64), Some(1u64), Some(1u64), Some(1u64)];
+			roll_to(5, authors.clone());
+
+			// rewards are credited but not paid out
+			let reward_1 = StakePallet::rewards(1);
+			let reward_2 = StakePallet::get_claimable_rewards(&2);
+			assert!(!reward_1.is_zero());
+			assert_eq!(reward_1, reward_2);
+			assert_eq!(StakePallet::get_claimable_rewards(&1), reward_1);
+			assert_eq!(StakePallet::unclaimed_rewards(), reward_1 + reward_2);
+			assert_eq!(Balances::free_balance(1), 10_000);
+			assert_eq!(Balances::free_balance(2), 10_000);
+			assert!(!events().iter().any(|e| matches!(e, Event::Rewarded(..))));
+			assert_noop!(
+				StakePallet::claim_rewards(RuntimeOrigin::signed(3)),
+				Error::<Test>::RewardsNotFound
+			);
+
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(2)));
+			assert_eq!(last_event(), MetaEvent::StakePallet(Event::Rewarded(2, reward_2)));
+			assert_eq!(Balances::free_balance(2), 10_000 + reward_2);
+			assert!(StakePallet::rewards(2).is_zero());
+			assert_eq!(StakePallet::unclaimed_rewards(), reward_1);
+
+			// unclaimed rewards are not issued again in the next session
+			roll_to(10, authors);
+			assert_eq!(StakePallet::rewards(1), reward_1 + 500);
+			assert_eq!(StakePallet::get_claimable_rewards(&2), 500);
+			assert_eq!(StakePallet::unclaimed_rewards(), reward_1 + 1000);
+
+			assert_ok!(StakePallet::claim_rewards(RuntimeOrigin::signed(1)));
+			assert_eq!(Balances::free_balance(1), 10_000 + reward_1 + 500);
+			assert_eq!(StakePallet::unclaimed_rewards(), 500);
+		});
+}
+
+#[test]
+fn reward_dust_is_released_to_the_pot() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000), (4, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 700), (3, 1, 700), (4, 1, 600)])
+		.build()
+		.execute_with(|| {
+			let pot = StakePallet::account_id();
+			let authors: Vec<Option<AccountId>> = (0u64..=10).map(|_| Some(1u64)).collect();
+			roll_to(5, authors.clone());
+
+			// the delegators earn 933.3, 933.3 and 799.9 but are paid 933, 933 and 799 while
+			// 2667 are set aside for them
+			claim_rewards(vec![1, 2, 3, 4]);
+			assert_eq!(Balances::free_balance(2), 10_000 + 933);
+			assert_eq!(Balances::free_balance(4), 10_000 + 799);
+			assert_eq!(StakePallet::unclaimed_rewards(), 2);
+			assert_eq!(StakePallet::reward_dust(), 2 * REWARD_PER_STAKE_SCALE);
+
+			// the left over units are issued again in the next session
+			roll_to(10, authors);
+			assert!(StakePallet::reward_dust() < REWARD_PER_STAKE_SCALE);
+			claim_rewards(vec![1, 2, 3, 4]);
+			assert_eq!(
+				StakePallet::reward_dust(),
+				StakePallet::unclaimed_rewards() * REWARD_PER_STAKE_SCALE
+			);
+			assert_eq!(
+				Balances::free_balance(pot) - Balances::minimum_balance(),
+				StakePallet::unclaimed_rewards()
+			);
+		});
+}
+
+#[test]
+fn delegator_rewards_are_settled_before_stake_changes() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000), (3, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000), (3, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			let authors: Vec<Option<AccountId>> = (0u64..=10).map(|_| Some(1u64)).collect();
+			roll_to(5, authors.clone());
+
+			// the session end only increases the reward per staked unit of the collator
+			assert!(StakePallet::rewards(2).is_zero());
+			assert!(StakePallet::delegation_reward_per_stake(2, 1).is_zero());
+			let reward = StakePallet::get_claimable_rewards(&2);
+			assert!(!reward.is_zero());
+			assert_eq!(StakePallet::get_claimable_rewards(&3), reward);
+
+			// the rewards are settled with the stake they have been earned with
+			assert_ok!(StakePallet::delegator_stake_more(RuntimeOrigin::signed(2), 1, 1000));
+			assert_eq!(StakePallet::rewards(2), reward);
+			assert_eq!(
+				StakePallet::delegation_reward_per_stake(2, 1),
+				StakePallet::reward_per_stake(1)
+			);
+
+			// the new stake earns more rewards from then on
+			roll_to(10, authors);
+			let reward_2 = StakePallet::get_claimable_rewards(&2) - reward;
+			let reward_3 = StakePallet::get_claimable_rewards(&3) - reward;
+			assert!(almost_equal(reward_2, 2 * reward_3, Perbill::from_percent(1)));
+
+			// leaving settles the rewards of the removed delegation
+			assert_ok!(StakePallet::leave_delegators(RuntimeOrigin::signed(3)));
+			assert_eq!(StakePallet::rewards(3), reward + reward_3);
+			assert!(!crate::DelegationRewardPerStake::<Test>::contains_key(3, 1));
+		});
+}
+
+#[test]
+fn reward_history_keeps_most_recent_sessions() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 10_000), (2, 10_000)])
+		.with_collators(vec![(1, 1000)])
+		.with_delegators(vec![(2, 1, 1000)])
+		.build()
+		.execute_with(|| {
+			let authors: Vec<Option<AccountId>> = (0u64..=20).map(|_| Some(1u64)).collect();
+			roll_to(5, authors.clone());
+			let record = |session, amount| RewardRecord { session, collator: 1, amount };
+			assert_eq!(StakePallet::get_reward_history(&1), vec![record(0, 2000)]);
+			// the rewards of delegators are recorded once they are settled
+			assert!(StakePallet::get_reward_history(&2).is_empty());
+
+			// the history is kept after claiming
+			claim_rewards(vec![1, 2]);
+			assert_eq!(StakePallet::get_reward_history(&2), vec![record(0, 2000)]);
+			assert_eq!(StakePallet::reward_history_head(2), Some(0));
+
+			// only the rewards of the most recent sessions are kept
+			roll_to(20, authors);
+			assert_eq!(
+				StakePallet::get_reward_history(&1),
+				vec![record(1, 2500), record(2, 2500), record(3, 2500)]
+			);
+			assert_eq!(
+				crate::RewardHistory::<Test>::iter_prefix(1).count(),
+				<Test as Config>::MaxRewardHistory::get() as usize
+			);
+			assert!(StakePallet::get_reward_history(&2).is_empty());
+
+			// the rewards of a delegation are recorded per session once they are settled
+			assert_ok!(StakePallet::delegator_stake_more(RuntimeOrigin::signed(2), 1, 100));
+			claim_rewards(vec![2]);
+			assert_eq!(
+				StakePallet::get_reward_history(&2),
+				vec![record(1, 2500), record(2, 2500), record(3, 2500)]
+			);
+			assert_eq!(StakePallet::reward_history_head(2), Some(3));
+			assert!(StakePallet::get_reward_history(&3).is_empty());
+		});
+}
@@ -16,12 +16,15 @@
 
 // If you feel like getting in touch with us, you can do so at info@botlabs.org
 
-use frame_support::traits::{Currency, Get};
+use frame_support::{
+	traits::{Currency, Get},
+	BoundedVec,
+};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Saturating, Zero},
-	RuntimeDebug,
+	Perbill, Perquintill, RuntimeDebug,
 };
 use sp_staking::SessionIndex;
 use sp_std::{
@@ -127,6 +130,9 @@ where
 	/// The current status of the candidate. Indicates whether a candidate is
 	/// active or leaving the candidate pool
 	pub status: CandidateStatus,
+
+	/// The share the candidate takes off its delegators' rewards.
+	pub commission: Perbill,
 }
 
 impl<A, B, S> Candidate<A, B, S>
@@ -143,6 +149,7 @@ where
 			delegators: OrderedSet::new(),
 			total,
 			status: CandidateStatus::default(), // default active
+			commission: Perbill::zero(),
 		}
 	}
 
@@ -204,6 +211,19 @@ where
 	pub fn leave_candidates(&mut self, round: SessionIndex) {
 		self.status = CandidateStatus::Leaving(round);
 	}
+
+	pub fn set_commission(&mut self, commission: Perbill) {
+		self.commission = commission;
+	}
+
+	/// Reduces the candidate's own stake by at most `amount` and returns the
+	/// amount by which it was reduced.
+	pub fn slash_stake(&mut self, amount: B) -> B {
+		let slashed = self.stake.min(amount);
+		self.stake = self.stake.saturating_sub(slashed);
+		self.total = self.total.saturating_sub(slashed);
+		slashed
+	}
 }
 
 #[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
@@ -309,6 +329,25 @@ where
 			None
 		}
 	}
+
+	/// Returns the amount by which the delegation was reduced, which is at
+	/// most `amount`, or None if the delegation was not found.
+	pub fn slash_delegation(&mut self, collator: AccountId, amount: Balance) -> Option<Balance> {
+		let i = self
+			.delegations
+			.linear_search(&Stake::<AccountId, Balance> {
+				owner: collator,
+				amount: Balance::zero(),
+			})
+			.ok()?;
+		let slashed = self.delegations[i].amount.min(amount);
+
+		self.delegations
+			.mutate(|vec| vec[i].amount = vec[i].amount.saturating_sub(slashed));
+		self.total = self.total.saturating_sub(slashed);
+		self.delegations.sort_greatest_to_lowest();
+		Some(slashed)
+	}
 }
 
 /// The current round index and transition information.
@@ -374,6 +413,56 @@ pub struct DelegationCounter {
 	pub counter: u32,
 }
 
+/// A slash which has been reported for an offline collator and which is applied
+/// once the deferral window has passed, unless it gets cancelled.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxDelegatorsPerCandidate))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
+pub struct UnappliedSlash<AccountId, Balance, MaxDelegatorsPerCandidate: Get<u32>> {
+	/// The offending collator.
+	pub collator: AccountId,
+	/// The amount slashed from the collator's own stake.
+	pub own: Balance,
+	/// The delegators of the collator and the amounts slashed from them.
+	pub others: BoundedVec<(AccountId, Balance), MaxDelegatorsPerCandidate>,
+}
+
+/// The shares of the total issuance which back the selected collators.
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct StakingRates {
+	/// The share of the total issuance staked by the selected collators.
+	pub collator_staking_rate: Perquintill,
+	/// The share of the total issuance delegated to the selected collators.
+	pub delegator_staking_rate: Perquintill,
+}
+
+/// The reward an account received for a single session.
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RewardRecord<AccountId, Balance> {
+	/// The index of the session in which the reward was earned.
+	pub session: u32,
+	/// The collator which was authoring blocks for the reward.
+	pub collator: AccountId,
+	/// The amount of the reward.
+	pub amount: Balance,
+}
+
+/// The increase of the reward per staked unit of the delegators of a
+/// collator candidate in a single session.
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Clone, RuntimeDebug)]
+pub struct SessionRewardPerStake {
+	/// The index of the session in which the rewards were earned.
+	pub session: u32,
+	/// The increase of the reward per staked unit in the session.
+	pub increase: u128,
+	/// The reward per staked unit at the end of the session.
+	pub reward_per_stake: u128,
+}
+
 /// Internal type which is only used when a delegator is replaced by another
 /// one to delay the storage entry removal until failure cannot happen anymore.
 pub(crate) struct ReplacedDelegator<T: Config> {
@@ -386,3 +475,8 @@ pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::B
 pub type CandidateOf<T, S> = Candidate<AccountIdOf<T>, BalanceOf<T>, S>;
 pub type MaxDelegatorsPerCollator<T> = <T as Config>::MaxDelegatorsPerCollator;
 pub type StakeOf<T> = Stake<AccountIdOf<T>, BalanceOf<T>>;
+pub type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
+pub type UnappliedSlashOf<T> =
+	UnappliedSlash<AccountIdOf<T>, BalanceOf<T>, <T as Config>::MaxDelegatorsPerCollator>;
+pub type RewardRecordOf<T> = RewardRecord<AccountIdOf<T>, BalanceOf<T>>;
@@ -23,4 +23,8 @@ pub trait WeightInfo {
 	fn leave_delegators(n: u32, m: u32) -> Weight;
 	fn unlock_unstaked(u: u32) -> Weight;
 	fn set_max_candidate_stake() -> Weight;
+	fn set_commission() -> Weight;
+	fn set_auto_compound() -> Weight;
+	fn cancel_deferred_slash(s: u32) -> Weight;
+	fn claim_rewards() -> Weight;
 }
@@ -523,6 +523,98 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 			.saturating_add(Weight::from_parts(0, 0))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof: ParachainStaking CandidatePool (max_values: None, max_size: Some(1314), added: 3789, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking Round (r:1 w:0)
+	/// Proof: ParachainStaking Round (max_values: Some(1), max_size: Some(12), added: 507, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking LastCommissionChange (r:1 w:1)
+	/// Proof: ParachainStaking LastCommissionChange (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn set_commission() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1586`
+		//  Estimated: `4779`
+		// Minimum execution time: 31_417_000 picoseconds.
+		Weight::from_parts(32_160_000, 0)
+			.saturating_add(Weight::from_parts(0, 4779))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: ParachainStaking DelegatorState (r:1 w:0)
+	/// Proof: ParachainStaking DelegatorState (max_values: None, max_size: Some(105), added: 2580, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking AutoCompound (r:0 w:1)
+	/// Proof: ParachainStaking AutoCompound (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	fn set_auto_compound() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `519`
+		//  Estimated: `3570`
+		// Minimum execution time: 24_815_000 picoseconds.
+		Weight::from_parts(25_387_000, 0)
+			.saturating_add(Weight::from_parts(0, 3570))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: ParachainStaking UnappliedSlashes (r:1 w:1)
+	/// Proof: ParachainStaking UnappliedSlashes (max_values: None, max_size: Some(9166), added: 11641, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DeselectedCandidates (r:0 w:16)
+	/// Proof: ParachainStaking DeselectedCandidates (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `s` is `[1, 16]`.
+	fn cancel_deferred_slash(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `217 + s * (554 ±0)`
+		//  Estimated: `12631`
+		// Minimum execution time: 21_874_000 picoseconds.
+		Weight::from_parts(22_306_517, 0)
+			.saturating_add(Weight::from_parts(0, 12631))
+			// Standard Error: 9_412
+			.saturating_add(Weight::from_parts(3_027_455, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof: ParachainStaking DelegatorState (max_values: None, max_size: Some(105), added: 2580, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardPerStake (r:1 w:0)
+	/// Proof: ParachainStaking RewardPerStake (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationRewardPerStake (r:1 w:1)
+	/// Proof: ParachainStaking DelegationRewardPerStake (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking RewardDust (r:1 w:1)
+	/// Proof: ParachainStaking RewardDust (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardPerStakeHistory (r:1 w:0)
+	/// Proof: ParachainStaking RewardPerStakeHistory (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking Rewards (r:1 w:1)
+	/// Proof: ParachainStaking Rewards (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardHistoryHead (r:1 w:1)
+	/// Proof: ParachainStaking RewardHistoryHead (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardHistory (r:1 w:1)
+	/// Proof: ParachainStaking RewardHistory (max_values: None, max_size: Some(121), added: 2596, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking AutoCompound (r:1 w:0)
+	/// Proof: ParachainStaking AutoCompound (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof: ParachainStaking CandidatePool (max_values: None, max_size: Some(1314), added: 3789, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking MaxCollatorCandidateStake (r:1 w:0)
+	/// Proof: ParachainStaking MaxCollatorCandidateStake (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking UnclaimedRewards (r:1 w:1)
+	/// Proof: ParachainStaking UnclaimedRewards (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopCandidates (r:1 w:1)
+	/// Proof: ParachainStaking TopCandidates (max_values: Some(1), max_size: Some(769), added: 1264, mode: MaxEncodedLen)
+	fn claim_rewards() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2251`
+		//  Estimated: `6196`
+		// Minimum execution time: 121_534_000 picoseconds.
+		Weight::from_parts(124_817_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(13))
+	}
 }
 
 // For backwards compatibility and tests
@@ -1018,4 +1110,96 @@ impl crate::WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 0))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof: ParachainStaking CandidatePool (max_values: None, max_size: Some(1314), added: 3789, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking Round (r:1 w:0)
+	/// Proof: ParachainStaking Round (max_values: Some(1), max_size: Some(12), added: 507, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking LastCommissionChange (r:1 w:1)
+	/// Proof: ParachainStaking LastCommissionChange (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn set_commission() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1586`
+		//  Estimated: `4779`
+		// Minimum execution time: 31_417_000 picoseconds.
+		Weight::from_parts(32_160_000, 0)
+			.saturating_add(Weight::from_parts(0, 4779))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	/// Storage: ParachainStaking DelegatorState (r:1 w:0)
+	/// Proof: ParachainStaking DelegatorState (max_values: None, max_size: Some(105), added: 2580, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking AutoCompound (r:0 w:1)
+	/// Proof: ParachainStaking AutoCompound (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	fn set_auto_compound() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `519`
+		//  Estimated: `3570`
+		// Minimum execution time: 24_815_000 picoseconds.
+		Weight::from_parts(25_387_000, 0)
+			.saturating_add(Weight::from_parts(0, 3570))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: ParachainStaking UnappliedSlashes (r:1 w:1)
+	/// Proof: ParachainStaking UnappliedSlashes (max_values: None, max_size: Some(9166), added: 11641, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DeselectedCandidates (r:0 w:16)
+	/// Proof: ParachainStaking DeselectedCandidates (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `s` is `[1, 16]`.
+	fn cancel_deferred_slash(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `217 + s * (554 ±0)`
+		//  Estimated: `12631`
+		// Minimum execution time: 21_874_000 picoseconds.
+		Weight::from_parts(22_306_517, 0)
+			.saturating_add(Weight::from_parts(0, 12631))
+			// Standard Error: 9_412
+			.saturating_add(Weight::from_parts(3_027_455, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: ParachainStaking DelegatorState (r:1 w:1)
+	/// Proof: ParachainStaking DelegatorState (max_values: None, max_size: Some(105), added: 2580, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardPerStake (r:1 w:0)
+	/// Proof: ParachainStaking RewardPerStake (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking DelegationRewardPerStake (r:1 w:1)
+	/// Proof: ParachainStaking DelegationRewardPerStake (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: ParachainStaking RewardDust (r:1 w:1)
+	/// Proof: ParachainStaking RewardDust (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardPerStakeHistory (r:1 w:0)
+	/// Proof: ParachainStaking RewardPerStakeHistory (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking Rewards (r:1 w:1)
+	/// Proof: ParachainStaking Rewards (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardHistoryHead (r:1 w:1)
+	/// Proof: ParachainStaking RewardHistoryHead (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking RewardHistory (r:1 w:1)
+	/// Proof: ParachainStaking RewardHistory (max_values: None, max_size: Some(121), added: 2596, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking AutoCompound (r:1 w:0)
+	/// Proof: ParachainStaking AutoCompound (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking CandidatePool (r:1 w:1)
+	/// Proof: ParachainStaking CandidatePool (max_values: None, max_size: Some(1314), added: 3789, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking MaxCollatorCandidateStake (r:1 w:0)
+	/// Proof: ParachainStaking MaxCollatorCandidateStake (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking UnclaimedRewards (r:1 w:1)
+	/// Proof: ParachainStaking UnclaimedRewards (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:1 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: ParachainStaking TopCandidates (r:1 w:1)
+	/// Proof: ParachainStaking TopCandidates (max_values: Some(1), max_size: Some(769), added: 1264, mode: MaxEncodedLen)
+	fn claim_rewards() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2251`
+		//  Estimated: `6196`
+		// Minimum execution time: 121_534_000 picoseconds.
+		Weight::from_parts(124_817_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(RocksDbWeight::get().reads(18))
+			.saturating_add(RocksDbWeight::get().writes(13))
+	}
 }
@@ -0,0 +1,21 @@
+//! Runtime API definition for the xc-asset-config pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+use xcm::VersionedMultiLocation;
+
+sp_api::decl_runtime_apis! {
+	pub trait XcAssetConfigApi<AssetId>
+	where
+		AssetId: Codec,
+	{
+		/// All assets which can be received as reserve assets, together with
+		/// the location trusted as their reserve.
+		fn asset_reserves() -> Vec<(AssetId, VersionedMultiLocation)>;
+		/// The location trusted as reserve of an asset, `None` if the asset
+		/// can't be received as reserve asset.
+		fn asset_reserve(asset_id: AssetId) -> Option<VersionedMultiLocation>;
+	}
+}
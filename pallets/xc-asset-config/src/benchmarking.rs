@@ -22,9 +22,10 @@ use super::*;
 use crate::Pallet as XcAssetConfig;
 
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::Hooks;
 use frame_system::RawOrigin;
 use sp_std::boxed::Box;
-use xcm::v3::MultiLocation;
+use xcm::v3::{Junction::Parachain, Junctions::X1, MultiLocation};
 
 benchmarks! {
 
@@ -94,6 +95,105 @@ benchmarks! {
 		assert!(!AssetLocationUnitsPerSecond::<T>::contains_key(&asset_location.into_versioned()));
 	}
 
+	register_foreign_asset {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+		let units = 123;
+		let metadata = foreign_asset_metadata::<T>();
+
+	}: _(RawOrigin::Root, Box::new(asset_location.clone().into_versioned()), asset_id, metadata, Some(units))
+	verify {
+		assert_eq!(AssetIdToLocation::<T>::get(&asset_id), Some(asset_location.clone().into_versioned()));
+		assert_eq!(AssetLocationUnitsPerSecond::<T>::get(&asset_location.into_versioned()), Some(units));
+	}
+
+	deregister_foreign_asset {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+		let units = 123;
+
+		XcAssetConfig::<T>::register_foreign_asset(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), asset_id, foreign_asset_metadata::<T>(), Some(units))?;
+
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(!AssetLocationToId::<T>::contains_key(&asset_location.clone().into_versioned()));
+		assert!(!AssetIdToLocation::<T>::contains_key(asset_id));
+		assert!(!AssetLocationUnitsPerSecond::<T>::contains_key(&asset_location.into_versioned()));
+	}
+
+	set_asset_reserve {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+
+		XcAssetConfig::<T>::register_asset_location(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), asset_id)?;
+
+	}: _(RawOrigin::Root, asset_id, Box::new(asset_location.clone().into_versioned()))
+	verify {
+		assert_eq!(AssetReserves::<T>::get(&asset_id), Some(asset_location.into_versioned()));
+	}
+
+	remove_asset_reserve {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+
+		XcAssetConfig::<T>::register_asset_location(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), asset_id)?;
+		XcAssetConfig::<T>::set_asset_reserve(RawOrigin::Root.into(), asset_id, Box::new(asset_location.into_versioned()))?;
+
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(!AssetReserves::<T>::contains_key(asset_id));
+	}
+
+	enable_pool_price {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+		let units = 123;
+
+		XcAssetConfig::<T>::register_asset_location(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), asset_id)?;
+		XcAssetConfig::<T>::set_asset_units_per_second(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), units)?;
+
+	}: _(RawOrigin::Root, Box::new(asset_location.clone().into_versioned()))
+	verify {
+		assert!(AssetLocationPoolPrice::<T>::contains_key(&asset_location.into_versioned()));
+	}
+
+	disable_pool_price {
+		let asset_location = MultiLocation::parent();
+		let asset_id = T::AssetId::default();
+		let units = 123;
+
+		XcAssetConfig::<T>::register_asset_location(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), asset_id)?;
+		XcAssetConfig::<T>::set_asset_units_per_second(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()), units)?;
+		XcAssetConfig::<T>::enable_pool_price(RawOrigin::Root.into(), Box::new(asset_location.clone().into_versioned()))?;
+
+	}: _(RawOrigin::Root, Box::new(asset_location.clone().into_versioned()))
+	verify {
+		assert!(!AssetLocationPoolPrice::<T>::contains_key(&asset_location.into_versioned()));
+	}
+
+	update_pool_prices {
+		let n in 0 .. T::MaxPoolPricedAssets::get();
+
+		for i in 0..n {
+			let asset_location = MultiLocation::new(1, X1(Parachain(i))).into_versioned();
+			AssetLocationUnitsPerSecond::<T>::insert(&asset_location, 123);
+			AssetLocationPoolPrice::<T>::insert(&asset_location, PoolPrice { units_per_second: 0, updated_at: 0_u32.into() });
+		}
+
+	}: {
+		XcAssetConfig::<T>::on_initialize(1_u32.into());
+	}
+
+}
+
+fn foreign_asset_metadata<T: Config>() -> ForeignAssetMetadata<T::Balance> {
+	ForeignAssetMetadata {
+		name: b"Relay Token".to_vec(),
+		symbol: b"RLY".to_vec(),
+		decimals: 12,
+		min_balance: 1_u32.into(),
+		is_sufficient: true,
+	}
 }
 
 #[cfg(test)]
@@ -41,6 +41,15 @@
 //! - `change_existing_asset_location` - changes the remote location of an existing local asset Id
 //! - `remove_payment_asset` - removes asset from the set of supported payment assets
 //! - `remove_asset` - removes all information related to this asset
+//! - `register_foreign_asset` - creates the local asset with its metadata and registers its remote
+//!   location in one go
+//! - `deregister_foreign_asset` - removes all information related to the asset and destroys it
+//! - `set_asset_reserve` - sets the location trusted as reserve of the asset
+//! - `remove_asset_reserve` - removes the trusted reserve, the asset can't be received as reserve
+//!   asset anymore
+//! - `enable_pool_price` - prices execution paid with the asset from its time-weighted spot price,
+//!   whose samples are bounded around the price so far
+//! - `disable_pool_price` - prices execution paid with the asset with its `units per second` only
 //!
 //! User is encouraged to refer to specific function implementations for more comprehensive
 //! documentation.
@@ -54,13 +63,19 @@
 //! `ExecutionPaymentRate` interface for fetching `units per second` if asset is supported payment
 //! asset
 //! - `get_units_per_second`
+//!
+//! `TrustedReserves` filter for the XCM executor, trusting only the stored reserve of each asset
+//!
+//! `PoolPaymentRate` interface for fetching the time-weighted `units per second` of assets priced
+//! from their pool
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::pallet;
+use frame_support::{pallet, traits::ContainsPair};
 pub use pallet::*;
 use sp_runtime::traits::Convert;
 use sp_std::{borrow::Borrow, marker::PhantomData};
+use xcm::latest::{AssetId::Concrete, MultiAsset};
 use xcm_executor::traits::Convert as XCMConvert;
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
@@ -84,10 +99,17 @@ pub mod pallet {
 	use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
 	use frame_system::pallet_prelude::*;
 	// use parity_scale_codec::HasCompact;
-	use sp_std::boxed::Box;
-	use xcm::{v3::MultiLocation, VersionedMultiLocation};
-
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	use sp_runtime::{
+		traits::{AtLeast32BitUnsigned, Saturating, Zero},
+		Perbill,
+	};
+	use sp_std::{boxed::Box, vec::Vec};
+	use xcm::{
+		v3::{Junction::Parachain, Junctions::X1, MultiLocation},
+		VersionedMultiLocation,
+	};
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -111,6 +133,57 @@ pub mod pallet {
 		fn get_units_per_second(asset_location: MultiLocation) -> Option<u128>;
 	}
 
+	/// Provides the current price of cross-chain assets, e.g. from a DEX pool with the native
+	/// token.
+	pub trait AssetSpotPrice {
+		/// Returns the amount of the asset currently worth one second of execution, in the same
+		/// unit as `units per second`, or `None` if there is no usable price.
+		fn spot_units_per_second(asset_location: &MultiLocation) -> Option<u128>;
+	}
+
+	impl AssetSpotPrice for () {
+		fn spot_units_per_second(_asset_location: &MultiLocation) -> Option<u128> {
+			None
+		}
+	}
+
+	/// Time-weighted `units per second` of an asset priced from its pool.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct PoolPrice<BlockNumber> {
+		/// Exponential moving average of the sampled spot prices, zero until the first sample.
+		pub units_per_second: u128,
+		/// The block of the last sample.
+		pub updated_at: BlockNumber,
+	}
+
+	/// Metadata of a foreign asset, used to create the local asset.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ForeignAssetMetadata<Balance> {
+		pub name: Vec<u8>,
+		pub symbol: Vec<u8>,
+		pub decimals: u8,
+		/// The minimum balance of an account holding the asset.
+		pub min_balance: Balance,
+		/// Whether the asset alone can keep an account alive.
+		pub is_sufficient: bool,
+	}
+
+	/// Creates and destroys the local assets of foreign asset locations.
+	pub trait ForeignAssetRegistrar<AssetId, Balance> {
+		/// Create the local asset with the given metadata.
+		fn create_asset(
+			asset_id: AssetId,
+			metadata: &ForeignAssetMetadata<Balance>,
+		) -> DispatchResult;
+
+		/// Destroy the local asset, together with its metadata.
+		fn destroy_asset(asset_id: AssetId) -> DispatchResult;
+
+		/// Weight of `destroy_asset` for an asset with the most holders and approvals it
+		/// removes, which isn't part of the benchmarked weight of `deregister_foreign_asset`.
+		fn destroy_asset_weight() -> Weight;
+	}
+
 	impl<T: Config> XcAssetLocation<T::AssetId> for Pallet<T> {
 		fn get_xc_asset_location(asset_id: T::AssetId) -> Option<MultiLocation> {
 			if asset_id == T::NativeAssetId::get() {
@@ -152,6 +225,38 @@ pub mod pallet {
 		/// Should most likely be root.
 		type ManagerOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
 
+		/// The balance type of the local assets.
+		type Balance: Member + Parameter + AtLeast32BitUnsigned + Copy + MaxEncodedLen;
+
+		/// Creates and destroys the local assets of registered foreign assets.
+		type ForeignAssetRegistrar: ForeignAssetRegistrar<Self::AssetId, Self::Balance>;
+
+		/// Spot price of the assets priced from their pool, sampled every block.
+		type SpotPrice: AssetSpotPrice;
+
+		/// Weight of sampling the `SpotPrice` of one asset.
+		#[pallet::constant]
+		type SpotPriceWeight: Get<Weight>;
+
+		/// Maximum number of assets priced from their pool.
+		#[pallet::constant]
+		type MaxPoolPricedAssets: Get<u32>;
+
+		/// Weight of a new spot price sample in the time-weighted price.
+		#[pallet::constant]
+		type PoolPriceSmoothing: Get<Perbill>;
+
+		/// Number of blocks after which a time-weighted price without new sample isn't used
+		/// anymore.
+		#[pallet::constant]
+		type MaxPoolPriceAge: Get<Self::BlockNumber>;
+
+		/// Maximum deviation of a spot price sample from the time-weighted price, or from the
+		/// `units per second` of its asset for the first sample. Samples deviating more are
+		/// clamped.
+		#[pallet::constant]
+		type MaxPoolPriceDeviation: Get<Perbill>;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -165,6 +270,16 @@ pub mod pallet {
 		MultiLocationNotSupported,
 		/// Asset is not supported as payment currency.
 		NativeAssetRelated,
+		/// Asset has no trusted reserve.
+		AssetReserveDoesNotExist,
+		/// Asset is not supported as payment asset.
+		PaymentAssetDoesNotExist,
+		/// Asset is already priced from its pool.
+		PoolPriceAlreadyEnabled,
+		/// Asset is not priced from its pool.
+		PoolPriceNotEnabled,
+		/// Too many assets are priced from their pool.
+		TooManyPoolPricedAssets,
 	}
 
 	#[allow(clippy::large_enum_variant)]
@@ -185,6 +300,14 @@ pub mod pallet {
 		SupportedAssetRemoved { asset_location: VersionedMultiLocation },
 		/// Removed all information related to an asset Id
 		AssetRemoved { asset_location: VersionedMultiLocation, asset_id: T::AssetId },
+		/// Set the location trusted as reserve for an asset Id
+		AssetReserveSet { asset_id: T::AssetId, reserve_location: VersionedMultiLocation },
+		/// Removed the trusted reserve of an asset Id
+		AssetReserveRemoved { asset_id: T::AssetId },
+		/// Execution paid with an asset is priced from its pool
+		PoolPriceEnabled { asset_location: VersionedMultiLocation },
+		/// Execution paid with an asset isn't priced from its pool anymore
+		PoolPriceDisabled { asset_location: VersionedMultiLocation },
 	}
 
 	/// Mapping from an asset id to asset type.
@@ -213,11 +336,67 @@ pub mod pallet {
 	pub type AssetLocationUnitsPerSecond<T: Config> =
 		StorageMap<_, Twox64Concat, VersionedMultiLocation, u128>;
 
+	/// Mapping from an asset id to the location trusted as its reserve.
+	///
+	/// Reserve asset transfers of an asset are only accepted from this location. Assets without
+	/// an entry can't be received as reserve assets.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_reserve)]
+	pub type AssetReserves<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, VersionedMultiLocation>;
+
+	/// Time-weighted price of the payment assets priced from their pool.
+	///
+	/// Only assets with an entry are priced from their pool, the others only with their
+	/// `units per second`.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_location_pool_price)]
+	pub type AssetLocationPoolPrice<T: Config> =
+		CountedStorageMap<_, Twox64Concat, VersionedMultiLocation, PoolPrice<T::BlockNumber>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let prices = AssetLocationPoolPrice::<T>::iter().collect::<Vec<_>>();
+			let priced_assets = prices.len() as u32;
+
+			for (asset_location, mut price) in prices {
+				let spot_units_per_second = MultiLocation::try_from(asset_location.clone())
+					.ok()
+					.and_then(|location| T::SpotPrice::spot_units_per_second(&location));
+				// Without sample, the price gets outdated and isn't used anymore
+				let Some(spot_units_per_second) = spot_units_per_second else { continue };
+
+				// A manipulated spot price only moves the price by a bounded step per block
+				let anchor = if price.units_per_second.is_zero() {
+					AssetLocationUnitsPerSecond::<T>::get(&asset_location)
+						.unwrap_or(spot_units_per_second)
+				} else {
+					price.units_per_second
+				};
+				let spot_units_per_second = Self::bounded_spot(anchor, spot_units_per_second);
+
+				price.units_per_second =
+					Self::smoothed_units_per_second(price.units_per_second, spot_units_per_second);
+				price.updated_at = now;
+				AssetLocationPoolPrice::<T>::insert(asset_location, price);
+			}
+
+			// the units per second of an asset are read for its first sample
+			let sample_weight =
+				T::SpotPriceWeight::get().saturating_add(T::DbWeight::get().reads(1));
+			T::WeightInfo::update_pool_prices(priced_assets)
+				.saturating_add(sample_weight.saturating_mul(priced_assets.into()))
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Register new asset location to asset Id mapping.
 		///
-		/// This makes the asset eligible for XCM interaction.
+		/// This makes the asset eligible for XCM interaction. Its reserve is the default one of
+		/// its location, see [`Pallet::default_reserve_of`], and can be changed with
+		/// `set_asset_reserve`.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::register_asset_location())]
 		pub fn register_asset_location(
@@ -227,27 +406,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ManagerOrigin::ensure_origin(origin)?;
 
-			ensure!(asset_id != T::NativeAssetId::get(), Error::<T>::NativeAssetRelated);
-
-			// Ensure such an assetId does not exist
-			ensure!(
-				!AssetIdToLocation::<T>::contains_key(asset_id),
-				Error::<T>::AssetAlreadyRegistered
-			);
-
-			let v3_asset_loc = MultiLocation::try_from(*asset_location)
-				.map_err(|_| Error::<T>::MultiLocationNotSupported)?;
-			let asset_location = VersionedMultiLocation::V3(v3_asset_loc);
-
-			ensure!(
-				asset_location != T::NativeAssetLocation::get().into_versioned(),
-				Error::<T>::NativeAssetRelated
-			);
-
-			AssetIdToLocation::<T>::insert(asset_id, asset_location.clone());
-			AssetLocationToId::<T>::insert(&asset_location, asset_id);
-
-			Self::deposit_event(Event::AssetRegistered { asset_location, asset_id });
+			Self::do_register_asset_location(*asset_location, asset_id)?;
 			Ok(())
 		}
 
@@ -276,14 +435,13 @@ pub mod pallet {
 				Error::<T>::AssetDoesNotExist
 			);
 
-			AssetLocationUnitsPerSecond::<T>::insert(&asset_location, units_per_second);
-
-			Self::deposit_event(Event::UnitsPerSecondChanged { asset_location, units_per_second });
+			Self::do_set_asset_units_per_second(asset_location, units_per_second);
 			Ok(())
 		}
 
 		/// Change the xcm type mapping for a given asset Id.
-		/// The new asset type will inherit old `units per second` value.
+		/// The new asset type will inherit old `units per second` value. The reserve of the asset
+		/// is reset to the default one of the new location, or removed if there is none.
 		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::change_existing_asset_location())]
 		pub fn change_existing_asset_location(
@@ -318,6 +476,12 @@ pub mod pallet {
 			if let Some(units) = AssetLocationUnitsPerSecond::<T>::take(&previous_asset_location) {
 				AssetLocationUnitsPerSecond::<T>::insert(&new_asset_location, units);
 			}
+			if let Some(price) = AssetLocationPoolPrice::<T>::take(&previous_asset_location) {
+				AssetLocationPoolPrice::<T>::insert(&new_asset_location, price);
+			}
+
+			// The reserve of the previous location may not be one of the new location
+			Self::do_set_default_asset_reserve(asset_id, &v3_asset_loc);
 
 			Self::deposit_event(Event::AssetLocationChanged {
 				previous_asset_location,
@@ -349,6 +513,7 @@ pub mod pallet {
 			);
 
 			AssetLocationUnitsPerSecond::<T>::remove(&asset_location);
+			AssetLocationPoolPrice::<T>::remove(&asset_location);
 
 			Self::deposit_event(Event::SupportedAssetRemoved { asset_location });
 			Ok(())
@@ -360,6 +525,192 @@ pub mod pallet {
 		pub fn remove_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
 			T::ManagerOrigin::ensure_origin(origin)?;
 
+			Self::do_remove_asset(asset_id)
+		}
+
+		/// Create a local asset for a foreign asset and register its location.
+		///
+		/// The asset is created with the given metadata and, if `units_per_second` is set,
+		/// registered as payment asset. Either all of it succeeds, or nothing is changed.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::register_foreign_asset())]
+		#[transactional]
+		pub fn register_foreign_asset(
+			origin: OriginFor<T>,
+			asset_location: Box<VersionedMultiLocation>,
+			asset_id: T::AssetId,
+			metadata: ForeignAssetMetadata<T::Balance>,
+			units_per_second: Option<u128>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let asset_location = Self::do_register_asset_location(*asset_location, asset_id)?;
+			T::ForeignAssetRegistrar::create_asset(asset_id, &metadata)?;
+			if let Some(units_per_second) = units_per_second {
+				Self::do_set_asset_units_per_second(asset_location, units_per_second);
+			}
+			Ok(())
+		}
+
+		/// Removes all information related to a foreign asset and destroys its local asset.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::deregister_foreign_asset()
+				.saturating_add(T::ForeignAssetRegistrar::destroy_asset_weight())
+		)]
+		#[transactional]
+		pub fn deregister_foreign_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Self::do_remove_asset(asset_id)?;
+			T::ForeignAssetRegistrar::destroy_asset(asset_id)
+		}
+
+		/// Set the location trusted as reserve of a registered asset.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_asset_reserve())]
+		pub fn set_asset_reserve(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			reserve_location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			ensure!(asset_id != T::NativeAssetId::get(), Error::<T>::NativeAssetRelated);
+			ensure!(AssetIdToLocation::<T>::contains_key(asset_id), Error::<T>::AssetDoesNotExist);
+
+			let v3_reserve_loc = MultiLocation::try_from(*reserve_location)
+				.map_err(|_| Error::<T>::MultiLocationNotSupported)?;
+
+			Self::do_set_asset_reserve(asset_id, v3_reserve_loc);
+			Ok(())
+		}
+
+		/// Remove the trusted reserve of an asset.
+		///
+		/// The asset can't be received as reserve asset until a new reserve is set.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::remove_asset_reserve())]
+		pub fn remove_asset_reserve(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				AssetReserves::<T>::take(asset_id).is_some(),
+				Error::<T>::AssetReserveDoesNotExist
+			);
+
+			Self::deposit_event(Event::AssetReserveRemoved { asset_id });
+			Ok(())
+		}
+
+		/// Price execution paid with a payment asset from its time-weighted spot price.
+		///
+		/// The price is only used while it is sampled, the `units per second` of the asset are
+		/// used otherwise. Each sample is clamped to `MaxPoolPriceDeviation` around the price so
+		/// far, starting from the `units per second`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::enable_pool_price())]
+		pub fn enable_pool_price(
+			origin: OriginFor<T>,
+			asset_location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let v3_asset_loc = MultiLocation::try_from(*asset_location)
+				.map_err(|_| Error::<T>::MultiLocationNotSupported)?;
+			let asset_location = VersionedMultiLocation::V3(v3_asset_loc);
+
+			ensure!(
+				AssetLocationUnitsPerSecond::<T>::contains_key(&asset_location),
+				Error::<T>::PaymentAssetDoesNotExist
+			);
+			ensure!(
+				!AssetLocationPoolPrice::<T>::contains_key(&asset_location),
+				Error::<T>::PoolPriceAlreadyEnabled
+			);
+			ensure!(
+				AssetLocationPoolPrice::<T>::count() < T::MaxPoolPricedAssets::get(),
+				Error::<T>::TooManyPoolPricedAssets
+			);
+
+			AssetLocationPoolPrice::<T>::insert(
+				&asset_location,
+				PoolPrice { units_per_second: 0, updated_at: Zero::zero() },
+			);
+
+			Self::deposit_event(Event::PoolPriceEnabled { asset_location });
+			Ok(())
+		}
+
+		/// Price execution paid with an asset with its `units per second` only.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::disable_pool_price())]
+		pub fn disable_pool_price(
+			origin: OriginFor<T>,
+			asset_location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let v3_asset_loc = MultiLocation::try_from(*asset_location)
+				.map_err(|_| Error::<T>::MultiLocationNotSupported)?;
+			let asset_location = VersionedMultiLocation::V3(v3_asset_loc);
+
+			ensure!(
+				AssetLocationPoolPrice::<T>::take(&asset_location).is_some(),
+				Error::<T>::PoolPriceNotEnabled
+			);
+
+			Self::deposit_event(Event::PoolPriceDisabled { asset_location });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn do_register_asset_location(
+			asset_location: VersionedMultiLocation,
+			asset_id: T::AssetId,
+		) -> Result<VersionedMultiLocation, DispatchError> {
+			ensure!(asset_id != T::NativeAssetId::get(), Error::<T>::NativeAssetRelated);
+
+			// Ensure such an assetId does not exist
+			ensure!(
+				!AssetIdToLocation::<T>::contains_key(asset_id),
+				Error::<T>::AssetAlreadyRegistered
+			);
+
+			let v3_asset_loc = MultiLocation::try_from(asset_location)
+				.map_err(|_| Error::<T>::MultiLocationNotSupported)?;
+			let asset_location = VersionedMultiLocation::V3(v3_asset_loc);
+
+			ensure!(
+				asset_location != T::NativeAssetLocation::get().into_versioned(),
+				Error::<T>::NativeAssetRelated
+			);
+
+			AssetIdToLocation::<T>::insert(asset_id, asset_location.clone());
+			AssetLocationToId::<T>::insert(&asset_location, asset_id);
+			Self::do_set_default_asset_reserve(asset_id, &v3_asset_loc);
+
+			Self::deposit_event(Event::AssetRegistered {
+				asset_location: asset_location.clone(),
+				asset_id,
+			});
+			Ok(asset_location)
+		}
+
+		fn do_set_asset_units_per_second(
+			asset_location: VersionedMultiLocation,
+			units_per_second: u128,
+		) {
+			AssetLocationUnitsPerSecond::<T>::insert(&asset_location, units_per_second);
+
+			Self::deposit_event(Event::UnitsPerSecondChanged { asset_location, units_per_second });
+		}
+
+		fn do_remove_asset(asset_id: T::AssetId) -> DispatchResult {
 			ensure!(asset_id != T::NativeAssetId::get(), Error::<T>::NativeAssetRelated);
 
 			let asset_location =
@@ -368,10 +719,124 @@ pub mod pallet {
 			AssetIdToLocation::<T>::remove(asset_id);
 			AssetLocationToId::<T>::remove(&asset_location);
 			AssetLocationUnitsPerSecond::<T>::remove(&asset_location);
+			AssetLocationPoolPrice::<T>::remove(&asset_location);
+			AssetReserves::<T>::remove(asset_id);
 
 			Self::deposit_event(Event::AssetRemoved { asset_id, asset_location });
 			Ok(())
 		}
+
+		pub(crate) fn do_set_asset_reserve(asset_id: T::AssetId, reserve_location: MultiLocation) {
+			let reserve_location = VersionedMultiLocation::V3(reserve_location);
+			AssetReserves::<T>::insert(asset_id, reserve_location.clone());
+
+			Self::deposit_event(Event::AssetReserveSet { asset_id, reserve_location });
+		}
+
+		/// Sets the default reserve of `asset_location` as reserve of the asset, or removes its
+		/// reserve if there is no default one.
+		fn do_set_default_asset_reserve(asset_id: T::AssetId, asset_location: &MultiLocation) {
+			match Self::default_reserve_of(asset_location) {
+				Some(reserve_location) => Self::do_set_asset_reserve(asset_id, reserve_location),
+				None =>
+					if AssetReserves::<T>::take(asset_id).is_some() {
+						Self::deposit_event(Event::AssetReserveRemoved { asset_id });
+					},
+			}
+		}
+
+		/// The reserve of an asset location as assumed before reserves were stored, the sibling
+		/// parachain for its assets and the relay chain for everything else of the parent.
+		pub fn default_reserve_of(asset_location: &MultiLocation) -> Option<MultiLocation> {
+			match (asset_location.parents, asset_location.first_interior()) {
+				// sibling parachain
+				(1, Some(Parachain(id))) => Some(MultiLocation::new(1, X1(Parachain(*id)))),
+				// relay chain
+				(1, _) => Some(MultiLocation::parent()),
+				_ => None,
+			}
+		}
+
+		/// All assets with a trusted reserve, and their reserve location.
+		pub fn asset_reserves() -> Vec<(T::AssetId, VersionedMultiLocation)> {
+			AssetReserves::<T>::iter().collect()
+		}
+
+		/// Moves the time-weighted `units_per_second` towards the sampled spot price.
+		pub(crate) fn smoothed_units_per_second(units_per_second: u128, spot: u128) -> u128 {
+			let smoothing = T::PoolPriceSmoothing::get();
+			if units_per_second.is_zero() {
+				spot
+			} else if spot >= units_per_second {
+				units_per_second.saturating_add(smoothing * (spot - units_per_second))
+			} else {
+				units_per_second.saturating_sub(smoothing * (units_per_second - spot))
+			}
+		}
+
+		// Clamps a spot price sample to `MaxPoolPriceDeviation` around `anchor`
+		pub(crate) fn bounded_spot(anchor: u128, spot: u128) -> u128 {
+			let max_deviation = T::MaxPoolPriceDeviation::get() * anchor;
+			let bounded = spot
+				.clamp(anchor.saturating_sub(max_deviation), anchor.saturating_add(max_deviation));
+			if bounded != spot {
+				log::debug!(
+					target: "xcm::weight",
+					"spot price {:?} clamped to {:?} around {:?}",
+					spot, bounded, anchor,
+				);
+			}
+			bounded
+		}
+
+		/// The time-weighted `units per second` of an asset priced from its pool, if it was
+		/// sampled within `MaxPoolPriceAge` and the asset is still a payment asset.
+		pub fn pool_units_per_second(asset_location: MultiLocation) -> Option<u128> {
+			let asset_location = asset_location.into_versioned();
+			let price = AssetLocationPoolPrice::<T>::get(&asset_location)?;
+			AssetLocationUnitsPerSecond::<T>::get(&asset_location)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if price.units_per_second.is_zero() ||
+				now.saturating_sub(price.updated_at) > T::MaxPoolPriceAge::get()
+			{
+				return None
+			}
+
+			Some(price.units_per_second)
+		}
+	}
+}
+
+/// Payment rate of the assets priced from their pool, while their time-weighted price is usable.
+///
+/// Meant to be used before the `units per second` of the assets, which are used otherwise.
+pub struct PoolPaymentRate<T: Config>(PhantomData<T>);
+
+impl<T: Config> ExecutionPaymentRate for PoolPaymentRate<T> {
+	fn get_units_per_second(asset_location: MultiLocation) -> Option<u128> {
+		Pallet::<T>::pool_units_per_second(asset_location)
+	}
+}
+
+/// Trusts only the stored reserve location of a registered asset as its reserve.
+pub struct TrustedReserves<T: Config>(PhantomData<T>);
+
+impl<T: Config> ContainsPair<MultiAsset, MultiLocation> for TrustedReserves<T> {
+	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		let reserve_location = match &asset.id {
+			Concrete(location) => Pallet::<T>::get_asset_id(*location)
+				.and_then(AssetReserves::<T>::get)
+				.and_then(|reserve| MultiLocation::try_from(reserve).ok()),
+			_ => None,
+		};
+
+		log::trace!(
+			target: "xcm::reserve",
+			"TrustedReserves origin: {:?}, reserve_location: {:?}",
+			origin, reserve_location,
+		);
+		reserve_location.as_ref() == Some(origin)
 	}
 }
 
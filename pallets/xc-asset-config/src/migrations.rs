@@ -24,7 +24,7 @@ use frame_support::{
 	traits::{Get, OnRuntimeUpgrade},
 };
 use sp_std::{marker::PhantomData, vec::Vec};
-use xcm::IntoVersion;
+use xcm::{latest::MultiLocation, IntoVersion};
 
 pub struct MigrationXcmV3<T: Config>(PhantomData<T>);
 impl<T: Config> OnRuntimeUpgrade for MigrationXcmV3<T> {
@@ -121,3 +121,58 @@ impl<T: Config> OnRuntimeUpgrade for MigrationXcmV3<T> {
 		Ok(())
 	}
 }
+
+/// Seeds the trusted reserves of all registered assets with the reserve they were implicitly
+/// trusted with before, see [`Pallet::default_reserve_of`].
+pub struct MigrationAssetReserves<T: Config>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for MigrationAssetReserves<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let version = Pallet::<T>::on_chain_storage_version();
+		let mut consumed_weight = Weight::zero();
+		if version >= 3 {
+			return consumed_weight
+		}
+
+		for (asset_id, asset_location) in AssetIdToLocation::<T>::iter() {
+			consumed_weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+			let reserve_location = MultiLocation::try_from(asset_location)
+				.ok()
+				.and_then(|location| Pallet::<T>::default_reserve_of(&location));
+			if let Some(reserve_location) = reserve_location {
+				consumed_weight.saturating_accrue(T::DbWeight::get().writes(2));
+				Pallet::<T>::do_set_asset_reserve(asset_id, reserve_location);
+			} else {
+				log::warn!("No default reserve for asset Id: {:?}", asset_id);
+			}
+		}
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		consumed_weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+		consumed_weight
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		assert_eq!(Pallet::<T>::on_chain_storage_version(), 2);
+
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		assert_eq!(Pallet::<T>::on_chain_storage_version(), 3);
+
+		for (asset_id, asset_location) in AssetIdToLocation::<T>::iter() {
+			let location = MultiLocation::try_from(asset_location)
+				.map_err(|_| "Cannot convert asset location")?;
+			assert_eq!(
+				AssetReserves::<T>::get(asset_id).and_then(|r| MultiLocation::try_from(r).ok()),
+				Pallet::<T>::default_reserve_of(&location)
+			);
+		}
+
+		Ok(())
+	}
+}
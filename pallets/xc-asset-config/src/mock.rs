@@ -16,18 +16,28 @@
 // You should have received a copy of the GNU General Public License
 // along with Astar. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{self as pallet_xc_asset_config};
+use crate::{
+	self as pallet_xc_asset_config, AssetSpotPrice, ForeignAssetMetadata, ForeignAssetRegistrar,
+};
 use xcm::latest::prelude::{Junction::GeneralKey, MultiLocation, X1};
 
-use frame_support::{construct_runtime, parameter_types, weights::Weight};
+use frame_support::{
+	construct_runtime,
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{fungibles, AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64},
+	weights::Weight,
+};
 use sp_core::H256;
 
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 
 type BlockNumber = u64;
 type Balance = u128;
@@ -37,6 +47,7 @@ type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 const EXISTENTIAL_DEPOSIT: Balance = 2;
+pub const DESTROY_ASSET_WEIGHT: Weight = Weight::from_parts(1_000_000, 0);
 
 construct_runtime!(
 	pub struct Test
@@ -47,6 +58,7 @@ construct_runtime!(
 	{
 		System: frame_system,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		XcAssetConfig: pallet_xc_asset_config,
 	}
 );
@@ -108,18 +120,109 @@ impl pallet_balances::Config for Test {
 
 type AssetId = u128;
 
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<5>;
+	type AssetIdParameter = AssetId;
+	type CallbackHandle = ();
+}
+
+pub const ASSET_OWNER: AccountId = 100;
+
+pub struct AssetsRegistrar;
+
+impl ForeignAssetRegistrar<AssetId, Balance> for AssetsRegistrar {
+	fn create_asset(asset_id: AssetId, metadata: &ForeignAssetMetadata<Balance>) -> DispatchResult {
+		<Assets as fungibles::Create<AccountId>>::create(
+			asset_id,
+			ASSET_OWNER,
+			metadata.is_sufficient,
+			metadata.min_balance,
+		)?;
+		Assets::force_set_metadata(
+			RawOrigin::Root.into(),
+			asset_id,
+			metadata.name.clone(),
+			metadata.symbol.clone(),
+			metadata.decimals,
+			false,
+		)
+	}
+
+	fn destroy_asset(asset_id: AssetId) -> DispatchResult {
+		<Assets as fungibles::Destroy<AccountId>>::start_destroy(asset_id, None)?;
+		<Assets as fungibles::Destroy<AccountId>>::destroy_accounts(asset_id, 5)?;
+		<Assets as fungibles::Destroy<AccountId>>::destroy_approvals(asset_id, 5)?;
+		<Assets as fungibles::Destroy<AccountId>>::finish_destroy(asset_id)
+	}
+
+	fn destroy_asset_weight() -> Weight {
+		DESTROY_ASSET_WEIGHT
+	}
+}
+
 parameter_types! {
 	pub const GetNativeAssetId: AssetId = 0;
 	pub SelfReserveLocation: MultiLocation =
 		MultiLocation::new(0, X1(GeneralKey { data: [0; 32], length: 2 }));
 }
 
+thread_local! {
+	static SPOT_PRICES: RefCell<BTreeMap<MultiLocation, u128>> = RefCell::new(BTreeMap::new());
+}
+
+/// Spot prices set by the tests.
+pub struct MockSpotPrice;
+
+impl MockSpotPrice {
+	pub fn set(asset_location: MultiLocation, units_per_second: Option<u128>) {
+		SPOT_PRICES.with(|prices| match units_per_second {
+			Some(units_per_second) => prices.borrow_mut().insert(asset_location, units_per_second),
+			None => prices.borrow_mut().remove(&asset_location),
+		});
+	}
+}
+
+impl AssetSpotPrice for MockSpotPrice {
+	fn spot_units_per_second(asset_location: &MultiLocation) -> Option<u128> {
+		SPOT_PRICES.with(|prices| prices.borrow().get(asset_location).copied())
+	}
+}
+
+parameter_types! {
+	pub const PoolPriceSmoothing: Perbill = Perbill::from_percent(50);
+	pub const MaxPoolPriceDeviation: Perbill = Perbill::from_percent(20);
+	pub const SpotPriceWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
 impl pallet_xc_asset_config::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetId = AssetId;
 	type NativeAssetId = GetNativeAssetId;
 	type NativeAssetLocation = SelfReserveLocation;
 	type ManagerOrigin = EnsureRoot<AccountId>;
+	type Balance = Balance;
+	type ForeignAssetRegistrar = AssetsRegistrar;
+	type SpotPrice = MockSpotPrice;
+	type SpotPriceWeight = SpotPriceWeight;
+	type MaxPoolPricedAssets = ConstU32<2>;
+	type PoolPriceSmoothing = PoolPriceSmoothing;
+	type MaxPoolPriceAge = ConstU64<2>;
+	type MaxPoolPriceDeviation = MaxPoolPriceDeviation;
 	type WeightInfo = crate::weights::SubstrateWeight<Self>;
 }
 
@@ -20,7 +20,14 @@ use super::{
 	pallet::{Error, Event},
 	*,
 };
-use frame_support::{assert_noop, assert_ok, WeakBoundedVec};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{
+		fungibles, ContainsPair, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+	},
+	WeakBoundedVec,
+};
 use mock::*;
 use sp_runtime::traits::BadOrigin;
 use xcm::latest::prelude::*;
@@ -417,3 +424,433 @@ fn incompatible_versioned_multilocations_are_not_ok() {
 		);
 	})
 }
+
+fn foreign_asset_metadata() -> ForeignAssetMetadata<u128> {
+	ForeignAssetMetadata {
+		name: b"Relay Token".to_vec(),
+		symbol: b"RLY".to_vec(),
+		decimals: 12,
+		min_balance: 3,
+		is_sufficient: true,
+	}
+}
+
+#[test]
+fn register_foreign_asset_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		let asset_location = MultiLocation::parent();
+		let asset_id = 13;
+		let units: u128 = 7 * 11 * 13;
+
+		assert_noop!(
+			XcAssetConfig::register_foreign_asset(
+				RuntimeOrigin::signed(1),
+				Box::new(asset_location.into_versioned()),
+				asset_id,
+				foreign_asset_metadata(),
+				Some(units),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(XcAssetConfig::register_foreign_asset(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned()),
+			asset_id,
+			foreign_asset_metadata(),
+			Some(units),
+		));
+		System::assert_has_event(mock::RuntimeEvent::XcAssetConfig(Event::AssetRegistered {
+			asset_location: asset_location.into_versioned(),
+			asset_id,
+		}));
+		System::assert_last_event(mock::RuntimeEvent::XcAssetConfig(
+			Event::UnitsPerSecondChanged {
+				asset_location: asset_location.into_versioned(),
+				units_per_second: units,
+			},
+		));
+
+		// Location, units per second and the local asset are all set up
+		assert_eq!(XcAssetConfig::get_xc_asset_location(asset_id), Some(asset_location));
+		assert_eq!(XcAssetConfig::get_asset_id(asset_location), Some(asset_id));
+		assert_eq!(XcAssetConfig::get_units_per_second(asset_location), Some(units));
+		assert!(<Assets as fungibles::Inspect<u64>>::asset_exists(asset_id));
+		assert_eq!(<Assets as fungibles::Inspect<u64>>::minimum_balance(asset_id), 3);
+		assert_eq!(
+			<Assets as fungibles::metadata::Inspect<u64>>::symbol(asset_id),
+			b"RLY".to_vec()
+		);
+		assert_eq!(<Assets as fungibles::metadata::Inspect<u64>>::decimals(asset_id), 12);
+	})
+}
+
+#[test]
+fn register_foreign_asset_is_atomic() {
+	ExternalityBuilder::build().execute_with(|| {
+		let asset_location = MultiLocation::parent();
+		let asset_id = 13;
+
+		// The local asset already exists, nothing must be registered
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, ASSET_OWNER, true, 1));
+		assert_noop!(
+			XcAssetConfig::register_foreign_asset(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned()),
+				asset_id,
+				foreign_asset_metadata(),
+				Some(1),
+			),
+			pallet_assets::Error::<Test>::InUse
+		);
+		assert!(XcAssetConfig::get_xc_asset_location(asset_id).is_none());
+		assert!(XcAssetConfig::get_asset_id(asset_location).is_none());
+
+		// Native asset can't be registered
+		assert_noop!(
+			XcAssetConfig::register_foreign_asset(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned()),
+				GetNativeAssetId::get(),
+				foreign_asset_metadata(),
+				None,
+			),
+			Error::<Test>::NativeAssetRelated
+		);
+	})
+}
+
+#[test]
+fn deregister_foreign_asset_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		let asset_location = MultiLocation::parent();
+		let asset_id = 13;
+
+		assert_noop!(
+			XcAssetConfig::deregister_foreign_asset(RuntimeOrigin::root(), asset_id),
+			Error::<Test>::AssetDoesNotExist
+		);
+
+		assert_ok!(XcAssetConfig::register_foreign_asset(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned()),
+			asset_id,
+			foreign_asset_metadata(),
+			Some(17),
+		));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(ASSET_OWNER), asset_id, 1, 10));
+
+		assert_noop!(
+			XcAssetConfig::deregister_foreign_asset(RuntimeOrigin::signed(1), asset_id),
+			BadOrigin
+		);
+		assert_ok!(XcAssetConfig::deregister_foreign_asset(RuntimeOrigin::root(), asset_id));
+		System::assert_has_event(mock::RuntimeEvent::XcAssetConfig(Event::AssetRemoved {
+			asset_location: asset_location.into_versioned(),
+			asset_id,
+		}));
+
+		// Everything is cleaned up, including the local asset and its metadata
+		assert!(XcAssetConfig::get_xc_asset_location(asset_id).is_none());
+		assert!(XcAssetConfig::get_asset_id(asset_location).is_none());
+		assert!(XcAssetConfig::get_units_per_second(asset_location).is_none());
+		assert!(!<Assets as fungibles::Inspect<u64>>::asset_exists(asset_id));
+		assert!(<Assets as fungibles::metadata::Inspect<u64>>::symbol(asset_id).is_empty());
+	})
+}
+
+#[test]
+fn deregister_foreign_asset_weight_includes_destroying_the_asset() {
+	let call = pallet::Call::<Test>::deregister_foreign_asset { asset_id: 13 };
+	assert_eq!(
+		call.get_dispatch_info().weight,
+		<Test as Config>::WeightInfo::deregister_foreign_asset()
+			.saturating_add(DESTROY_ASSET_WEIGHT)
+	);
+}
+
+#[test]
+fn asset_reserves_are_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		let asset_location = MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(7)));
+		let reserve_location = MultiLocation::new(1, X1(Parachain(1000)));
+		let asset_id = 13;
+		let asset: MultiAsset = (asset_location, 100).into();
+
+		assert_noop!(
+			XcAssetConfig::set_asset_reserve(
+				RuntimeOrigin::signed(1),
+				asset_id,
+				Box::new(reserve_location.into_versioned())
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			XcAssetConfig::set_asset_reserve(
+				RuntimeOrigin::root(),
+				asset_id,
+				Box::new(reserve_location.into_versioned())
+			),
+			Error::<Test>::AssetDoesNotExist
+		);
+
+		// A registered asset is trusted from the default reserve of its location
+		assert_ok!(XcAssetConfig::register_asset_location(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned()),
+			asset_id
+		));
+		assert_eq!(
+			XcAssetConfig::asset_reserves(),
+			vec![(asset_id, reserve_location.into_versioned())]
+		);
+		assert!(TrustedReserves::<Test>::contains(&asset, &reserve_location));
+		assert!(!TrustedReserves::<Test>::contains(&asset, &MultiLocation::parent()));
+		assert!(!TrustedReserves::<Test>::contains(
+			&(MultiLocation::new(1, X1(Parachain(1000))), 100).into(),
+			&reserve_location
+		));
+
+		assert_ok!(XcAssetConfig::set_asset_reserve(
+			RuntimeOrigin::root(),
+			asset_id,
+			Box::new(MultiLocation::parent().into_versioned())
+		));
+		System::assert_last_event(mock::RuntimeEvent::XcAssetConfig(Event::AssetReserveSet {
+			asset_id,
+			reserve_location: MultiLocation::parent().into_versioned(),
+		}));
+		assert!(TrustedReserves::<Test>::contains(&asset, &MultiLocation::parent()));
+		assert!(!TrustedReserves::<Test>::contains(&asset, &reserve_location));
+
+		assert_ok!(XcAssetConfig::remove_asset_reserve(RuntimeOrigin::root(), asset_id));
+		System::assert_last_event(mock::RuntimeEvent::XcAssetConfig(Event::AssetReserveRemoved {
+			asset_id,
+		}));
+		assert!(!TrustedReserves::<Test>::contains(&asset, &MultiLocation::parent()));
+		assert_noop!(
+			XcAssetConfig::remove_asset_reserve(RuntimeOrigin::root(), asset_id),
+			Error::<Test>::AssetReserveDoesNotExist
+		);
+
+		// Changing the asset location resets its reserve to the default one of the new location
+		assert_ok!(XcAssetConfig::change_existing_asset_location(
+			RuntimeOrigin::root(),
+			Box::new(MultiLocation::new(1, X1(GeneralIndex(7))).into_versioned()),
+			asset_id
+		));
+		assert_eq!(
+			AssetReserves::<Test>::get(asset_id),
+			Some(MultiLocation::parent().into_versioned())
+		);
+		assert_ok!(XcAssetConfig::change_existing_asset_location(
+			RuntimeOrigin::root(),
+			Box::new(MultiLocation::new(0, X1(GeneralIndex(7))).into_versioned()),
+			asset_id
+		));
+		System::assert_has_event(mock::RuntimeEvent::XcAssetConfig(Event::AssetReserveRemoved {
+			asset_id,
+		}));
+		assert!(!AssetReserves::<Test>::contains_key(asset_id));
+
+		// Removing the asset removes its reserve as well
+		assert_ok!(XcAssetConfig::set_asset_reserve(
+			RuntimeOrigin::root(),
+			asset_id,
+			Box::new(reserve_location.into_versioned())
+		));
+		assert_ok!(XcAssetConfig::remove_asset(RuntimeOrigin::root(), asset_id));
+		assert!(!AssetReserves::<Test>::contains_key(asset_id));
+	})
+}
+
+#[test]
+fn pool_price_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		let asset_location = MultiLocation::parent();
+		let asset_id = 17;
+
+		assert_noop!(
+			XcAssetConfig::enable_pool_price(
+				RuntimeOrigin::signed(1),
+				Box::new(asset_location.into_versioned())
+			),
+			BadOrigin
+		);
+		assert_ok!(XcAssetConfig::register_asset_location(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned()),
+			asset_id
+		));
+		assert_noop!(
+			XcAssetConfig::enable_pool_price(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned())
+			),
+			Error::<Test>::PaymentAssetDoesNotExist
+		);
+
+		assert_ok!(XcAssetConfig::set_asset_units_per_second(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned()),
+			1_000
+		));
+		assert_ok!(XcAssetConfig::enable_pool_price(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned())
+		));
+		System::assert_last_event(mock::RuntimeEvent::XcAssetConfig(Event::PoolPriceEnabled {
+			asset_location: asset_location.into_versioned(),
+		}));
+		assert_noop!(
+			XcAssetConfig::enable_pool_price(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned())
+			),
+			Error::<Test>::PoolPriceAlreadyEnabled
+		);
+
+		// Not sampled yet
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), None);
+
+		// The first sample is taken as is within 20% of the units per second, the next ones move
+		// the price half way
+		MockSpotPrice::set(asset_location, Some(1_100));
+		// Sampling the spot price is charged on top of the pallet's own weight
+		assert_eq!(
+			XcAssetConfig::on_initialize(1),
+			<Test as Config>::WeightInfo::update_pool_prices(1)
+				.saturating_add(SpotPriceWeight::get())
+				.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads(1))
+		);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), Some(1_100));
+
+		MockSpotPrice::set(asset_location, Some(900));
+		System::set_block_number(2);
+		XcAssetConfig::on_initialize(2);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), Some(1_000));
+
+		// A manipulated spot price is clamped to 20% around the time-weighted price, which
+		// then moves by half of it
+		MockSpotPrice::set(asset_location, Some(1_600));
+		System::set_block_number(3);
+		XcAssetConfig::on_initialize(3);
+		assert_eq!(
+			XcAssetConfig::asset_location_pool_price(asset_location.into_versioned()),
+			Some(PoolPrice { units_per_second: 1_100, updated_at: 3 })
+		);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), Some(1_100));
+
+		MockSpotPrice::set(asset_location, Some(1_000));
+		System::set_block_number(4);
+		XcAssetConfig::on_initialize(4);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), Some(1_050));
+
+		// Without new samples the price gets outdated
+		MockSpotPrice::set(asset_location, None);
+		System::set_block_number(6);
+		XcAssetConfig::on_initialize(6);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), Some(1_050));
+		System::set_block_number(7);
+		assert_eq!(PoolPaymentRate::<Test>::get_units_per_second(asset_location), None);
+
+		// A lasting price move is followed beyond 20% of the units per second
+		MockSpotPrice::set(asset_location, Some(2_000));
+		for now in 7..10 {
+			System::set_block_number(now);
+			XcAssetConfig::on_initialize(now);
+		}
+		assert!(matches!(
+			PoolPaymentRate::<Test>::get_units_per_second(asset_location),
+			Some(units_per_second) if units_per_second > 1_200
+		));
+
+		assert_ok!(XcAssetConfig::disable_pool_price(
+			RuntimeOrigin::root(),
+			Box::new(asset_location.into_versioned())
+		));
+		System::assert_last_event(mock::RuntimeEvent::XcAssetConfig(Event::PoolPriceDisabled {
+			asset_location: asset_location.into_versioned(),
+		}));
+		assert_noop!(
+			XcAssetConfig::disable_pool_price(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned())
+			),
+			Error::<Test>::PoolPriceNotEnabled
+		);
+	})
+}
+
+#[test]
+fn pool_priced_assets_are_bounded_and_removed_with_their_asset() {
+	ExternalityBuilder::build().execute_with(|| {
+		for asset_id in 1..=3 {
+			let asset_location = MultiLocation::new(1, X1(Parachain(asset_id as u32)));
+			assert_ok!(XcAssetConfig::register_asset_location(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned()),
+				asset_id
+			));
+			assert_ok!(XcAssetConfig::set_asset_units_per_second(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned()),
+				1_000
+			));
+		}
+		let location =
+			|asset_id| Box::new(MultiLocation::new(1, X1(Parachain(asset_id))).into_versioned());
+
+		assert_ok!(XcAssetConfig::enable_pool_price(RuntimeOrigin::root(), location(1)));
+		assert_ok!(XcAssetConfig::enable_pool_price(RuntimeOrigin::root(), location(2)));
+		assert_noop!(
+			XcAssetConfig::enable_pool_price(RuntimeOrigin::root(), location(3)),
+			Error::<Test>::TooManyPoolPricedAssets
+		);
+
+		// The pool price follows the asset location, and is removed together with the asset or
+		// its units per second
+		assert_ok!(XcAssetConfig::change_existing_asset_location(
+			RuntimeOrigin::root(),
+			location(4),
+			1
+		));
+		assert!(!AssetLocationPoolPrice::<Test>::contains_key(*location(1)));
+		assert!(AssetLocationPoolPrice::<Test>::contains_key(*location(4)));
+
+		assert_ok!(XcAssetConfig::remove_asset(RuntimeOrigin::root(), 1));
+		assert_ok!(XcAssetConfig::remove_payment_asset(RuntimeOrigin::root(), location(2)));
+		assert_eq!(AssetLocationPoolPrice::<Test>::count(), 0);
+		assert_ok!(XcAssetConfig::enable_pool_price(RuntimeOrigin::root(), location(3)));
+	})
+}
+
+#[test]
+fn migration_seeds_default_reserves() {
+	ExternalityBuilder::build().execute_with(|| {
+		let sibling_asset = MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(7)));
+		let relay_asset = MultiLocation::parent();
+		let local_asset = MultiLocation::new(0, X1(GeneralIndex(7)));
+
+		for (asset_id, asset_location) in [(1, sibling_asset), (2, relay_asset), (3, local_asset)] {
+			assert_ok!(XcAssetConfig::register_asset_location(
+				RuntimeOrigin::root(),
+				Box::new(asset_location.into_versioned()),
+				asset_id
+			));
+		}
+		// As before reserves were stored
+		let _ = AssetReserves::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(2).put::<XcAssetConfig>();
+
+		migrations::MigrationAssetReserves::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			AssetReserves::<Test>::get(1),
+			Some(MultiLocation::new(1, X1(Parachain(1000))).into_versioned())
+		);
+		assert_eq!(AssetReserves::<Test>::get(2), Some(MultiLocation::parent().into_versioned()));
+		assert_eq!(AssetReserves::<Test>::get(3), None);
+		assert_eq!(XcAssetConfig::on_chain_storage_version(), 3);
+	})
+}
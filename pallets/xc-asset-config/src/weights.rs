@@ -52,6 +52,13 @@ pub trait WeightInfo {
 	fn change_existing_asset_location() -> Weight;
 	fn remove_payment_asset() -> Weight;
 	fn remove_asset() -> Weight;
+	fn register_foreign_asset() -> Weight;
+	fn deregister_foreign_asset() -> Weight;
+	fn set_asset_reserve() -> Weight;
+	fn remove_asset_reserve() -> Weight;
+	fn enable_pool_price() -> Weight;
+	fn disable_pool_price() -> Weight;
+	fn update_pool_prices(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_xc_asset_config using the Substrate node and recommended hardware.
@@ -63,12 +70,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Proof Skipped: EVM AccountCodes (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
 	fn register_asset_location() -> Weight {
 		// Minimum execution time: 15_540 nanoseconds.
 		Weight::from_parts(16_114_000, 0)
 			.saturating_add(Weight::from_parts(0, 2493))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	// Storage: XcAssetConfig AssetLocationToId (r:1 w:0)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
@@ -87,20 +96,31 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:2)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
 	fn change_existing_asset_location() -> Weight {
 		// Minimum execution time: 22_357 nanoseconds.
 		Weight::from_parts(22_572_000, 0)
 			.saturating_add(Weight::from_parts(0, 5373))
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn remove_payment_asset() -> Weight {
 		// Minimum execution time: 9_707 nanoseconds.
 		Weight::from_parts(10_005_000, 0)
 			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
 	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
@@ -110,12 +130,115 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn remove_asset() -> Weight {
 		// Minimum execution time: 18_645 nanoseconds.
 		Weight::from_parts(18_878_000, 0)
 			.saturating_add(Weight::from_parts(0, 2987))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: Assets Asset (r:1 w:1)
+	// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	// Storage: Assets Metadata (r:1 w:1)
+	// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn register_foreign_asset() -> Weight {
+		// Minimum execution time: 34_512 nanoseconds.
+		Weight::from_parts(35_208_000, 0)
+			.saturating_add(Weight::from_parts(0, 8193))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: Assets Asset (r:1 w:1)
+	// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	// Storage: Assets Metadata (r:1 w:1)
+	// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn deregister_foreign_asset() -> Weight {
+		// Minimum execution time: 38_943 nanoseconds.
+		Weight::from_parts(39_611_000, 0)
+			.saturating_add(Weight::from_parts(0, 8193))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:0)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn set_asset_reserve() -> Weight {
+		// Minimum execution time: 14_865 nanoseconds.
+		Weight::from_parts(15_302_000, 0)
+			.saturating_add(Weight::from_parts(0, 2493))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: XcAssetConfig AssetReserves (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn remove_asset_reserve() -> Weight {
+		// Minimum execution time: 13_129 nanoseconds.
+		Weight::from_parts(13_540_000, 0)
+			.saturating_add(Weight::from_parts(0, 2493))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:1 w:0)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn enable_pool_price() -> Weight {
+		// Minimum execution time: 17_940 nanoseconds.
+		Weight::from_parts(18_412_000, 0)
+			.saturating_add(Weight::from_parts(0, 3150))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn disable_pool_price() -> Weight {
+		// Minimum execution time: 16_217 nanoseconds.
+		Weight::from_parts(16_733_000, 0)
+			.saturating_add(Weight::from_parts(0, 3150))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:17 w:16)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Reads of the runtime `SpotPrice` are added by the pallet with `SpotPriceWeight`.
+	/// The range of component `n` is `[0, 16]`.
+	fn update_pool_prices(n: u32, ) -> Weight {
+		// Minimum execution time: 3_412 nanoseconds.
+		Weight::from_parts(3_695_000, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 9_871
+			.saturating_add(Weight::from_parts(14_583_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2605).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 	}
 }
 
@@ -127,12 +250,14 @@ impl WeightInfo for () {
 	// Proof Skipped: EVM AccountCodes (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
 	fn register_asset_location() -> Weight {
 		// Minimum execution time: 15_540 nanoseconds.
 		Weight::from_parts(16_114_000, 0)
 			.saturating_add(Weight::from_parts(0, 2493))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	// Storage: XcAssetConfig AssetLocationToId (r:1 w:0)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
@@ -151,20 +276,31 @@ impl WeightInfo for () {
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:2)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
 	fn change_existing_asset_location() -> Weight {
 		// Minimum execution time: 22_357 nanoseconds.
 		Weight::from_parts(22_572_000, 0)
 			.saturating_add(Weight::from_parts(0, 5373))
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn remove_payment_asset() -> Weight {
 		// Minimum execution time: 9_707 nanoseconds.
 		Weight::from_parts(10_005_000, 0)
 			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
 	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
@@ -174,11 +310,114 @@ impl WeightInfo for () {
 	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
 	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
 	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn remove_asset() -> Weight {
 		// Minimum execution time: 18_645 nanoseconds.
 		Weight::from_parts(18_878_000, 0)
 			.saturating_add(Weight::from_parts(0, 2987))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: Assets Asset (r:1 w:1)
+	// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	// Storage: Assets Metadata (r:1 w:1)
+	// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn register_foreign_asset() -> Weight {
+		// Minimum execution time: 34_512 nanoseconds.
+		Weight::from_parts(35_208_000, 0)
+			.saturating_add(Weight::from_parts(0, 8193))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: Assets Asset (r:1 w:1)
+	// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	// Storage: Assets Metadata (r:1 w:1)
+	// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationToId (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationToId (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn deregister_foreign_asset() -> Weight {
+		// Minimum execution time: 38_943 nanoseconds.
+		Weight::from_parts(39_611_000, 0)
+			.saturating_add(Weight::from_parts(0, 8193))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	// Storage: XcAssetConfig AssetIdToLocation (r:1 w:0)
+	// Proof Skipped: XcAssetConfig AssetIdToLocation (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetReserves (r:0 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn set_asset_reserve() -> Weight {
+		// Minimum execution time: 14_865 nanoseconds.
+		Weight::from_parts(15_302_000, 0)
+			.saturating_add(Weight::from_parts(0, 2493))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: XcAssetConfig AssetReserves (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetReserves (max_values: None, max_size: None, mode: Measured)
+	fn remove_asset_reserve() -> Weight {
+		// Minimum execution time: 13_129 nanoseconds.
+		Weight::from_parts(13_540_000, 0)
+			.saturating_add(Weight::from_parts(0, 2493))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationUnitsPerSecond (r:1 w:0)
+	// Proof Skipped: XcAssetConfig AssetLocationUnitsPerSecond (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn enable_pool_price() -> Weight {
+		// Minimum execution time: 17_940 nanoseconds.
+		Weight::from_parts(18_412_000, 0)
+			.saturating_add(Weight::from_parts(0, 3150))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:1 w:1)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Storage: XcAssetConfig CounterForAssetLocationPoolPrice (r:1 w:1)
+	// Proof: XcAssetConfig CounterForAssetLocationPoolPrice (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn disable_pool_price() -> Weight {
+		// Minimum execution time: 16_217 nanoseconds.
+		Weight::from_parts(16_733_000, 0)
+			.saturating_add(Weight::from_parts(0, 3150))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: XcAssetConfig AssetLocationPoolPrice (r:17 w:16)
+	// Proof Skipped: XcAssetConfig AssetLocationPoolPrice (max_values: None, max_size: None, mode: Measured)
+	// Reads of the runtime `SpotPrice` are added by the pallet with `SpotPriceWeight`.
+	/// The range of component `n` is `[0, 16]`.
+	fn update_pool_prices(n: u32, ) -> Weight {
+		// Minimum execution time: 3_412 nanoseconds.
+		Weight::from_parts(3_695_000, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 9_871
+			.saturating_add(Weight::from_parts(14_583_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2605).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
 	}
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The AddressUnification contract's address.
+address constant ADDRESS_UNIFICATION_ADDRESS = 0x000000000000000000000000000000000000080A;
+
+/// @dev The AddressUnification contract's instance.
+AddressUnification constant ADDRESS_UNIFICATION_CONTRACT = AddressUnification(
+    ADDRESS_UNIFICATION_ADDRESS
+);
+
+/// @author The Peaq Team
+/// @title AddressUnification Interface
+/// The interface through which solidity contracts will interact with the address-unification pallet
+/// @custom:address 0x000000000000000000000000000000000000080A
+interface AddressUnification {
+    /// Get the Substrate account backing an EVM address.
+    /// Returns the default account if the address is not linked.
+    /// selector: 0xe0b490f7
+    function getAccountId(address evmAddress) external view returns (bytes32);
+
+    /// Get the EVM address of a Substrate account.
+    /// Returns the default address if the account is not linked.
+    /// selector: 0x0232027e
+    function getEvmAddress(bytes32 accountId) external view returns (address);
+
+    /// Check whether a Substrate account is linked to an EVM address.
+    /// selector: 0x39c05b3f
+    function isLinked(
+        bytes32 accountId,
+        address evmAddress
+    ) external view returns (bool);
+
+    /// Link the caller's Substrate account to an EVM address.
+    /// The signature is the EIP-712 claim signature of the EVM address.
+    /// The caller's Substrate account is the one its address maps to, which exists as it paid
+    /// for the transaction: claiming the caller's own address fails, use claimSubstrateAccount.
+    /// Can't be called from a contract, nor with DELEGATECALL, unlike the views.
+    /// selector: 0xd9f01604
+    function claimAccount(
+        address evmAddress,
+        bytes memory signature
+    ) external returns (bool);
+
+    /// Link the caller's address to a Substrate account, merging the native balance of the
+    /// caller's own Substrate account into it.
+    /// The signature is the SCALE encoded signature of the Substrate account over the claim
+    /// message of the pallet.
+    /// Can't be called from a contract, nor with DELEGATECALL, unlike the views.
+    /// selector: 0xd693e6d1
+    function claimSubstrateAccount(
+        bytes32 accountId,
+        bytes memory signature
+    ) external returns (bool);
+
+    /// Emitted when a Substrate account is linked to an EVM address.
+    event ClaimAccount(bytes32 accountId, address evmAddress);
+}
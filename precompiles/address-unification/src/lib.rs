@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use address_unification::{EVMAddressMapping, Eip712Signature};
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::ConstU32,
+};
+use pallet_evm::AddressMapping;
+use parity_scale_codec::DecodeAll;
+use precompile_utils::{
+	keccak256,
+	precompile_set::{get_address_type, AddressType},
+	prelude::*,
+	solidity, EvmResult,
+};
+use sp_core::H256;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
+type AddressUnificationOf<Runtime> = address_unification::Pallet<Runtime>;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type GetSignatureLimit = ConstU32<65>;
+type GetSubstrateSignatureLimit = ConstU32<128>;
+
+pub(crate) const SELECTOR_LOG_CLAIM_ACCOUNT: [u8; 32] = keccak256!("ClaimAccount(bytes32,address)");
+
+pub struct AddressUnificationPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> AddressUnificationPrecompile<Runtime>
+where
+	Runtime: address_unification::Config + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<address_unification::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	AccountIdOf<Runtime>: From<[u8; 32]>,
+	[u8; 32]: From<AccountIdOf<Runtime>>,
+{
+	#[precompile::public("getAccountId(address)")]
+	#[precompile::view]
+	fn get_account_id(handle: &mut impl PrecompileHandle, evm_address: Address) -> EvmResult<H256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let account_id =
+			AddressUnificationOf::<Runtime>::get_account_id_or_default(&evm_address.into());
+		Ok(H256::from(<[u8; 32]>::from(account_id)))
+	}
+
+	#[precompile::public("getEvmAddress(bytes32)")]
+	#[precompile::view]
+	fn get_evm_address(handle: &mut impl PrecompileHandle, account_id: H256) -> EvmResult<Address> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let account_id = AccountIdOf::<Runtime>::from(account_id.to_fixed_bytes());
+		Ok(Address(AddressUnificationOf::<Runtime>::get_evm_address_or_default(&account_id)))
+	}
+
+	#[precompile::public("isLinked(bytes32,address)")]
+	#[precompile::view]
+	fn is_linked(
+		handle: &mut impl PrecompileHandle,
+		account_id: H256,
+		evm_address: Address,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let account_id = AccountIdOf::<Runtime>::from(account_id.to_fixed_bytes());
+		Ok(AddressUnificationOf::<Runtime>::is_linked(&account_id, &evm_address.into()))
+	}
+
+	/// Links the account of the caller, `AddressMapping::into_account_id(caller)`, to
+	/// `evm_address`. As this account pays for the transaction it always exists, so claiming the
+	/// caller's own address fails with `NonEmptyAccounts`, use `claimSubstrateAccount` instead.
+	#[precompile::public("claimAccount(address,bytes)")]
+	fn claim_account(
+		handle: &mut impl PrecompileHandle,
+		evm_address: Address,
+		signature: BoundedBytes<GetSignatureLimit>,
+	) -> EvmResult<bool> {
+		Self::ensure_eoa_caller(handle)?;
+
+		let eth_signature: Eip712Signature = Vec::<u8>::from(signature)
+			.try_into()
+			.map_err(|_| Revert::new(RevertReason::custom("Invalid signature length")))?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin.clone()).into(),
+			address_unification::Call::<Runtime>::claim_account {
+				evm_address: evm_address.into(),
+				eth_signature,
+			},
+			0,
+		)?;
+
+		let event = log1(
+			handle.context().address,
+			SELECTOR_LOG_CLAIM_ACCOUNT,
+			solidity::encode_event_data((H256::from(<[u8; 32]>::from(origin)), evm_address)),
+		);
+		event.record(handle)?;
+
+		Ok(true)
+	}
+
+	/// Links the caller's address to `account_id`, merging the native balance of the caller's
+	/// padded account into it. `signature` is the SCALE encoded signature of `account_id` over
+	/// `substrate_signable_message` of the pallet.
+	#[precompile::public("claimSubstrateAccount(bytes32,bytes)")]
+	fn claim_substrate_account(
+		handle: &mut impl PrecompileHandle,
+		account_id: H256,
+		signature: BoundedBytes<GetSubstrateSignatureLimit>,
+	) -> EvmResult<bool> {
+		Self::ensure_eoa_caller(handle)?;
+
+		let signature =
+			Runtime::OffchainSignature::decode_all(&mut Vec::<u8>::from(signature).as_slice())
+				.map_err(|_| Revert::new(RevertReason::custom("Invalid signature")))?;
+
+		let caller = handle.context().caller;
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		let account_id = AccountIdOf::<Runtime>::from(account_id.to_fixed_bytes());
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			address_unification::Call::<Runtime>::claim_account_from_evm {
+				evm_address: caller,
+				account_id: account_id.clone(),
+				signature,
+				assets: Default::default(),
+			},
+			0,
+		)?;
+
+		let event = log1(
+			handle.context().address,
+			SELECTOR_LOG_CLAIM_ACCOUNT,
+			solidity::encode_event_data((
+				H256::from(<[u8; 32]>::from(account_id)),
+				Address(caller),
+			)),
+		);
+		event.record(handle)?;
+
+		Ok(true)
+	}
+}
+
+impl<Runtime> AddressUnificationPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	/// Claims link the account of the caller, which thus has to be the signer of the
+	/// transaction rather than a contract or the caller of a contract using DELEGATECALL.
+	fn ensure_eoa_caller(handle: &mut impl PrecompileHandle) -> EvmResult {
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"))
+		}
+		if get_address_type::<Runtime>(handle, handle.context().caller)? != AddressType::EOA {
+			return Err(revert("Function not callable by smart contracts"))
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,216 @@
+use crate::{mock::*, *};
+
+use parity_scale_codec::Encode;
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::get_account_id_selectors().contains(&0xe0b490f7));
+	assert!(PCall::get_evm_address_selectors().contains(&0x0232027e));
+	assert!(PCall::is_linked_selectors().contains(&0x39c05b3f));
+	assert!(PCall::claim_account_selectors().contains(&0xd9f01604));
+	assert!(PCall::claim_substrate_account_selectors().contains(&0xd693e6d1));
+}
+
+#[test]
+fn get_default_mapping() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_address = AddressUnification::evm_address(&alice_secret());
+
+		precompiles()
+			.prepare_test(
+				MockPeaqAccount::Alice,
+				MockPeaqAccount::EVMu1Account,
+				PCall::get_account_id { evm_address: Address(evm_address) },
+			)
+			.expect_no_logs()
+			.execute_returns(H256::from(<[u8; 32]>::from(MockPeaqAccount::into_account_id(
+				evm_address,
+			))));
+
+		precompiles()
+			.prepare_test(
+				MockPeaqAccount::Alice,
+				MockPeaqAccount::EVMu1Account,
+				PCall::get_evm_address { account_id: MockPeaqAccount::Bob.into() },
+			)
+			.expect_no_logs()
+			.execute_returns(Address(AddressUnification::get_detault_evm_address(
+				&MockPeaqAccount::Bob,
+			)));
+	});
+}
+
+#[test]
+fn claim_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		let origin = MockPeaqAccount::Alice;
+		let evm_address = AddressUnification::evm_address(&alice_secret());
+		let account_id = H256::from(<[u8; 32]>::from(origin));
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::is_linked { account_id, evm_address: Address(evm_address) },
+			)
+			.execute_returns(false);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_account {
+					evm_address: Address(evm_address),
+					signature: vec![0u8; 64].into(),
+				},
+			)
+			.execute_reverts(|output| output == b"Invalid signature length");
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_account {
+					evm_address: Address(evm_address),
+					signature: AddressUnification::eth_sign(&alice_secret(), &origin)
+						.to_vec()
+						.into(),
+				},
+			)
+			.expect_log(log1(
+				MockPeaqAccount::EVMu1Account,
+				SELECTOR_LOG_CLAIM_ACCOUNT,
+				solidity::encode_event_data((account_id, Address(evm_address))),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::is_linked { account_id, evm_address: Address(evm_address) },
+			)
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::get_account_id { evm_address: Address(evm_address) },
+			)
+			.execute_returns(account_id);
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::get_evm_address { account_id },
+			)
+			.execute_returns(Address(evm_address));
+	});
+}
+
+#[test]
+fn claim_account_cannot_be_delegate_called() {
+	ExtBuilder::default().build().execute_with(|| {
+		let origin = MockPeaqAccount::Alice;
+		let evm_address = AddressUnification::evm_address(&alice_secret());
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_account {
+					evm_address: Address(evm_address),
+					signature: AddressUnification::eth_sign(&alice_secret(), &origin)
+						.to_vec()
+						.into(),
+				},
+			)
+			.with_delegate_call_from(MockPeaqAccount::EVMu2Account)
+			.execute_reverts(|output| output == b"Cannot be called with DELEGATECALL or CALLCODE");
+	});
+}
+
+#[test]
+fn claim_substrate_account_links_the_caller() {
+	ExtBuilder::default().build().execute_with(|| {
+		let origin = MockPeaqAccount::Alice;
+		let evm_address = H160::from(origin);
+		let account_id = H256::from(<[u8; 32]>::from(MockPeaqAccount::Bob));
+		// the caller's own account, which paid for the transaction
+		Balances::make_free_balance_be(&origin, 1000);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_substrate_account { account_id, signature: vec![0xffu8; 3].into() },
+			)
+			.execute_reverts(|output| output == b"Invalid signature");
+
+		let signature = MockSignature(
+			MockPeaqAccount::Bob,
+			AddressUnification::substrate_signable_message(&MockPeaqAccount::Bob, evm_address),
+		);
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_substrate_account { account_id, signature: signature.encode().into() },
+			)
+			.expect_log(log1(
+				MockPeaqAccount::EVMu1Account,
+				SELECTOR_LOG_CLAIM_ACCOUNT,
+				solidity::encode_event_data((account_id, Address(evm_address))),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::is_linked { account_id, evm_address: Address(evm_address) },
+			)
+			.execute_returns(true);
+		assert_eq!(Balances::free_balance(MockPeaqAccount::Bob), 1000);
+		assert!(!System::account_exists(&origin));
+	});
+}
+
+#[test]
+fn contracts_can_read_links_but_not_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		let origin = MockPeaqAccount::Alice;
+		let evm_address = AddressUnification::evm_address(&alice_secret());
+		pallet_evm::AccountCodes::<Runtime>::insert(H160::from(origin), vec![10u8]);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::is_linked {
+					account_id: H256::from(<[u8; 32]>::from(origin)),
+					evm_address: Address(evm_address),
+				},
+			)
+			.execute_returns(false);
+
+		precompiles()
+			.prepare_test(
+				origin,
+				MockPeaqAccount::EVMu1Account,
+				PCall::claim_account {
+					evm_address: Address(evm_address),
+					signature: AddressUnification::eth_sign(&alice_secret(), &origin)
+						.to_vec()
+						.into(),
+				},
+			)
+			.execute_reverts(|output| output == b"Function not callable by smart contracts");
+	});
+}
@@ -18,10 +18,21 @@ interface ParachainStaking {
         uint256 amount;
     }
 
+    struct RewardInfo {
+        uint32 session;
+        bytes32 collator;
+        uint256 amount;
+    }
+
     /// Get all collator informations
     // selector: 0xaaacb283
     function getCollatorList() external view returns (CollatorInfo[] memory);
 
+    /// Get the rewards an account has received in the most recent sessions,
+    /// oldest first
+    /// selector: 0x7d931ad3
+    function getRewardHistory(bytes32 account) external view returns (RewardInfo[] memory);
+
     /// Join the set of delegators by delegating to a collator candidate
     /// selector: 0xd9f511cd
     function joinDelegators(bytes32 collator, uint256 stake) external;
@@ -53,4 +64,14 @@ interface ParachainStaking {
 		/// elapsed.
     /// selector: 0x0f615369
     function unlockUnstaked(address target) external;
+
+		/// Set the share of the rewards of a delegation which is automatically
+		/// restaked into the same collator candidate.
+    /// selector: 0xff03bb11
+    function setAutoCompound(bytes32 collator, uint8 percentage) external;
+
+		/// Claim the staking rewards which have been credited to the origin
+		/// account as collator and delegator.
+    /// selector: 0x372500ab
+    function claimRewards() external;
 }
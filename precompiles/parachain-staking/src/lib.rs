@@ -27,12 +27,17 @@ mod tests;
 use fp_evm::PrecompileHandle;
 use frame_support::{
 	dispatch::{GetDispatchInfo, PostDispatchInfo},
-	traits::Currency,
+	traits::{Currency, Get},
 };
 use pallet_evm::AddressMapping;
+use parachain_staking::types::RewardRecordOf;
+use parity_scale_codec::MaxEncodedLen;
 use precompile_utils::prelude::*;
 use sp_core::{H256, U256};
-use sp_runtime::traits::{Dispatchable, StaticLookup};
+use sp_runtime::{
+	traits::{Dispatchable, StaticLookup},
+	Percent,
+};
 use sp_std::{convert::TryInto, marker::PhantomData, vec::Vec};
 
 type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
@@ -52,6 +57,13 @@ pub struct CollatorInfo {
 	amount: U256,
 }
 
+#[derive(Default, solidity::Codec)]
+pub struct RewardInfo {
+	session: u32,
+	collator: H256,
+	amount: U256,
+}
+
 #[precompile_utils::precompile]
 impl<Runtime> ParachainStakingPrecompile<Runtime>
 where
@@ -82,6 +94,39 @@ where
 			.collect::<Vec<CollatorInfo>>())
 	}
 
+	#[precompile::public("getRewardHistory(bytes32)")]
+	#[precompile::public("get_reward_history(bytes32)")]
+	#[precompile::view]
+	fn get_reward_history(
+		handle: &mut impl PrecompileHandle,
+		account: H256,
+	) -> EvmResult<Vec<RewardInfo>> {
+		// CurrentIndex: SessionIndex(4)
+		handle.record_db_read::<Runtime>(4)?;
+		// RewardHistoryHead: SessionIndex(4)
+		handle.record_db_read::<Runtime>(4)?;
+		// RewardHistory: one slot per session, each a BoundedVec of at most
+		// MaxCollatorsPerDelegator records
+		let max_records = <Runtime as parachain_staking::Config>::MaxCollatorsPerDelegator::get();
+		let slot_len =
+			(max_records as usize).saturating_mul(RewardRecordOf::<Runtime>::max_encoded_len());
+		for _ in 0..<Runtime as parachain_staking::Config>::MaxRewardHistory::get() {
+			handle.record_db_read::<Runtime>(slot_len)?;
+		}
+
+		let account: Runtime::AccountId = AccountIdOf::<Runtime>::from(account.to_fixed_bytes());
+		Ok(parachain_staking::Pallet::<Runtime>::get_reward_history(&account)
+			.into_iter()
+			.map(|record| RewardInfo {
+				session: record.session,
+				collator: H256::from(<AccountIdOf<Runtime> as Into<[u8; 32]>>::into(
+					record.collator,
+				)),
+				amount: record.amount.into(),
+			})
+			.collect::<Vec<RewardInfo>>())
+	}
+
 	#[precompile::public("joinDelegators(bytes32,uint256)")]
 	#[precompile::public("join_delegators(bytes32,uint256)")]
 	fn join_delegators(
@@ -224,6 +269,46 @@ where
 		Ok(())
 	}
 
+	#[precompile::public("setAutoCompound(bytes32,uint8)")]
+	#[precompile::public("set_auto_compound(bytes32,uint8)")]
+	fn set_auto_compound(
+		handle: &mut impl PrecompileHandle,
+		collator: H256,
+		percentage: u8,
+	) -> EvmResult {
+		if percentage > 100 {
+			return Err(revert("percentage must be at most 100"))
+		}
+
+		// Build call with origin.
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let collator: Runtime::AccountId = AccountIdOf::<Runtime>::from(collator.to_fixed_bytes());
+		let collator: <Runtime::Lookup as StaticLookup>::Source =
+			<Runtime::Lookup as StaticLookup>::unlookup(collator.clone());
+		let call = parachain_staking::Call::<Runtime>::set_auto_compound {
+			collator,
+			percentage: Percent::from_percent(percentage),
+		};
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("claimRewards()")]
+	#[precompile::public("claim_rewards()")]
+	fn claim_rewards(handle: &mut impl PrecompileHandle) -> EvmResult {
+		// Build call with origin.
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = parachain_staking::Call::<Runtime>::claim_rewards {};
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
 	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime>> {
 		value
 			.try_into()
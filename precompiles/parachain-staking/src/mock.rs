@@ -202,6 +202,12 @@ parameter_types! {
 	pub const MinDelegatorStake: Balance = 5;
 	pub const MinDelegation: Balance = 3;
 	pub const MaxUnstakeRequests: u32 = 6;
+	pub const MaxCollatorCommission: Perbill = Perbill::from_percent(50);
+	pub const OfflineSlashFraction: Perbill = Perbill::zero();
+	pub const SlashDelegators: bool = false;
+	pub const SlashDeferDuration: u32 = 1;
+	pub const OffenceDeselectDuration: u32 = 1;
+	pub const MaxRewardHistory: u32 = 3;
 }
 
 impl parachain_staking::Config for Test {
@@ -223,6 +229,14 @@ impl parachain_staking::Config for Test {
 	type MinDelegatorStake = MinDelegatorStake;
 	type MinDelegation = MinDelegation;
 	type MaxUnstakeRequests = MaxUnstakeRequests;
+	type MaxCollatorCommission = MaxCollatorCommission;
+	type OfflineSlashFraction = OfflineSlashFraction;
+	type SlashDelegators = SlashDelegators;
+	type SlashDeferDuration = SlashDeferDuration;
+	type OffenceDeselectDuration = OffenceDeselectDuration;
+	type MaxRewardHistory = MaxRewardHistory;
+	type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slash = ();
 	type PotId = PotId;
 	type WeightInfo = parachain_staking::weights::WeightInfo<Test>;
 }
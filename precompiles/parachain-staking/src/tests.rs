@@ -19,9 +19,9 @@
 use crate::{
 	mock::{
 		roll_to, Balances, BlockNumber, ExtBuilder, PCall, Precompiles, PrecompilesValue,
-		RuntimeOrigin, StakePallet, Test,
+		RuntimeOrigin, StakePallet, Test, BLOCKS_PER_ROUND,
 	},
-	Address, BalanceOf, CollatorInfo, U256,
+	Address, BalanceOf, CollatorInfo, RewardInfo, U256,
 };
 use frame_support::{
 	assert_ok, storage::bounded_btree_map::BoundedBTreeMap, traits::LockIdentifier,
@@ -30,6 +30,7 @@ use pallet_balances::{BalanceLock, Reasons};
 use parachain_staking::types::TotalStake;
 use precompile_utils::testing::{MockPeaqAccount, PrecompileTesterExt, PrecompilesModifierTester};
 use sp_core::H256;
+use sp_runtime::Percent;
 
 const STAKING_ID: LockIdentifier = *b"peaqstak";
 
@@ -48,6 +49,7 @@ fn convert_mock_account_by_u8_list(account: MockPeaqAccount) -> H256 {
 #[test]
 fn test_selector_enum() {
 	assert!(PCall::get_collator_list_selectors().contains(&0xaaacb283));
+	assert!(PCall::get_reward_history_selectors().contains(&0x7d931ad3));
 	assert!(PCall::join_delegators_selectors().contains(&0xd9f511cd));
 	assert!(PCall::delegate_another_candidate_selectors().contains(&0x1916fdca));
 	assert!(PCall::leave_delegators_selectors().contains(&0x4b99dc38));
@@ -55,6 +57,8 @@ fn test_selector_enum() {
 	assert!(PCall::delegator_stake_more_selectors().contains(&0x1b3d3cdf));
 	assert!(PCall::delegator_stake_less_selectors().contains(&0xb7e8947f));
 	assert!(PCall::unlock_unstaked_selectors().contains(&0x0f615369));
+	assert!(PCall::set_auto_compound_selectors().contains(&0xff03bb11));
+	assert!(PCall::claim_rewards_selectors().contains(&0x372500ab));
 }
 
 #[test]
@@ -71,6 +75,7 @@ fn modifiers() {
 			);
 
 			tester.test_view_modifier(PCall::get_collator_list_selectors());
+			tester.test_view_modifier(PCall::get_reward_history_selectors());
 		});
 }
 
@@ -111,6 +116,32 @@ fn collator_list_test() {
 		});
 }
 
+#[test]
+fn reward_history_test() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 10)])
+		.with_collators(vec![(MockPeaqAccount::Alice, 10)])
+		.build()
+		.execute_with(|| {
+			roll_to(BLOCKS_PER_ROUND, vec![None, Some(MockPeaqAccount::Alice)]);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::get_reward_history {
+						account: convert_mock_account_by_u8_list(MockPeaqAccount::Alice),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(vec![RewardInfo {
+					session: 0,
+					collator: convert_mock_account_by_u8_list(MockPeaqAccount::Alice),
+					amount: U256::from(1000),
+				}]);
+		});
+}
+
 #[test]
 fn unlock_unstaked() {
 	// same_unstaked_as_restaked
@@ -341,3 +372,41 @@ fn should_update_total_stake() {
 			);
 		})
 }
+
+#[test]
+fn set_auto_compound() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 10), (MockPeaqAccount::Bob, 100)])
+		.with_collators(vec![(MockPeaqAccount::Alice, 10)])
+		.with_delegators(vec![(MockPeaqAccount::Bob, MockPeaqAccount::Alice, 100)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::set_auto_compound {
+						collator: convert_mock_account_by_u8_list(MockPeaqAccount::Alice),
+						percentage: 101,
+					},
+				)
+				.execute_reverts(|output| output == b"percentage must be at most 100");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::set_auto_compound {
+						collator: convert_mock_account_by_u8_list(MockPeaqAccount::Alice),
+						percentage: 30,
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(());
+
+			assert_eq!(
+				StakePallet::auto_compound(MockPeaqAccount::Bob, MockPeaqAccount::Alice),
+				Percent::from_percent(30)
+			);
+		});
+}
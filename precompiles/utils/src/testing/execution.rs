@@ -71,6 +71,12 @@ impl<'p, P: PrecompileSet> PrecompilesTester<'p, P> {
 		self
 	}
 
+	/// Executes the precompile code in the context of `address`, as DELEGATECALL does.
+	pub fn with_delegate_call_from(mut self, address: impl Into<H160>) -> Self {
+		self.handle.context.address = address.into();
+		self
+	}
+
 	pub fn with_subcall_handle(mut self, subcall_handle: impl SubcallTrait) -> Self {
 		self.subcall_handle = Some(Box::new(subcall_handle));
 		self
@@ -55,6 +55,8 @@ pub enum TracerInput {
 	None,
 	Blockscout,
 	CallTracer,
+	PrestateTracer,
+	FourByteTracer,
 }
 
 /// DebugRuntimeApi V2 result. Trace response is stored in client and runtime api call response is
@@ -203,6 +203,9 @@ macro_rules! log_icon {
 	(PeaqMultiCurrenciesOnChargeTransaction $e:expr) => {
 		"💵"
 	};
+	(PeaqMultiCurrenciesOnChargeEVMTransaction $e:expr) => {
+		"💵"
+	};
 	(PeaqCurrencyPaymentConvert $e:expr) => {
 		"💵"
 	};
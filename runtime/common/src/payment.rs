@@ -1,20 +1,29 @@
 use crate::PaymentConvertInfo;
 use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
 	pallet_prelude::{
-		InvalidTransaction, MaxEncodedLen, MaybeSerializeDeserialize, TransactionValidityError,
+		InvalidTransaction, MaxEncodedLen, MaybeSerializeDeserialize, TransactionValidity,
+		TransactionValidityError,
 	},
+	storage::{with_transaction, TransactionOutcome},
 	traits::{Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, WithdrawReasons},
 	Parameter,
 };
-use frame_system::Config as SysConfig;
+use frame_system::{pallet_prelude::OriginFor, CheckWeight, Config as SysConfig};
 use orml_traits::MultiCurrency;
+use pallet_ethereum::{
+	Call as EthereumCall, Config as EthereumConfig, RawOrigin as EthereumOrigin, Transaction,
+};
+use pallet_evm::{AddressMapping, Config as EvmConfig, Error as EvmError, OnChargeEVMTransaction};
 use pallet_transaction_payment::{Config as TransPayConfig, OnChargeTransaction};
+use sp_core::{H160, U256};
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
 	traits::{
-		Convert, DispatchInfoOf, MaybeDisplay, Member, PostDispatchInfoOf, SaturatedConversion,
-		Saturating, Zero,
+		Convert, DispatchInfoOf, Dispatchable, MaybeDisplay, Member, PostDispatchInfoOf,
+		SaturatedConversion, Saturating, UniqueSaturatedInto, Zero,
 	},
-	Perbill,
+	DispatchError, Perbill, Rounding,
 };
 use sp_std::{fmt::Debug, marker::PhantomData, vec, vec::Vec};
 
@@ -73,7 +82,7 @@ where
 		let tx_fee = total_fee.saturating_add(eot_fee);
 
 		// Check if user can withdraw in any valid currency.
-		let currency_id = PCPC::ensure_can_withdraw(who, tx_fee)?;
+		let (currency_id, _) = PCPC::ensure_can_withdraw(who, tx_fee)?;
 		let native_currency_id = PeaqAssetId::default().try_into().ok().unwrap();
 		if currency_id != native_currency_id {
 			log!(
@@ -129,6 +138,185 @@ where
 	}
 }
 
+/// Peaq's EVM Currency Adapter to enable withdrawal of EVM transaction fees from foreign
+/// currencies.
+///
+/// The fee is withdrawn in the same way as for extrinsics, swapping another accepted currency
+/// into native tokens if needed. Refunds of unused gas are swapped back into that currency.
+/// The base fee is handled by `OU`, the priority fee goes to the block author.
+pub struct PeaqMultiCurrenciesOnChargeEVMTransaction<C, OU, PCPC>(PhantomData<(C, OU, PCPC)>);
+
+impl<T, C, OU, PCPC> OnChargeEVMTransaction<T>
+	for PeaqMultiCurrenciesOnChargeEVMTransaction<C, OU, PCPC>
+where
+	T: SysConfig + EvmConfig,
+	C: Currency<T::AccountId>,
+	OU: OnUnbalanced<NegativeImbalanceOf<C, T>>,
+	PCPC: PeaqMultiCurrenciesPaymentConvert<AccountId = T::AccountId, Currency = C>,
+	U256: UniqueSaturatedInto<BalanceOf<C, T>>,
+{
+	/// The withdrawn fee and the swap which paid for it, if any.
+	type LiquidityInfo = Option<(NegativeImbalanceOf<C, T>, Option<PaymentConvertInfo>)>;
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, EvmError<T>> {
+		if fee.is_zero() {
+			return Ok(None)
+		}
+		let account_id = T::AddressMapping::into_account_id(*who);
+		let fee: BalanceOf<C, T> = fee.unique_saturated_into();
+
+		// Check if user can withdraw in any valid currency.
+		let (currency_id, swap_info) =
+			PCPC::ensure_can_withdraw(&account_id, fee).map_err(|_| EvmError::<T>::BalanceLow)?;
+		if currency_id != PCPC::NativeAssetId::get() {
+			log!(
+				info,
+				PeaqMultiCurrenciesOnChargeEVMTransaction,
+				"Payment with swap of {:?}-tokens",
+				currency_id
+			);
+		}
+
+		let imbalance =
+			C::withdraw(&account_id, fee, WithdrawReasons::FEE, ExistenceRequirement::AllowDeath)
+				.map_err(|_| EvmError::<T>::BalanceLow)?;
+		Ok(Some((imbalance, swap_info)))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		let (paid, swap_info) = already_withdrawn?;
+		let account_id = T::AddressMapping::into_account_id(*who);
+
+		// Calculate how much refund we should return. The account might have been reaped when
+		// the fee was withdrawn, so it may need to be created again.
+		let refund_amount = paid.peek().saturating_sub(corrected_fee.unique_saturated_into());
+		let refund_imbalance = C::deposit_into_existing(&account_id, refund_amount)
+			.unwrap_or_else(|_| C::deposit_creating(&account_id, refund_amount));
+		let refunded = refund_imbalance.peek();
+		// merge the imbalance caused by paying the fees and refunding parts of it again.
+		let adjusted_paid = paid
+			.offset(refund_imbalance)
+			.same()
+			.unwrap_or_else(|_| C::NegativeImbalance::zero());
+
+		if let Some(swap_info) = swap_info.as_ref() {
+			PCPC::swap_refund(&account_id, swap_info, refunded);
+		}
+
+		let (base_fee, tip) = adjusted_paid.split(base_fee.unique_saturated_into());
+		OU::on_unbalanced(base_fee);
+		Some((tip, swap_info))
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		if let Some((tip, _)) = tip {
+			// The tip goes to the block author, as with Frontier's `EVMCurrencyAdapter`
+			let author = T::AddressMapping::into_account_id(pallet_evm::Pallet::<T>::find_author());
+			if let Err(tip) = C::resolve_into_existing(&author, tip) {
+				OU::on_unbalanced(tip);
+			}
+		}
+	}
+}
+
+/// Validates a self-contained Ethereum call as if its maximum fee had already been swapped into
+/// native tokens, see [`with_evm_fee_swapped`].
+pub fn validate_self_contained_with_fee_swap<T, PCPC>(
+	call: &EthereumCall<T>,
+	who: &H160,
+	dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+	len: usize,
+) -> Option<TransactionValidity>
+where
+	T: EthereumConfig,
+	OriginFor<T>: Into<Result<EthereumOrigin, OriginFor<T>>>,
+	T::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + From<EthereumCall<T>>,
+	PCPC: PeaqMultiCurrenciesPaymentConvert<AccountId = T::AccountId>,
+	U256: UniqueSaturatedInto<BalanceOfA<PCPC::Currency, T::AccountId>>,
+{
+	with_evm_fee_swapped::<T, PCPC, _>(call, who, || {
+		call.validate_self_contained(who, dispatch_info, len)
+	})
+	.unwrap_or(Some(Err(InvalidTransaction::ExhaustsResources.into())))
+}
+
+/// Pre-dispatch checks of a self-contained Ethereum call as if its maximum fee had already been
+/// swapped into native tokens, see [`with_evm_fee_swapped`].
+pub fn pre_dispatch_self_contained_with_fee_swap<T, PCPC>(
+	call: &EthereumCall<T>,
+	who: &H160,
+	dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+	len: usize,
+) -> Option<Result<(), TransactionValidityError>>
+where
+	T: EthereumConfig,
+	OriginFor<T>: Into<Result<EthereumOrigin, OriginFor<T>>>,
+	T::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + From<EthereumCall<T>>,
+	PCPC: PeaqMultiCurrenciesPaymentConvert<AccountId = T::AccountId>,
+	U256: UniqueSaturatedInto<BalanceOfA<PCPC::Currency, T::AccountId>>,
+{
+	if evm_max_fee(call).is_zero() {
+		return call.pre_dispatch_self_contained(who, dispatch_info, len)
+	}
+	// The block weight the checks note is rolled back along with the swap, so note it again.
+	with_evm_fee_swapped::<T, PCPC, _>(call, who, || {
+		call.pre_dispatch_self_contained(who, dispatch_info, len)
+	})
+	.unwrap_or(Some(Err(InvalidTransaction::ExhaustsResources.into())))
+	.map(|result| result.and_then(|_| CheckWeight::<T>::do_pre_dispatch(dispatch_info, len)))
+}
+
+/// The most an Ethereum call can be charged for its gas.
+fn evm_max_fee<T: EthereumConfig>(call: &EthereumCall<T>) -> U256 {
+	match call {
+		EthereumCall::transact { transaction } => match transaction {
+			Transaction::Legacy(t) => t.gas_price.saturating_mul(t.gas_limit),
+			Transaction::EIP2930(t) => t.gas_price.saturating_mul(t.gas_limit),
+			Transaction::EIP1559(t) => t.max_fee_per_gas.saturating_mul(t.gas_limit),
+		},
+		_ => U256::zero(),
+	}
+}
+
+/// Runs `check` as if the maximum fee of `call` had already been swapped into native tokens,
+/// rolling back all of its changes afterwards. Returns `None` if the transactional limit was hit.
+///
+/// Frontier only checks the native balance of the sender, so without this, transactions of
+/// accounts which pay their fees in another accepted currency would never enter the pool.
+fn with_evm_fee_swapped<T, PCPC, R>(
+	call: &EthereumCall<T>,
+	who: &H160,
+	check: impl FnOnce() -> R,
+) -> Option<R>
+where
+	T: EthereumConfig,
+	PCPC: PeaqMultiCurrenciesPaymentConvert<AccountId = T::AccountId>,
+	U256: UniqueSaturatedInto<BalanceOfA<PCPC::Currency, T::AccountId>>,
+{
+	let max_fee = evm_max_fee(call);
+	if max_fee.is_zero() {
+		return Some(check())
+	}
+	let account_id = T::AddressMapping::into_account_id(*who);
+	// Frontier keeps the existential deposit out of the spendable balance.
+	let max_fee: BalanceOfA<PCPC::Currency, T::AccountId> = max_fee.unique_saturated_into();
+	let max_fee = max_fee.saturating_add(PCPC::ExistentialDeposit::get());
+
+	with_transaction(|| {
+		// If no currency can pay for the fee, `check` reports the low balance itself.
+		let _ = PCPC::ensure_can_withdraw(&account_id, max_fee);
+		TransactionOutcome::Rollback(Ok::<_, DispatchError>(check()))
+	})
+	.ok()
+}
+
 /// Individual trait to handle payments in non-local currencies. The intention is to keep it as
 /// generic as possible to enable the usage in PeaqCurrencyAdapter.
 pub trait PeaqMultiCurrenciesPaymentConvert {
@@ -163,19 +351,24 @@ pub trait PeaqMultiCurrenciesPaymentConvert {
 	/// List of all accepted CurrencyIDs except for the local ones in type of Zenlink's AssetId.
 	type LocalAcceptedIds: Get<Vec<Self::AssetId>>;
 
+	/// Share of a refund which may be lost when it is swapped back into the currency the fee was
+	/// paid in, covering the pool fees of both swaps.
+	type MaxRefundSlippage: Get<Perbill>;
+
 	type AssetId: Parameter + Member + MaybeSerializeDeserialize + Debug + Copy;
 
 	type AssetIdToZenlinkId: Convert<Self::AssetId, Option<ZenlinkAssetId>>;
 
 	/// This method checks if the fee can be withdrawn in any currency and returns the asset_id
-	/// of the choosen currency in dependency of the priority-list and availability of tokens.
+	/// of the choosen currency in dependency of the priority-list and availability of tokens,
+	/// together with the swap into native tokens, if one was needed.
 	fn ensure_can_withdraw(
 		who: &Self::AccountId,
 		tx_fee: BalanceOfA<Self::Currency, Self::AccountId>,
-	) -> Result<Self::AssetId, TransactionValidityError> {
+	) -> Result<(Self::AssetId, Option<PaymentConvertInfo>), TransactionValidityError> {
 		let (currency_id, option) = Self::check_currencies_n_priorities(who, tx_fee)?;
 
-		if let Some(info) = option {
+		if let Some(info) = option.as_ref() {
 			Self::DexOperator::inner_swap_assets_for_exact_assets(
 				who,
 				info.amount_out,
@@ -186,7 +379,7 @@ pub trait PeaqMultiCurrenciesPaymentConvert {
 			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
 		}
 
-		Ok(currency_id)
+		Ok((currency_id, option))
 	}
 
 	/// Checks all accepted native currencies and selects the first with enough tokens.
@@ -226,4 +419,45 @@ pub trait PeaqMultiCurrenciesPaymentConvert {
 			Err(InvalidTransaction::Payment.into())
 		}
 	}
+
+	/// Swaps a refund in native tokens back into the currency the fee was paid in, by reversing
+	/// the swap which paid for it. The refund has to return at least its share of that swap's
+	/// amount-in, less `MaxRefundSlippage`, and stays in native tokens otherwise.
+	fn swap_refund(
+		who: &Self::AccountId,
+		swap_info: &PaymentConvertInfo,
+		refund: BalanceOfA<Self::Currency, Self::AccountId>,
+	) {
+		if refund.is_zero() || swap_info.amount_out.is_zero() {
+			return
+		}
+
+		let refund: AssetBalance = refund.saturated_into();
+		let amount_out_min = Self::MaxRefundSlippage::get().left_from_one() *
+			multiply_by_rational_with_rounding(
+				refund,
+				swap_info.amount_in,
+				swap_info.amount_out,
+				Rounding::Down,
+			)
+			.unwrap_or(AssetBalance::MAX);
+		let zen_path: Vec<ZenlinkAssetId> = swap_info.zen_path.iter().rev().cloned().collect();
+		if Self::DexOperator::inner_swap_exact_assets_for_assets(
+			who,
+			refund,
+			amount_out_min,
+			&zen_path,
+			who,
+		)
+		.is_err()
+		{
+			log!(
+				warn,
+				PeaqCurrencyPaymentConvert,
+				"Refund of {:?} could not be swapped back along {:?}",
+				refund,
+				zen_path
+			);
+		}
+	}
 }
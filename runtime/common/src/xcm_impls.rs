@@ -1,35 +1,53 @@
-use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
-use sp_std::marker::PhantomData;
-use xc_asset_config::ExecutionPaymentRate;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{fungibles, Get},
+	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, WeightToFee},
+};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{Convert, SaturatedConversion, Zero},
+	Rounding,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+use xc_asset_config::{
+	AssetSpotPrice, ExecutionPaymentRate, ForeignAssetMetadata, ForeignAssetRegistrar,
+	XcAssetLocation,
+};
 use xcm::latest::{
 	prelude::{Fungibility, MultiAsset, MultiLocation, XcmError},
 	Weight,
 };
 use xcm_builder::TakeRevenue;
 use xcm_executor::traits::WeightTrader;
+use zenlink_protocol::{AssetBalance, AssetId as ZenlinkAssetId};
+
+/// Weight bought with a single payment asset.
+struct ConsumedAsset {
+	asset_location: MultiLocation,
+	units_per_second: u128,
+	/// Used weight paid with this asset
+	weight: Weight,
+	/// Consumed amount of this asset
+	amount: u128,
+}
 
 /// Used as weight trader for foreign assets.
 ///
 /// In case foreigin asset is supported as payment asset, XCM execution time
 /// on-chain can be paid by the foreign asset, using the configured rate.
+///
+/// Consumption is tracked per payment asset, so programs buying execution with several
+/// assets are refunded in the asset they paid with. As a refund is a single asset, a refund of
+/// more weight than any asset paid for is capped to the weight paid with one asset.
 pub struct FixedRateOfForeignAsset<T: ExecutionPaymentRate, R: TakeRevenue> {
-	/// Total used weight
-	weight: Weight,
-	/// Total consumed assets
-	consumed: u128,
-	/// Asset Id (as MultiLocation) and units per second for payment
-	asset_location_and_units_per_second: Option<(MultiLocation, u128)>,
+	/// Consumed assets, in order of first use
+	consumed: Vec<ConsumedAsset>,
 	_pd: PhantomData<(T, R)>,
 }
 
 impl<T: ExecutionPaymentRate, R: TakeRevenue> WeightTrader for FixedRateOfForeignAsset<T, R> {
 	fn new() -> Self {
-		Self {
-			weight: Weight::zero(),
-			consumed: 0,
-			asset_location_and_units_per_second: None,
-			_pd: PhantomData,
-		}
+		Self { consumed: Vec::new(), _pd: PhantomData }
 	}
 
 	fn buy_weight(
@@ -62,21 +80,17 @@ impl<T: ExecutionPaymentRate, R: TakeRevenue> WeightTrader for FixedRateOfForeig
 						.checked_sub((asset_location, amount).into())
 						.map_err(|_| XcmError::TooExpensive)?;
 
-					self.weight = self.weight.saturating_add(weight);
-
-					// If there are multiple calls to `BuyExecution` but with different assets, we
-					// need to be able to handle that. Current primitive implementation will just
-					// keep total track of consumed asset for the FIRST consumed asset. Others will
-					// just be ignored when refund is concerned.
-					if let Some((old_asset_location, _)) = self.asset_location_and_units_per_second
-					{
-						if old_asset_location == asset_location {
-							self.consumed = self.consumed.saturating_add(amount);
-						}
-					} else {
-						self.consumed = self.consumed.saturating_add(amount);
-						self.asset_location_and_units_per_second =
-							Some((asset_location, units_per_second));
+					match self.consumed.iter_mut().find(|c| c.asset_location == asset_location) {
+						Some(consumed) => {
+							consumed.weight = consumed.weight.saturating_add(weight);
+							consumed.amount = consumed.amount.saturating_add(amount);
+						},
+						None => self.consumed.push(ConsumedAsset {
+							asset_location,
+							units_per_second,
+							weight,
+							amount,
+						}),
 					}
 
 					Ok(unused)
@@ -91,19 +105,24 @@ impl<T: ExecutionPaymentRate, R: TakeRevenue> WeightTrader for FixedRateOfForeig
 	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
 		log::trace!(target: "xcm::weight", "FixedRateOfForeignAsset::refund_weight weight: {:?}", weight);
 
-		if let Some((asset_location, units_per_second)) = self.asset_location_and_units_per_second {
-			let weight = weight.min(self.weight);
-			let amount = units_per_second.saturating_mul(weight.ref_time() as u128) /
-				(WEIGHT_REF_TIME_PER_SECOND as u128);
+		// Only a single asset can be refunded at once. The weight is refunded from the last asset
+		// used with enough weight left, otherwise from the one with the most weight left, whose
+		// weight is all refunded: the rest of `weight` isn't refunded then.
+		let consumed = match self.consumed.iter().rposition(|c| c.weight.all_gte(weight)) {
+			Some(index) => &mut self.consumed[index],
+			None => self.consumed.iter_mut().max_by_key(|c| c.weight.ref_time())?,
+		};
+		let weight = weight.min(consumed.weight);
+		// The purchases were rounded down one by one, the refund mustn't exceed their sum
+		let amount = (consumed.units_per_second.saturating_mul(weight.ref_time() as u128) /
+			(WEIGHT_REF_TIME_PER_SECOND as u128))
+			.min(consumed.amount);
 
-			self.weight = self.weight.saturating_sub(weight);
-			self.consumed = self.consumed.saturating_sub(amount);
+		consumed.weight = consumed.weight.saturating_sub(weight);
+		consumed.amount = consumed.amount.saturating_sub(amount);
 
-			if amount > 0 {
-				Some((asset_location, amount).into())
-			} else {
-				None
-			}
+		if amount > 0 {
+			Some((consumed.asset_location, amount).into())
 		} else {
 			None
 		}
@@ -112,10 +131,298 @@ impl<T: ExecutionPaymentRate, R: TakeRevenue> WeightTrader for FixedRateOfForeig
 
 impl<T: ExecutionPaymentRate, R: TakeRevenue> Drop for FixedRateOfForeignAsset<T, R> {
 	fn drop(&mut self) {
-		if let Some((asset_location, _)) = self.asset_location_and_units_per_second {
-			if self.consumed > 0 {
-				R::take_revenue((asset_location, self.consumed).into());
+		for consumed in self.consumed.iter().filter(|c| c.amount > 0) {
+			R::take_revenue((consumed.asset_location, consumed.amount).into());
+		}
+	}
+}
+
+/// Spot price of foreign assets from their Zenlink pool with the native token, as the amount
+/// worth the native fee from `WF` of one second of execution.
+///
+/// Pools whose native reserve is below `MinLiquidity` aren't sampled, as they are cheap to move.
+/// The spot price is only meant to be sampled into the time-weighted price of `xc-asset-config`,
+/// which bounds the deviation of each sample from the price so far.
+pub struct ZenlinkSpotPrice<Z, AssetId, L, C, NativeAssetId, WF, MinLiquidity>(
+	PhantomData<(Z, AssetId, L, C, NativeAssetId, WF, MinLiquidity)>,
+);
+
+impl<Z, AssetId, L, C, NativeAssetId, WF, MinLiquidity> AssetSpotPrice
+	for ZenlinkSpotPrice<Z, AssetId, L, C, NativeAssetId, WF, MinLiquidity>
+where
+	Z: zenlink_protocol::Config<AssetId = ZenlinkAssetId>,
+	L: XcAssetLocation<AssetId>,
+	C: Convert<AssetId, Option<ZenlinkAssetId>>,
+	NativeAssetId: Get<AssetId>,
+	WF: WeightToFee,
+	MinLiquidity: Get<AssetBalance>,
+{
+	fn spot_units_per_second(asset_location: &MultiLocation) -> Option<u128> {
+		let foreign_zen_id = C::convert(L::get_asset_id(*asset_location)?)?;
+		let native_zen_id = C::convert(NativeAssetId::get())?;
+
+		let pair =
+			zenlink_protocol::Pallet::<Z>::get_pair_by_asset_id(foreign_zen_id, native_zen_id)?;
+		let (foreign_reserve, native_reserve) = if pair.asset_0 == foreign_zen_id {
+			(pair.reserve_0, pair.reserve_1)
+		} else {
+			(pair.reserve_1, pair.reserve_0)
+		};
+
+		let native_fee =
+			WF::weight_to_fee(&Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND, 0)).saturated_into();
+		let units_per_second =
+			pool_units_per_second(native_fee, foreign_reserve, native_reserve, MinLiquidity::get());
+		if units_per_second.is_none() {
+			log::debug!(
+				target: "xcm::weight",
+				"ZenlinkSpotPrice: no price for {:?}, foreign_reserve: {:?}, native_reserve: {:?}",
+				asset_location, foreign_reserve, native_reserve,
+			);
+		}
+		units_per_second
+	}
+}
+
+/// The amount of foreign asset worth `native_fee` at the spot price of a pool, if the native
+/// reserve of the pool is at least `min_liquidity`.
+fn pool_units_per_second(
+	native_fee: u128,
+	foreign_reserve: AssetBalance,
+	native_reserve: AssetBalance,
+	min_liquidity: AssetBalance,
+) -> Option<u128> {
+	if foreign_reserve.is_zero() || native_reserve.is_zero() || native_reserve < min_liquidity {
+		return None
+	}
+	multiply_by_rational_with_rounding(native_fee, foreign_reserve, native_reserve, Rounding::Up)
+}
+
+/// Creates and destroys the local assets of foreign assets in `pallet-assets`.
+///
+/// Assets are created without deposits and owned by `Owner`.
+pub struct ForeignAssetsRegistrar<R, Owner>(PhantomData<(R, Owner)>);
+
+impl<R, Owner> ForeignAssetRegistrar<R::AssetId, R::Balance> for ForeignAssetsRegistrar<R, Owner>
+where
+	R: pallet_assets::Config,
+	Owner: Get<R::AccountId>,
+{
+	fn create_asset(
+		asset_id: R::AssetId,
+		metadata: &ForeignAssetMetadata<R::Balance>,
+	) -> DispatchResult {
+		<pallet_assets::Pallet<R> as fungibles::Create<R::AccountId>>::create(
+			asset_id.clone(),
+			Owner::get(),
+			metadata.is_sufficient,
+			metadata.min_balance,
+		)?;
+		pallet_assets::Pallet::<R>::force_set_metadata(
+			frame_system::RawOrigin::Root.into(),
+			asset_id.into(),
+			metadata.name.clone(),
+			metadata.symbol.clone(),
+			metadata.decimals,
+			false,
+		)
+	}
+
+	fn destroy_asset(asset_id: R::AssetId) -> DispatchResult {
+		type Assets<R> = pallet_assets::Pallet<R>;
+		let limit = R::RemoveItemsLimit::get();
+
+		<Assets<R> as fungibles::Destroy<R::AccountId>>::start_destroy(asset_id.clone(), None)?;
+		<Assets<R> as fungibles::Destroy<R::AccountId>>::destroy_accounts(asset_id.clone(), limit)?;
+		<Assets<R> as fungibles::Destroy<R::AccountId>>::destroy_approvals(
+			asset_id.clone(),
+			limit,
+		)?;
+		match <Assets<R> as fungibles::Destroy<R::AccountId>>::finish_destroy(asset_id) {
+			// An asset with more holders than `RemoveItemsLimit` stays in the destroying state,
+			// anyone can finish its destruction through `pallet-assets` afterwards.
+			Err(err) if err == pallet_assets::Error::<R>::InUse.into() => Ok(()),
+			result => result,
+		}
+	}
+
+	fn destroy_asset_weight() -> Weight {
+		use pallet_assets::WeightInfo;
+		let limit = R::RemoveItemsLimit::get();
+
+		R::WeightInfo::start_destroy()
+			.saturating_add(R::WeightInfo::destroy_accounts(limit))
+			.saturating_add(R::WeightInfo::destroy_approvals(limit))
+			.saturating_add(R::WeightInfo::finish_destroy())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::RefCell;
+	use xcm::latest::prelude::{Here, Parachain, X1};
+
+	thread_local! {
+		static REVENUE: RefCell<Vec<MultiAsset>> = RefCell::new(Vec::new());
+	}
+
+	const RELAY: MultiLocation = MultiLocation { parents: 1, interior: Here };
+	const SIBLING: MultiLocation = MultiLocation { parents: 1, interior: X1(Parachain(1000)) };
+
+	/// One unit per ref time for the relay asset, three for the sibling asset.
+	pub struct PaymentRate;
+	impl ExecutionPaymentRate for PaymentRate {
+		fn get_units_per_second(asset_location: MultiLocation) -> Option<u128> {
+			if asset_location == RELAY {
+				Some(WEIGHT_REF_TIME_PER_SECOND as u128)
+			} else if asset_location == SIBLING {
+				Some(3 * WEIGHT_REF_TIME_PER_SECOND as u128)
+			} else {
+				None
 			}
 		}
 	}
+
+	/// Two units per ref time for the relay asset, as if priced from its pool.
+	pub struct PoolRate;
+	impl ExecutionPaymentRate for PoolRate {
+		fn get_units_per_second(asset_location: MultiLocation) -> Option<u128> {
+			(asset_location == RELAY).then_some(2 * WEIGHT_REF_TIME_PER_SECOND as u128)
+		}
+	}
+
+	/// Half a unit per ref time for the relay asset.
+	pub struct HalfRate;
+	impl ExecutionPaymentRate for HalfRate {
+		fn get_units_per_second(asset_location: MultiLocation) -> Option<u128> {
+			(asset_location == RELAY).then_some(WEIGHT_REF_TIME_PER_SECOND as u128 / 2)
+		}
+	}
+
+	pub struct Revenue;
+	impl TakeRevenue for Revenue {
+		fn take_revenue(revenue: MultiAsset) {
+			REVENUE.with(|r| r.borrow_mut().push(revenue));
+		}
+	}
+
+	type Trader = FixedRateOfForeignAsset<PaymentRate, Revenue>;
+
+	fn payment(asset_location: MultiLocation, amount: u128) -> xcm_executor::Assets {
+		MultiAsset::from((asset_location, amount)).into()
+	}
+
+	#[test]
+	fn refunds_are_tracked_per_asset() {
+		REVENUE.with(|r| r.borrow_mut().clear());
+		let mut trader = Trader::new();
+
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(100, 0), payment(RELAY, 1_000)),
+			Ok(payment(RELAY, 900))
+		);
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(200, 0), payment(SIBLING, 1_000)),
+			Ok(payment(SIBLING, 400))
+		);
+
+		// Refunds start with the last asset, at its own rate
+		assert_eq!(trader.refund_weight(Weight::from_parts(150, 0)), Some((SIBLING, 450).into()));
+		// Then use the last asset with enough weight left to refund all of the weight
+		assert_eq!(trader.refund_weight(Weight::from_parts(100, 0)), Some((RELAY, 100).into()));
+		assert_eq!(trader.refund_weight(Weight::from_parts(40, 0)), Some((SIBLING, 120).into()));
+		// Without such an asset, only the weight left of a single asset can be refunded
+		assert_eq!(trader.refund_weight(Weight::from_parts(20, 0)), Some((SIBLING, 30).into()));
+		assert_eq!(trader.refund_weight(Weight::from_parts(20, 0)), None);
+
+		drop(trader);
+		REVENUE.with(|r| assert!(r.borrow().is_empty()));
+	}
+
+	#[test]
+	fn refunds_do_not_exceed_the_rounded_down_purchases() {
+		REVENUE.with(|r| r.borrow_mut().clear());
+		let mut trader = FixedRateOfForeignAsset::<HalfRate, Revenue>::new();
+
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(3, 0), payment(RELAY, 1_000)),
+			Ok(payment(RELAY, 999))
+		);
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(3, 0), payment(RELAY, 1_000)),
+			Ok(payment(RELAY, 999))
+		);
+
+		// The weight of both purchases is worth 3 at once, but only 2 were charged
+		assert_eq!(trader.refund_weight(Weight::from_parts(6, 0)), Some((RELAY, 2).into()));
+		assert_eq!(trader.refund_weight(Weight::from_parts(6, 0)), None);
+
+		drop(trader);
+		REVENUE.with(|r| assert!(r.borrow().is_empty()));
+	}
+
+	#[test]
+	fn every_consumed_asset_is_taken_as_revenue() {
+		REVENUE.with(|r| r.borrow_mut().clear());
+		let mut trader = Trader::new();
+
+		assert!(trader.buy_weight(Weight::from_parts(100, 0), payment(RELAY, 1_000)).is_ok());
+		assert!(trader.buy_weight(Weight::from_parts(10, 0), payment(SIBLING, 1_000)).is_ok());
+		assert!(trader.buy_weight(Weight::from_parts(50, 0), payment(RELAY, 1_000)).is_ok());
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(10, 0), payment(MultiLocation::here(), 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+
+		assert_eq!(trader.refund_weight(Weight::from_parts(5, 0)), Some((SIBLING, 15).into()));
+
+		drop(trader);
+		REVENUE.with(|r| {
+			assert_eq!(
+				*r.borrow(),
+				vec![MultiAsset::from((RELAY, 150)), MultiAsset::from((SIBLING, 15))]
+			)
+		});
+	}
+
+	#[test]
+	fn pool_rate_is_used_before_the_units_per_second() {
+		REVENUE.with(|r| r.borrow_mut().clear());
+		let mut trader = <(
+			FixedRateOfForeignAsset<PoolRate, Revenue>,
+			FixedRateOfForeignAsset<PaymentRate, Revenue>,
+		)>::new();
+
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(100, 0), payment(RELAY, 1_000)),
+			Ok(payment(RELAY, 800))
+		);
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(100, 0), payment(SIBLING, 1_000)),
+			Ok(payment(SIBLING, 700))
+		);
+
+		// The relay asset is refunded at its pool rate
+		assert_eq!(trader.refund_weight(Weight::from_parts(50, 0)), Some((RELAY, 100).into()));
+
+		drop(trader);
+		REVENUE.with(|r| {
+			assert_eq!(
+				*r.borrow(),
+				vec![MultiAsset::from((RELAY, 100)), MultiAsset::from((SIBLING, 300))]
+			)
+		});
+	}
+
+	#[test]
+	fn pool_units_per_second_is_the_spot_price() {
+		assert_eq!(pool_units_per_second(1_000, 500, 2_000, 2_000), Some(250));
+		// rounded up
+		assert_eq!(pool_units_per_second(1, 1, 3, 0), Some(1));
+		// not enough liquidity
+		assert_eq!(pool_units_per_second(1_000, 500, 1_999, 2_000), None);
+		assert_eq!(pool_units_per_second(1_000, 0, 2_000, 0), None);
+		assert_eq!(pool_units_per_second(1_000, 500, 0, 0), None);
+	}
 }
@@ -0,0 +1,91 @@
+use super::*;
+use ethereum::{LegacyTransaction, TransactionAction, TransactionSignature};
+use fp_self_contained::SelfContainedCall;
+use frame_support::{
+	assert_ok,
+	traits::fungibles::{Create, Inspect, Mutate},
+};
+use pallet_evm::{AddressMapping, FeeCalculator};
+use zenlink_protocol::ExportZenlink;
+
+const LP: AccountId = AccountId::new([1u8; 32]);
+const SENDER: H160 = H160::repeat_byte(2);
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn zenlink_id(asset_id: StorageAssetId) -> ZenlinkAssetId {
+	AssetIdToZenlinkId::<SelfParaId>::convert(asset_id).unwrap()
+}
+
+fn transact(gas_price: U256) -> pallet_ethereum::Call<Runtime> {
+	let transaction = LegacyTransaction {
+		nonce: U256::zero(),
+		gas_price,
+		gas_limit: U256::from(21_000),
+		action: TransactionAction::Call(H160::repeat_byte(3)),
+		value: U256::zero(),
+		input: vec![],
+		signature: TransactionSignature::new(
+			27,
+			H256::from_low_u64_be(1),
+			H256::from_low_u64_be(1),
+		)
+		.unwrap(),
+	};
+	pallet_ethereum::Call::transact {
+		transaction: pallet_ethereum::Transaction::Legacy(transaction),
+	}
+}
+
+#[test]
+fn evm_transaction_fee_can_be_paid_in_local_accepted_currency() {
+	new_test_ext().execute_with(|| {
+		let token: StorageAssetId = PeaqAssetId::Token(1).try_into().unwrap();
+		let sender = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(SENDER);
+
+		// Provide a pool between the native and the accepted currency.
+		assert_ok!(<Assets as Create<AccountId>>::create(token, LP, true, 1));
+		assert_ok!(<Assets as Mutate<AccountId>>::mint_into(token, &LP, 1_000 * DOLLARS));
+		Balances::make_free_balance_be(&LP, 1_001 * DOLLARS);
+		assert_ok!(ZenlinkProtocol::create_pair(
+			RuntimeOrigin::root(),
+			zenlink_id(GetNativeAssetId::get()),
+			zenlink_id(token),
+		));
+		assert_ok!(ZenlinkProtocol::inner_add_liquidity(
+			&LP,
+			zenlink_id(GetNativeAssetId::get()),
+			zenlink_id(token),
+			1_000 * DOLLARS,
+			1_000 * DOLLARS,
+			0,
+			0,
+		));
+
+		// The sender only holds the accepted currency.
+		assert_ok!(<Assets as Mutate<AccountId>>::mint_into(token, &sender, 10 * DOLLARS));
+		assert_eq!(Balances::free_balance(&sender), 0);
+
+		let (base_fee, _) = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+		let call = transact(base_fee);
+		let dispatch_info = call.get_dispatch_info();
+
+		// Frontier alone only checks the native balance.
+		assert!(matches!(
+			call.validate_self_contained(&SENDER, &dispatch_info, 0),
+			Some(Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)))
+		));
+		let call = RuntimeCall::Ethereum(call);
+		assert!(matches!(call.validate_self_contained(&SENDER, &dispatch_info, 0), Some(Ok(_))));
+		assert_eq!(call.pre_dispatch_self_contained(&SENDER, &dispatch_info, 0), Some(Ok(())));
+
+		// The swap is only simulated for the checks.
+		assert_eq!(Balances::free_balance(&sender), 0);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(token, &sender), 10 * DOLLARS);
+	});
+}
@@ -1,21 +1,27 @@
 use super::{
 	AccountId, AllPalletsWithSystem, Assets, Balance, Balances, BlockReward, GetNativeAssetId,
-	ParachainInfo, ParachainSystem, PeaqPotAccount, PolkadotXcm, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeOrigin, StorageAssetId, WeightToFee, XcAssetConfig, XcmpQueue,
+	ParachainInfo, ParachainSystem, PeaqPotAccount, PeaqTreasuryAccount, PolkadotXcm, Runtime,
+	RuntimeCall, RuntimeEvent, RuntimeOrigin, SelfParaId, StorageAssetId, WeightToFee,
+	XcAssetConfig, XcmpQueue,
 };
 use frame_support::{
 	dispatch::Weight,
 	match_types, parameter_types,
-	traits::{fungibles, ContainsPair, Everything, Nothing},
+	traits::{fungibles, Everything, Nothing},
+	weights::constants::RocksDbWeight,
 };
 use frame_system::EnsureRoot;
 use orml_traits::location::{RelativeReserveProvider, Reserve};
 use orml_xcm_support::DisabledParachainFee;
 use pallet_xcm::XcmPassthrough;
+use peaq_primitives_xcm::AssetIdToZenlinkId;
 use polkadot_parachain::primitives::Sibling;
-use runtime_common::{AccountIdToMultiLocation, FixedRateOfForeignAsset};
-use sp_runtime::traits::ConstU32;
-use xc_asset_config::MultiLocationToAssetId;
+use runtime_common::{
+	AccountIdToMultiLocation, FixedRateOfForeignAsset, ForeignAssetsRegistrar, ZenlinkSpotPrice,
+	DOLLARS,
+};
+use sp_runtime::{traits::ConstU32, Perbill};
+use xc_asset_config::{MultiLocationToAssetId, PoolPaymentRate, TrustedReserves};
 use xcm::latest::{prelude::*, MultiAsset};
 use xcm_builder::{
 	AccountId32Aliases,
@@ -196,8 +202,34 @@ pub type PeaqXcmFungibleFeeHandler = XcmFungibleFeeHandler<
 	PeaqPotAccount,
 >;
 
+parameter_types! {
+	/// Minimum native reserve of a Zenlink pool to sample its price.
+	pub const XcmPoolMinLiquidity: Balance = 1_000 * DOLLARS;
+	/// Weight of each block's spot price in the time-weighted pool price.
+	pub const XcmPoolPriceSmoothing: Perbill = Perbill::from_percent(5);
+	/// Maximum deviation of a spot price sample from the time-weighted pool price.
+	pub const XcmPoolMaxPriceDeviation: Perbill = Perbill::from_percent(20);
+	/// Weight of a Zenlink spot price: the asset id of the location, the pair, its liquidity
+	/// asset and supply, and both reserves.
+	pub XcmPoolSpotPriceWeight: Weight =
+		Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().reads(6));
+}
+
+/// Spot price of foreign assets from their Zenlink pool with the native token.
+pub type XcmZenlinkSpotPrice = ZenlinkSpotPrice<
+	Runtime,
+	StorageAssetId,
+	XcAssetConfig,
+	AssetIdToZenlinkId<SelfParaId>,
+	GetNativeAssetId,
+	WeightToFee,
+	XcmPoolMinLiquidity,
+>;
+
 pub type Trader = (
 	UsingComponents<WeightToFee, SelfReserveLocation, AccountId, Balances, BlockReward>,
+	// Foreign assets opted in to pool pricing, while their pool price is usable
+	FixedRateOfForeignAsset<PoolPaymentRate<Runtime>, PeaqXcmFungibleFeeHandler>,
 	FixedRateOfForeignAsset<XcAssetConfig, PeaqXcmFungibleFeeHandler>,
 );
 
@@ -212,36 +244,6 @@ pub type Barrier = (
 	AllowSubscriptionsFrom<Everything>,
 );
 
-/// Used to determine whether the cross-chain asset is coming from a trusted reserve or not
-///
-/// Basically, we trust any cross-chain asset from any location to act as a reserve since
-/// in order to support the xc-asset, we need to first register it in the `XcAssetConfig` pallet.
-pub struct ReserveAssetFilter;
-impl ContainsPair<MultiAsset, MultiLocation> for ReserveAssetFilter {
-	fn contains(asset: &MultiAsset, origin: &MultiLocation) -> bool {
-		// We assume that relay chain and sibling parachain assets are trusted reserves for their
-		// assets
-		let reserve_location = if let Concrete(location) = &asset.id {
-			match (location.parents, location.first_interior()) {
-				// sibling parachain
-				(1, Some(Parachain(id))) => Some(MultiLocation::new(1, X1(Parachain(*id)))),
-				// relay chain
-				(1, _) => Some(MultiLocation::parent()),
-				_ => None,
-			}
-		} else {
-			None
-		};
-
-		log::trace!("show origin: {:?} and reserve_location: {:?}", origin, reserve_location);
-		if let Some(ref reserve) = reserve_location {
-			origin == reserve
-		} else {
-			false
-		}
-	}
-}
-
 pub type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 
 pub struct XcmConfig;
@@ -252,7 +254,7 @@ impl xcm_executor::Config for XcmConfig {
 	type XcmSender = XcmRouter;
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToCallOrigin;
-	type IsReserve = ReserveAssetFilter;
+	type IsReserve = TrustedReserves<Runtime>;
 	// type IsReserve = Everything;
 	type IsTeleporter = ();
 	type UniversalLocation = UniversalLocation;
@@ -399,5 +401,13 @@ impl xc_asset_config::Config for Runtime {
 	type NativeAssetId = GetNativeAssetId;
 	type NativeAssetLocation = SelfReserveLocation;
 	type ManagerOrigin = EnsureRoot<AccountId>;
+	type Balance = Balance;
+	type ForeignAssetRegistrar = ForeignAssetsRegistrar<Runtime, PeaqTreasuryAccount>;
+	type SpotPrice = XcmZenlinkSpotPrice;
+	type SpotPriceWeight = XcmPoolSpotPriceWeight;
+	type MaxPoolPricedAssets = ConstU32<16>;
+	type PoolPriceSmoothing = XcmPoolPriceSmoothing;
+	type MaxPoolPriceAge = ConstU32<10>;
+	type MaxPoolPriceDeviation = XcmPoolMaxPriceDeviation;
 	type WeightInfo = xc_asset_config::weights::SubstrateWeight<Self>;
 }
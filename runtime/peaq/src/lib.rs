@@ -43,7 +43,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, ConvertInto,
 		DispatchInfoOf, Dispatchable, OpaqueKeys, PostDispatchInfoOf, SaturatedConversion,
-		UniqueSaturatedInto,
+		UniqueSaturatedInto, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
@@ -109,10 +109,11 @@ use peaq_primitives_xcm::EVMAddressToAssetId;
 pub use precompiles::EVMAssetPrefix;
 
 use runtime_common::{
+	pre_dispatch_self_contained_with_fee_swap, validate_self_contained_with_fee_swap,
 	LocalAssetAdaptor, OperationalFeeMultiplier, PeaqAssetZenlinkLpGenerate,
-	PeaqMultiCurrenciesOnChargeTransaction, PeaqMultiCurrenciesPaymentConvert,
-	PeaqMultiCurrenciesWrapper, PeaqNativeCurrencyWrapper, TransactionByteFee, CENTS, DOLLARS,
-	MILLICENTS,
+	PeaqMultiCurrenciesOnChargeEVMTransaction, PeaqMultiCurrenciesOnChargeTransaction,
+	PeaqMultiCurrenciesPaymentConvert, PeaqMultiCurrenciesWrapper, PeaqNativeCurrencyWrapper,
+	TransactionByteFee, CENTS, DOLLARS, MILLICENTS,
 };
 
 /// An index to a block.
@@ -453,6 +454,7 @@ parameter_types! {
 	pub PcpcLocalAccepted: Vec<StorageAssetId> = vec![
 		PeaqAssetId::Token(1).try_into().unwrap(),
 	];
+	pub const PcpcMaxRefundSlippage: Perbill = Perbill::from_percent(1);
 }
 
 pub struct PeaqCPC;
@@ -465,6 +467,7 @@ impl PeaqMultiCurrenciesPaymentConvert for PeaqCPC {
 	type ExistentialDeposit = ExistentialDeposit;
 	type NativeAssetId = GetNativeAssetId;
 	type LocalAcceptedIds = PcpcLocalAccepted;
+	type MaxRefundSlippage = PcpcMaxRefundSlippage;
 	type AssetId = StorageAssetId;
 	type AssetIdToZenlinkId = AssetIdToZenlinkId<SelfParaId>;
 }
@@ -649,7 +652,8 @@ impl pallet_evm::Config for Runtime {
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = EvmChainId;
 	type BlockGasLimit = BlockGasLimit;
-	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, BlockReward>;
+	type OnChargeTransaction =
+		PeaqMultiCurrenciesOnChargeEVMTransaction<Balances, BlockReward, PeaqCPC>;
 	type OnCreate = ();
 	type FindAuthor = FindAuthorTruncated<Aura>;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
@@ -788,6 +792,18 @@ pub mod staking {
 			pub const MaxCollatorCandidates: u32 = 32;
 			/// Maximum number of concurrent requests to unlock unstaked balance
 			pub const MaxUnstakeRequests: u32 = 10;
+			/// Collators can take at most 30% commission off their delegators' rewards
+			pub const MaxCollatorCommission: Perbill = Perbill::from_percent(30);
+			/// Collators which miss a whole session lose 1% of their stake
+			pub const OfflineSlashFraction: Perbill = Perbill::from_percent(1);
+			/// Only the collators themselves are slashed, not their delegators
+			pub const SlashDelegators: bool = false;
+			/// Slashes can be cancelled by governance for one day (6 rounds/sessions)
+			pub const SlashDeferDuration: u32 = 6;
+			/// Offline collators are not selected for one day (6 rounds/sessions)
+			pub const OffenceDeselectDuration: u32 = 6;
+			/// Rewards of the last one month (180 rounds/sessions) are kept per account
+			pub const MaxRewardHistory: u32 = 180;
 	}
 }
 
@@ -812,6 +828,17 @@ impl parachain_staking::Config for Runtime {
 	type MinDelegation = staking::MinDelegatorStake;
 	type MinDelegatorStake = staking::MinDelegatorStake;
 	type MaxUnstakeRequests = staking::MaxUnstakeRequests;
+	type MaxCollatorCommission = staking::MaxCollatorCommission;
+	type OfflineSlashFraction = staking::OfflineSlashFraction;
+	type SlashDelegators = staking::SlashDelegators;
+	type SlashDeferDuration = staking::SlashDeferDuration;
+	type OffenceDeselectDuration = staking::OffenceDeselectDuration;
+	type MaxRewardHistory = staking::MaxRewardHistory;
+	type SlashCancelOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+	>;
+	type Slash = ToTreasuryPot;
 
 	type WeightInfo = parachain_staking::weights::WeightInfo<Runtime>;
 }
@@ -828,7 +855,15 @@ macro_rules! impl_to_pot_adapter {
 
 			fn on_nonzero_unbalanced(amount: $negbal) {
 				let pot = $pot::get().into_account_truncating();
-				Balances::resolve_creating(&pot, amount);
+				// an amount below the existential deposit cannot create the pot and would be
+				// burned
+				if amount.peek() < Balances::minimum_balance() &&
+					Balances::total_balance(&pot).is_zero()
+				{
+					ToTreasuryPot::on_unbalanced(amount);
+				} else {
+					Balances::resolve_creating(&pot, amount);
+				}
 			}
 		}
 	};
@@ -851,34 +886,24 @@ impl OnUnbalanced<NegativeImbalance> for ToTreasuryPot {
 impl pallet_block_reward::Config for Runtime {
 	type Currency = Balances;
 	type BeneficiaryPayout = BeneficiaryPayout;
+	type MaxBeneficiaries = ConstU32<16>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_block_reward::weights::WeightInfo<Runtime>;
 }
 
 pub struct BeneficiaryPayout();
 impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance> for BeneficiaryPayout {
-	fn treasury(reward: NegativeImbalance) {
-		ToTreasuryPot::on_unbalanced(reward);
-	}
-
-	fn collators_delegators(reward: NegativeImbalance) {
-		ToStakingPot::on_unbalanced(reward);
-	}
-
-	fn coretime(reward: NegativeImbalance) {
-		ToCoreTimePot::on_unbalanced(reward);
-	}
-
-	fn subsidization_pool(reward: NegativeImbalance) {
-		ToSubsidizationPot::on_unbalanced(reward);
-	}
-
-	fn depin_staking(reward: NegativeImbalance) {
-		ToDepinStakingPot::on_unbalanced(reward);
-	}
-
-	fn depin_incentivization(reward: NegativeImbalance) {
-		ToDepinIncentivizationPot::on_unbalanced(reward);
+	fn payout(beneficiary: &pallet_block_reward::BeneficiaryId, reward: NegativeImbalance) {
+		match *beneficiary {
+			pallet_block_reward::COLLATORS_DELEGATORS => ToStakingPot::on_unbalanced(reward),
+			pallet_block_reward::CORETIME => ToCoreTimePot::on_unbalanced(reward),
+			pallet_block_reward::SUBSIDIZATION_POOL => ToSubsidizationPot::on_unbalanced(reward),
+			pallet_block_reward::DEPIN_STAKING => ToDepinStakingPot::on_unbalanced(reward),
+			pallet_block_reward::DEPIN_INCENTIVIZATION =>
+				ToDepinIncentivizationPot::on_unbalanced(reward),
+			// the treasury and unknown beneficiaries
+			_ => ToTreasuryPot::on_unbalanced(reward),
+		}
 	}
 }
 
@@ -993,6 +1018,11 @@ impl inflation_manager::Config for Runtime {
 	type WeightInfo = inflation_manager::weights::WeightInfo<Runtime>;
 	type DoInitializeAt = InitializeInflationAt;
 	type BlockRewardBeforeInitialize = BlockRewardBeforeInitialize;
+	type UpdateOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+	>;
+	type UnixTime = Timestamp;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -1081,7 +1111,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	(),
+	xc_asset_config::migrations::MigrationAssetReserves<Runtime>,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1136,7 +1166,12 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	) -> Option<TransactionValidity> {
 		match self {
 			RuntimeCall::Ethereum(call) =>
-				call.validate_self_contained(signed_info, dispatch_info, len),
+				validate_self_contained_with_fee_swap::<Runtime, PeaqCPC>(
+					call,
+					signed_info,
+					dispatch_info,
+					len,
+				),
 			_ => None,
 		}
 	}
@@ -1148,8 +1183,10 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 		len: usize,
 	) -> Option<Result<(), TransactionValidityError>> {
 		match self {
-			RuntimeCall::Ethereum(call) =>
-				call.pre_dispatch_self_contained(info, dispatch_info, len),
+			RuntimeCall::Ethereum(call) => pre_dispatch_self_contained_with_fee_swap::<
+				Runtime,
+				PeaqCPC,
+			>(call, info, dispatch_info, len),
 			_ => None,
 		}
 	}
@@ -1864,6 +1901,84 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl parachain_staking_runtime_api::ParachainStakingApi<Block, AccountId, Balance> for Runtime {
+		fn get_projected_rewards(account: AccountId) -> Balance {
+			ParachainStaking::get_projected_rewards(&account)
+		}
+
+		fn get_staking_rates() -> parachain_staking_runtime_api::StakingRates {
+			ParachainStaking::get_staking_rates()
+		}
+
+		fn get_collator_blocks() -> Vec<(AccountId, u32)> {
+			ParachainStaking::get_collator_blocks()
+		}
+
+		fn get_claimable_rewards(account: AccountId) -> Balance {
+			ParachainStaking::get_claimable_rewards(&account)
+		}
+
+		fn get_reward_history(
+			account: AccountId,
+		) -> Vec<parachain_staking_runtime_api::RewardRecord<AccountId, Balance>> {
+			ParachainStaking::get_reward_history(&account)
+		}
+	}
+
+	impl inflation_manager_runtime_api::InflationManagerApi<Block, BlockNumber, Balance> for Runtime {
+		fn get_inflation_parameters() -> inflation_manager_runtime_api::InflationParameters {
+			InflationManager::inflation_parameters()
+		}
+
+		fn get_current_year() -> u128 {
+			InflationManager::current_year()
+		}
+
+		fn get_block_rewards() -> Balance {
+			InflationManager::block_rewards()
+		}
+
+		fn get_next_recalculation_at() -> BlockNumber {
+			InflationManager::do_recalculation_at()
+		}
+
+		fn get_issuance_projection(
+			years: u32,
+		) -> Vec<inflation_manager_runtime_api::IssuanceProjection<Balance>> {
+			InflationManager::issuance_projection(years)
+		}
+	}
+
+	impl address_unification_runtime_api::AddressUnificationApi<Block, AccountId> for Runtime {
+		fn get_account_id(
+			evm_address: H160,
+		) -> address_unification_runtime_api::AccountMapping<AccountId> {
+			AddressUnification::account_mapping_of_evm_address(evm_address)
+		}
+
+		fn get_evm_address(
+			account_id: AccountId,
+		) -> address_unification_runtime_api::AccountMapping<AccountId> {
+			AddressUnification::account_mapping_of_account_id(account_id)
+		}
+
+		fn get_claim_data(
+			account_id: AccountId,
+		) -> address_unification_runtime_api::Eip712ClaimData {
+			AddressUnification::eip712_claim_data(&account_id)
+		}
+	}
+
+	impl xc_asset_config_runtime_api::XcAssetConfigApi<Block, StorageAssetId> for Runtime {
+		fn asset_reserves() -> Vec<(StorageAssetId, xcm::VersionedMultiLocation)> {
+			XcAssetConfig::asset_reserves()
+		}
+
+		fn asset_reserve(asset_id: StorageAssetId) -> Option<xcm::VersionedMultiLocation> {
+			XcAssetConfig::asset_reserve(asset_id)
+		}
+	}
+
 	impl peaq_pallet_storage_runtime_api::PeaqStorageApi<Block, AccountId> for Runtime{
 		fn read(did_account: AccountId, item_type: Vec<u8>) -> Option<Vec<u8>>{
 			PeaqStorage::read(&did_account, &item_type)
@@ -2072,11 +2187,23 @@ impl pallet_assets::Config for Runtime {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub const UnlinkCooldown: BlockNumber = DAYS;
+	pub const MaxMergedAssets: u32 = 16;
+	pub const MaxSecondaryEvmAddresses: u32 = 16;
+}
+
 impl address_unification::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type Assets = Assets;
+	type MaxMergedAssets = MaxMergedAssets;
 	type OriginAddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type OffchainSignature = Signature;
+	type OffchainPublic = peaq_primitives_xcm::AccountPublic;
 	type ChainId = EvmChainId;
+	type MaxSecondaryEvmAddresses = MaxSecondaryEvmAddresses;
+	type UnlinkCooldown = UnlinkCooldown;
 	type WeightInfo = address_unification::weights::SubstrateWeight<Runtime>;
 }
 